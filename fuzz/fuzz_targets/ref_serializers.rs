@@ -0,0 +1,134 @@
+#![no_main]
+
+// Drives RefListSerializer and RefDictSerializer with arbitrary call sequences
+// and checks that any output produced without errors parses back.
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use sfv::{Decimal, Parser, RefBareItem, RefDictSerializer, RefListSerializer};
+
+type SFVResult<T> = Result<T, &'static str>;
+
+#[derive(Arbitrary, Debug)]
+enum Value {
+    Integer(i64),
+    // Scaled by 1000, as sf-decimal has up to three fractional digits.
+    Decimal(i64),
+    String(String),
+    Token(String),
+    ByteSeq(Vec<u8>),
+    Boolean(bool),
+}
+
+impl Value {
+    fn to_ref_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(match self {
+            Value::Integer(val) => RefBareItem::Integer(*val),
+            Value::Decimal(val) => {
+                RefBareItem::Decimal(Decimal::try_new(*val, 3).map_err(|_| "decimal out of range")?)
+            }
+            Value::String(val) => RefBareItem::String(val),
+            Value::Token(val) => RefBareItem::Token(val),
+            Value::ByteSeq(val) => RefBareItem::ByteSeq(val),
+            Value::Boolean(val) => RefBareItem::Boolean(*val),
+        })
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum InnerListCall {
+    BareItem(Value),
+    Parameter(String, Value),
+}
+
+#[derive(Arbitrary, Debug)]
+enum ListCall {
+    BareItem(Value),
+    Parameter(String, Value),
+    InnerList(Vec<InnerListCall>),
+}
+
+#[derive(Arbitrary, Debug)]
+enum DictCall {
+    BareItemMember(String, Value),
+    Parameter(String, Value),
+    InnerList(String, Vec<InnerListCall>),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Input {
+    List(Vec<ListCall>),
+    Dict(Vec<DictCall>),
+}
+
+fn serialize_list(calls: &[ListCall], output: &mut String) -> SFVResult<()> {
+    let mut ser = RefListSerializer::new(output);
+    for call in calls {
+        ser = match call {
+            ListCall::BareItem(value) => ser.bare_item(&value.to_ref_bare_item()?)?,
+            ListCall::Parameter(name, value) => ser.parameter(name, &value.to_ref_bare_item()?)?,
+            ListCall::InnerList(inner_calls) => {
+                let mut inner_ser = ser.open_inner_list();
+                for inner_call in inner_calls {
+                    inner_ser = match inner_call {
+                        InnerListCall::BareItem(value) => {
+                            inner_ser.inner_list_bare_item(&value.to_ref_bare_item()?)?
+                        }
+                        InnerListCall::Parameter(name, value) => {
+                            inner_ser.inner_list_parameter(name, &value.to_ref_bare_item()?)?
+                        }
+                    };
+                }
+                inner_ser.close_inner_list()
+            }
+        };
+    }
+    Ok(())
+}
+
+fn serialize_dict(calls: &[DictCall], output: &mut String) -> SFVResult<()> {
+    let mut ser = RefDictSerializer::new(output);
+    for call in calls {
+        ser = match call {
+            DictCall::BareItemMember(name, value) => {
+                ser.bare_item_member(name, &value.to_ref_bare_item()?)?
+            }
+            DictCall::Parameter(name, value) => ser.parameter(name, &value.to_ref_bare_item()?)?,
+            DictCall::InnerList(name, inner_calls) => {
+                let mut inner_ser = ser.open_inner_list(name)?;
+                for inner_call in inner_calls {
+                    inner_ser = match inner_call {
+                        InnerListCall::BareItem(value) => {
+                            inner_ser.inner_list_bare_item(&value.to_ref_bare_item()?)?
+                        }
+                        InnerListCall::Parameter(name, value) => {
+                            inner_ser.inner_list_parameter(name, &value.to_ref_bare_item()?)?
+                        }
+                    };
+                }
+                inner_ser.close_inner_list()
+            }
+        };
+    }
+    Ok(())
+}
+
+fuzz_target!(|input: Input| {
+    let mut output = String::new();
+    match input {
+        Input::List(calls) => {
+            if serialize_list(&calls, &mut output).is_ok() && !output.is_empty() {
+                if let Err(err) = Parser::parse_list(output.as_bytes()) {
+                    panic!("serialized list {:?} doesn't parse: {}", output, err);
+                }
+            }
+        }
+        Input::Dict(calls) => {
+            if serialize_dict(&calls, &mut output).is_ok() && !output.is_empty() {
+                if let Err(err) = Parser::parse_dictionary(output.as_bytes()) {
+                    panic!("serialized dictionary {:?} doesn't parse: {}", output, err);
+                }
+            }
+        }
+    }
+});
@@ -0,0 +1,265 @@
+/*!
+Compile-time parsed Structured Field Values literals for [sfv](https://docs.rs/sfv).
+
+`sfv_literal!` parses a string literal with `sfv::Parser` while compiling,
+so an invalid field value embedded in a binary is a compile error rather than a run-time one.
+The macro expands to the code constructing the parsed `Item`, `List` or `Dictionary`.
+
+```
+use sfv::{BareItem, SerializeValue};
+use sfv_macros::sfv_literal;
+
+let dict = sfv_literal!(dict "a=1, b;foo=*");
+assert_eq!(dict.serialize_value().unwrap(), "a=1, b;foo=*");
+
+let list = sfv_literal!(list "gzip, br;q=0.5");
+assert_eq!(list.len(), 2);
+
+let item = sfv_literal!(item r#""text";lang=en"#);
+assert_eq!(item.bare_item, BareItem::String("text".into()));
+```
+
+The kind of field value must be given before the literal, as `item`, `list` or `dict`,
+as the same text can be valid as more than one kind, e.g. `"a, b"` is both a `List` and a `Dictionary`.
+A literal without a kind doesn't compile:
+```compile_fail
+let value = sfv_macros::sfv_literal!("a, b");
+```
+
+Invalid literals don't compile:
+```compile_fail
+let item = sfv_macros::sfv_literal!(item "1 2");
+```
+*/
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+use sfv::{BareItem, Dictionary, InnerList, Item, List, ListEntry, Parameters, Parser};
+use std::fmt::Write;
+
+/// Parses a structured field value literal at compile time, expanding to the constructed `Item`, `List` or `Dictionary`.
+#[proc_macro]
+pub fn sfv_literal(input: TokenStream) -> TokenStream {
+    let output = match expand(input) {
+        Ok(output) => output,
+        Err(err) => format!("::std::compile_error!({:?})", err),
+    };
+    output
+        .parse()
+        .expect("sfv_literal: generated code is not valid")
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Item,
+    List,
+    Dictionary,
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let mut tokens = flatten(input).into_iter().peekable();
+
+    let kind = match tokens.peek() {
+        Some(TokenTree::Ident(ident)) => {
+            let kind = match ident.to_string().as_str() {
+                "item" => Kind::Item,
+                "list" => Kind::List,
+                "dict" => Kind::Dictionary,
+                other => {
+                    return Err(format!(
+                        "sfv_literal: unknown kind `{}`, expected `item`, `list` or `dict`",
+                        other
+                    ))
+                }
+            };
+            tokens.next();
+            kind
+        }
+        _ => {
+            return Err(
+                "sfv_literal: expected kind `item`, `list` or `dict` before the literal".to_owned(),
+            )
+        }
+    };
+
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal.to_string(),
+        _ => return Err("sfv_literal: expected a string literal".to_owned()),
+    };
+    let value = unquote(&literal)?;
+    let input_bytes = value.as_bytes();
+
+    let parse_error = |err| format!("sfv_literal: {}", err);
+    match kind {
+        Kind::Item => item(&Parser::parse_item(input_bytes).map_err(parse_error)?),
+        Kind::List => list(&Parser::parse_list(input_bytes).map_err(parse_error)?),
+        Kind::Dictionary => {
+            dictionary(&Parser::parse_dictionary(input_bytes).map_err(parse_error)?)
+        }
+    }
+}
+
+// Arguments forwarded from `macro_rules!` macros come wrapped in invisible groups
+fn flatten(input: TokenStream) -> Vec<TokenTree> {
+    let mut tokens = vec![];
+    for token in input {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                tokens.extend(flatten(group.stream()))
+            }
+            token => tokens.push(token),
+        }
+    }
+    tokens
+}
+
+// Returns value of a string literal, given as it's written in the source
+fn unquote(literal: &str) -> Result<String, String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Ok(raw[hashes + 1..raw.len() - hashes - 1].to_owned());
+    }
+
+    let quoted = match literal.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(quoted) => quoted,
+        None => return Err("sfv_literal: expected a string literal".to_owned()),
+    };
+    let mut output = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => output.push('"'),
+            Some('\\') => output.push('\\'),
+            Some('\'') => output.push('\''),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some('t') => output.push('\t'),
+            Some('0') => output.push('\0'),
+            Some('x') => {
+                let code: String = chars.by_ref().take(2).collect();
+                let code = u8::from_str_radix(&code, 16).map_err(|err| err.to_string())?;
+                output.push(char::from(code));
+            }
+            Some('u') => {
+                let code: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                let code = u32::from_str_radix(&code, 16).map_err(|err| err.to_string())?;
+                output.push(char::from_u32(code).ok_or("sfv_literal: invalid unicode escape")?);
+            }
+            // Line continuation skips the line break and following whitespace
+            Some('\n') => {
+                while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            _ => return Err("sfv_literal: unsupported escape in string literal".to_owned()),
+        }
+    }
+    Ok(output)
+}
+
+fn dictionary(dict: &Dictionary) -> Result<String, String> {
+    let mut output = "{ #[allow(unused_mut)] let mut dict = ::sfv::Dictionary::new();".to_owned();
+    for (key, member) in dict {
+        write!(
+            output,
+            " dict.insert(::std::string::String::from({:?}), {});",
+            key,
+            list_entry(member)?
+        )
+        .unwrap();
+    }
+    output.push_str(" dict }");
+    Ok(output)
+}
+
+fn list(list: &List) -> Result<String, String> {
+    let members = list.iter().map(list_entry).collect::<Result<Vec<_>, _>>()?;
+    Ok(format!(
+        "{{ let list: ::sfv::List = ::std::vec![{}]; list }}",
+        members.join(", ")
+    ))
+}
+
+fn list_entry(member: &ListEntry) -> Result<String, String> {
+    match member {
+        ListEntry::Item(member) => Ok(format!("::sfv::ListEntry::Item({})", item(member)?)),
+        ListEntry::InnerList(member) => Ok(format!(
+            "::sfv::ListEntry::InnerList({})",
+            inner_list(member)?
+        )),
+    }
+}
+
+fn inner_list(inner_list: &InnerList) -> Result<String, String> {
+    let items = inner_list
+        .items
+        .iter()
+        .map(item)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!(
+        "::sfv::InnerList::with_params(::std::vec![{}], {})",
+        items.join(", "),
+        parameters(&inner_list.params)?
+    ))
+}
+
+fn item(item: &Item) -> Result<String, String> {
+    Ok(format!(
+        "::sfv::Item::with_params({}, {})",
+        bare_item(&item.bare_item)?,
+        parameters(&item.params)?
+    ))
+}
+
+fn parameters(params: &Parameters) -> Result<String, String> {
+    if params.is_empty() {
+        return Ok("::sfv::Parameters::new()".to_owned());
+    }
+    let mut output = "{ let mut params = ::sfv::Parameters::new();".to_owned();
+    for (key, value) in params {
+        write!(
+            output,
+            " params.insert(::std::string::String::from({:?}), {});",
+            key,
+            bare_item(value)?
+        )
+        .unwrap();
+    }
+    output.push_str(" params }");
+    Ok(output)
+}
+
+fn bare_item(bare_item: &BareItem) -> Result<String, String> {
+    let output = match bare_item {
+        BareItem::Integer(value) => format!("::sfv::BareItem::Integer({}i64)", value),
+        BareItem::Decimal(value) => format!(
+            "::sfv::BareItem::Decimal(::sfv::Decimal::new({}i64, {}u32))",
+            value.mantissa(),
+            value.scale()
+        ),
+        BareItem::String(value) => format!(
+            "::sfv::BareItem::String(::std::string::String::from({:?}))",
+            value
+        ),
+        BareItem::ByteSeq(value) => format!(
+            "::sfv::BareItem::ByteSeq(::std::vec![{}])",
+            value
+                .iter()
+                .map(|byte| format!("{}u8", byte))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        BareItem::Boolean(value) => format!("::sfv::BareItem::Boolean({})", value),
+        BareItem::Token(value) => format!(
+            "::sfv::BareItem::Token(::std::string::String::from({:?}))",
+            value
+        ),
+        _ => return Err("sfv_literal: unsupported bare item type".to_owned()),
+    };
+    Ok(output)
+}
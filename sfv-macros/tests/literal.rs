@@ -0,0 +1,37 @@
+use sfv::{Dictionary, Item, List, Parser};
+use sfv_macros::sfv_literal;
+use std::error::Error;
+
+#[test]
+fn literal_matches_parsed_value() -> Result<(), Box<dyn Error>> {
+    let item: Item = sfv_literal!(item "-1.25;a=:AQID:;b=\"x\\\"y\";c=?0;d=*tok/x");
+    assert_eq!(
+        Parser::parse_item(r#"-1.25;a=:AQID:;b="x\"y";c=?0;d=*tok/x"#.as_bytes())?,
+        item
+    );
+
+    let list: List = sfv_literal!(list "a, b");
+    assert_eq!(Parser::parse_list("a, b".as_bytes())?, list);
+
+    let dict: Dictionary = sfv_literal!(dict r##"a=(1 "#2";p);q=9, b"##);
+    assert_eq!(
+        Parser::parse_dictionary(r##"a=(1 "#2";p);q=9, b"##.as_bytes())?,
+        dict
+    );
+    Ok(())
+}
+
+macro_rules! forwarded {
+    ($kind:ident $literal:literal) => {
+        sfv_literal!($kind $literal)
+    };
+}
+
+#[test]
+fn literal_forwarded_from_macro() -> Result<(), Box<dyn Error>> {
+    let list: List = forwarded!(list "\
+        a,   \
+        b");
+    assert_eq!(Parser::parse_list("a, b".as_bytes())?, list);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+/*!
+Parsing of many independent field values at once.
+
+With the `rayon` feature enabled, inputs are parsed in parallel on the rayon
+global thread pool. Otherwise they are parsed sequentially. Either way the
+results are returned in the order of the inputs.
+
+```
+use sfv::batch;
+
+let stored = vec!["a=1, b", "c=(1 2)", "d=\""];
+let results = batch::parse_dictionaries(&stored);
+assert!(results[0].is_ok());
+assert!(results[1].is_ok());
+assert!(results[2].is_err());
+```
+*/
+
+use crate::{Dictionary, Item, List, Parser, SFVResult};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Parses every input into structured field value of Item type.
+pub fn parse_items<I: AsRef<[u8]> + Sync>(inputs: &[I]) -> Vec<SFVResult<Item>> {
+    parse_all(inputs, Parser::parse_item)
+}
+
+/// Parses every input into structured field value of List type.
+pub fn parse_lists<I: AsRef<[u8]> + Sync>(inputs: &[I]) -> Vec<SFVResult<List>> {
+    parse_all(inputs, Parser::parse_list)
+}
+
+/// Parses every input into structured field value of Dictionary type.
+pub fn parse_dictionaries<I: AsRef<[u8]> + Sync>(inputs: &[I]) -> Vec<SFVResult<Dictionary>> {
+    parse_all(inputs, Parser::parse_dictionary)
+}
+
+#[cfg(feature = "rayon")]
+fn parse_all<I, T, F>(inputs: &[I], parse_value: F) -> Vec<SFVResult<T>>
+where
+    I: AsRef<[u8]> + Sync,
+    T: Send,
+    F: Fn(&[u8]) -> SFVResult<T> + Sync,
+{
+    inputs
+        .par_iter()
+        .map(|input| parse_value(input.as_ref()))
+        .collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn parse_all<I, T, F>(inputs: &[I], parse_value: F) -> Vec<SFVResult<T>>
+where
+    I: AsRef<[u8]> + Sync,
+    F: Fn(&[u8]) -> SFVResult<T>,
+{
+    inputs
+        .iter()
+        .map(|input| parse_value(input.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_keep_input_order() {
+        let inputs: Vec<String> = (0..100).map(|i| format!("{}, a", i)).collect();
+        let results = parse_lists(&inputs);
+        assert_eq!(inputs.len(), results.len());
+        for (input, result) in inputs.iter().zip(results) {
+            assert_eq!(Parser::parse_list(input.as_bytes()), result);
+        }
+    }
+
+    #[test]
+    fn errors_are_reported_per_input() {
+        let results = parse_items(&["1", "?2", "tok"]);
+        assert!(results[0].is_ok());
+        assert_eq!(Err("parse_bool: invalid variant"), results[1]);
+        assert!(results[2].is_ok());
+    }
+}
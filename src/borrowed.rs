@@ -0,0 +1,576 @@
+/*!
+Structured field values borrowing from the parsed input.
+
+Returned by `Parser::parse_*_borrowed` methods. Keys, tokens and strings without escapes
+are slices of the input, so parsing allocates only for collections, escaped strings and byte sequences.
+Values can be converted into their owned counterparts with `From` implementations.
+
+```
+use sfv::borrowed::BareItemRef;
+use sfv::Parser;
+use std::borrow::Cow;
+
+let input = String::from(r#"a=tok, b="plain";q="\"escaped\"""#);
+let dict = Parser::parse_dictionary_borrowed(input.as_bytes()).unwrap();
+
+let b = dict["b"].as_item().unwrap();
+assert_eq!(b.bare_item, BareItemRef::String(Cow::Borrowed("plain")));
+assert!(matches!(b.params["q"], BareItemRef::String(Cow::Owned(_))));
+```
+*/
+
+use crate::parser::Input;
+use crate::{chars, utils};
+use crate::{
+    unescape_string, BareItem, Decimal, InnerList, Item, ListEntry, Number, Parameters,
+    ParseLimits, ParseOptions, Parser, SFVResult,
+};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::str::from_utf8;
+
+/// Parameters of `ItemRef` or `InnerListRef`.
+pub type ParametersRef<'a> = IndexMap<&'a str, BareItemRef<'a>>;
+
+/// Dictionary type structured field value borrowing from the input.
+pub type DictionaryRef<'a> = IndexMap<&'a str, ListEntryRef<'a>>;
+
+/// List type structured field value borrowing from the input.
+pub type ListRef<'a> = Vec<ListEntryRef<'a>>;
+
+/// Bare item borrowing from the input.
+///
+/// Unlike `RefBareItem`, which is used for serialization, escaped strings are owned
+/// and byte sequences are decoded.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum BareItemRef<'a> {
+    /// Decimal number
+    Decimal(Decimal),
+    /// Integer number
+    Integer(i64),
+    /// String, borrowed unless it contains escaped characters
+    String(Cow<'a, str>),
+    /// Decoded byte sequence
+    ByteSeq(Vec<u8>),
+    /// Boolean
+    Boolean(bool),
+    /// Token
+    Token(&'a str),
+}
+
+/// Item type structured field value borrowing from the input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemRef<'a> {
+    /// Value of `ItemRef`.
+    pub bare_item: BareItemRef<'a>,
+    /// `ItemRef`'s associated parameters. Can be empty.
+    pub params: ParametersRef<'a>,
+}
+
+/// Inner list borrowing from the input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InnerListRef<'a> {
+    /// `Items` that `InnerListRef` contains. Can be empty.
+    pub items: Vec<ItemRef<'a>>,
+    /// `InnerListRef`'s associated parameters. Can be empty.
+    pub params: ParametersRef<'a>,
+}
+
+/// Member of `ListRef` or `DictionaryRef`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ListEntryRef<'a> {
+    /// Member of `ItemRef` type.
+    Item(ItemRef<'a>),
+    /// Member of `InnerListRef` type.
+    InnerList(InnerListRef<'a>),
+}
+
+impl<'a> ListEntryRef<'a> {
+    /// If `ListEntryRef` is an item, returns `Some(&ItemRef)`, otherwise returns `None`.
+    pub fn as_item(&self) -> Option<&ItemRef<'a>> {
+        match self {
+            ListEntryRef::Item(item) => Some(item),
+            ListEntryRef::InnerList(_) => None,
+        }
+    }
+
+    /// If `ListEntryRef` is an inner list, returns `Some(&InnerListRef)`, otherwise returns `None`.
+    pub fn as_inner_list(&self) -> Option<&InnerListRef<'a>> {
+        match self {
+            ListEntryRef::InnerList(inner_list) => Some(inner_list),
+            ListEntryRef::Item(_) => None,
+        }
+    }
+}
+
+impl From<BareItemRef<'_>> for BareItem {
+    fn from(bare_item: BareItemRef<'_>) -> BareItem {
+        match bare_item {
+            BareItemRef::Decimal(val) => BareItem::Decimal(val),
+            BareItemRef::Integer(val) => BareItem::Integer(val),
+            BareItemRef::String(val) => BareItem::String(val.into_owned()),
+            BareItemRef::ByteSeq(val) => BareItem::ByteSeq(val),
+            BareItemRef::Boolean(val) => BareItem::Boolean(val),
+            BareItemRef::Token(val) => BareItem::Token(val.to_owned()),
+        }
+    }
+}
+
+impl From<ItemRef<'_>> for Item {
+    fn from(item: ItemRef<'_>) -> Item {
+        Item::with_params(item.bare_item.into(), owned_params(item.params))
+    }
+}
+
+impl From<InnerListRef<'_>> for InnerList {
+    fn from(inner_list: InnerListRef<'_>) -> InnerList {
+        InnerList::with_params(
+            inner_list.items.into_iter().map(Item::from).collect(),
+            owned_params(inner_list.params),
+        )
+    }
+}
+
+impl From<ListEntryRef<'_>> for ListEntry {
+    fn from(entry: ListEntryRef<'_>) -> ListEntry {
+        match entry {
+            ListEntryRef::Item(item) => ListEntry::Item(item.into()),
+            ListEntryRef::InnerList(inner_list) => ListEntry::InnerList(inner_list.into()),
+        }
+    }
+}
+
+impl From<bool> for BareItemRef<'_> {
+    fn from(value: bool) -> Self {
+        BareItemRef::Boolean(value)
+    }
+}
+
+fn owned_params(params: ParametersRef<'_>) -> Parameters {
+    params
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value.into()))
+        .collect()
+}
+
+impl Parser {
+    /// Parses input into structured field value of Dictionary type, borrowing from the input.
+    pub fn parse_dictionary_borrowed(input_bytes: &[u8]) -> SFVResult<DictionaryRef<'_>> {
+        Self::parse_dictionary_borrowed_with_options(input_bytes, &ParseOptions::default())
+    }
+
+    /// Parses input into structured field value of List type, borrowing from the input.
+    pub fn parse_list_borrowed(input_bytes: &[u8]) -> SFVResult<ListRef<'_>> {
+        Self::parse_list_borrowed_with_options(input_bytes, &ParseOptions::default())
+    }
+
+    /// Parses input into structured field value of Item type, borrowing from the input.
+    pub fn parse_item_borrowed(input_bytes: &[u8]) -> SFVResult<ItemRef<'_>> {
+        Self::parse_item_borrowed_with_options(input_bytes, &ParseOptions::default())
+    }
+
+    /// Parses input into structured field value of Dictionary type using `options`, borrowing from the input.
+    ///
+    /// All options apply as in `Parser::parse_dictionary_with_options`, except hooks registered with
+    /// `ParseOptions::normalize_with`, which transform owned `BareItem` and so are not run.
+    /// # Examples
+    /// ```
+    /// # use sfv::{DuplicateKeys, ParseLimits, ParseOptions, Parser};
+    /// let options = ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::Reject);
+    /// assert_eq!(
+    ///     Parser::parse_dictionary_borrowed_with_options("a, b, a".as_bytes(), &options),
+    ///     Err("parse_dict: duplicate key")
+    /// );
+    ///
+    /// let options = ParseOptions::new().limits(ParseLimits::new().max_dictionary_members(2));
+    /// assert_eq!(
+    ///     Parser::parse_dictionary_borrowed_with_options("a, b, c".as_bytes(), &options),
+    ///     Err("parse_dict: too many members")
+    /// );
+    /// ```
+    pub fn parse_dictionary_borrowed_with_options<'a>(
+        input_bytes: &'a [u8],
+        options: &ParseOptions,
+    ) -> SFVResult<DictionaryRef<'a>> {
+        parse_with(input_bytes, options, parse_dict_members)
+    }
+
+    /// Parses input into structured field value of List type using `options`, borrowing from the input.
+    ///
+    /// All options apply as in `Parser::parse_list_with_options`, except hooks registered with
+    /// `ParseOptions::normalize_with`, which are not run.
+    pub fn parse_list_borrowed_with_options<'a>(
+        input_bytes: &'a [u8],
+        options: &ParseOptions,
+    ) -> SFVResult<ListRef<'a>> {
+        parse_with(input_bytes, options, parse_list_members)
+    }
+
+    /// Parses input into structured field value of Item type using `options`, borrowing from the input.
+    ///
+    /// All options apply as in `Parser::parse_item_with_options`, except hooks registered with
+    /// `ParseOptions::normalize_with`, which are not run.
+    pub fn parse_item_borrowed_with_options<'a>(
+        input_bytes: &'a [u8],
+        options: &ParseOptions,
+    ) -> SFVResult<ItemRef<'a>> {
+        parse_with(input_bytes, options, parse_item)
+    }
+}
+
+fn parse_with<'a, T, F>(
+    input_bytes: &'a [u8],
+    options: &ParseOptions,
+    parse_value: F,
+) -> SFVResult<T>
+where
+    F: FnOnce(&mut Cursor<'a>, &ParseOptions) -> SFVResult<T>,
+{
+    Parser::check_options(input_bytes, options)?;
+
+    // https://httpwg.org/specs/rfc8941.html#text-parse
+    if !input_bytes.is_ascii() {
+        return Err("parse: non-ascii characters in input");
+    }
+
+    let input = from_utf8(input_bytes).map_err(|_| "parse: conversion from bytes to str failed")?;
+    let mut cursor = Cursor { input, pos: 0 };
+    utils::consume_sp_chars(&mut cursor);
+
+    let output = parse_value(&mut cursor, options)?;
+
+    utils::consume_sp_chars(&mut cursor);
+
+    // Terminators are trailing characters here, as in `Parser::parse_*_with_options`
+    if cursor.peek_char().is_some() {
+        return Err("parse: trailing characters after parsed value");
+    };
+    Ok(output)
+}
+
+// Members, inner lists and parameters are parsed by the loops shared with `Parser`,
+// while `Cursor` slices keys, tokens and strings from the input.
+
+fn parse_list_members<'a>(
+    cursor: &mut Cursor<'a>,
+    options: &ParseOptions,
+) -> SFVResult<ListRef<'a>> {
+    let mut members = ListRef::new();
+    Parser::parse_list_with(cursor, options, |cursor| {
+        members.push(parse_list_entry(cursor, options)?);
+        Ok(())
+    })?;
+    Ok(members)
+}
+
+fn parse_dict_members<'a>(
+    cursor: &mut Cursor<'a>,
+    options: &ParseOptions,
+) -> SFVResult<DictionaryRef<'a>> {
+    let mut dict = DictionaryRef::new();
+    Parser::parse_dict_with(cursor, options, |cursor, this_key| {
+        let member = if let Some('=') = cursor.peek_char() {
+            cursor.next_char();
+            parse_list_entry(cursor, options)?
+        } else {
+            ListEntryRef::Item(ItemRef {
+                bare_item: BareItemRef::Boolean(true),
+                params: parse_parameters(cursor, options)?,
+            })
+        };
+        options.duplicate_dictionary_keys.insert(
+            &mut dict,
+            this_key,
+            member,
+            "parse_dict: duplicate key",
+        )
+    })?;
+    Ok(dict)
+}
+
+fn parse_list_entry<'a>(
+    cursor: &mut Cursor<'a>,
+    options: &ParseOptions,
+) -> SFVResult<ListEntryRef<'a>> {
+    match cursor.peek_char() {
+        Some('(') => Ok(ListEntryRef::InnerList(parse_inner_list(cursor, options)?)),
+        _ => Ok(ListEntryRef::Item(parse_item(cursor, options)?)),
+    }
+}
+
+fn parse_item<'a>(cursor: &mut Cursor<'a>, options: &ParseOptions) -> SFVResult<ItemRef<'a>> {
+    let bare_item = cursor.parse_bare_item(options)?;
+    let params = parse_parameters(cursor, options)?;
+    Ok(ItemRef { bare_item, params })
+}
+
+fn parse_inner_list<'a>(
+    cursor: &mut Cursor<'a>,
+    options: &ParseOptions,
+) -> SFVResult<InnerListRef<'a>> {
+    let mut items = Vec::new();
+    Parser::parse_inner_list_with(cursor, options, |cursor| {
+        items.push(parse_item(cursor, options)?);
+        Ok(())
+    })?;
+    let params = parse_parameters(cursor, options)?;
+    Ok(InnerListRef { items, params })
+}
+
+fn parse_parameters<'a>(
+    cursor: &mut Cursor<'a>,
+    options: &ParseOptions,
+) -> SFVResult<ParametersRef<'a>> {
+    let mut params = ParametersRef::new();
+    Parser::parse_parameters_with(cursor, options, |param_name, param_value| {
+        options.duplicate_parameter_keys.insert(
+            &mut params,
+            param_name,
+            param_value,
+            "parse_parameters: duplicate key",
+        )
+    })?;
+    Ok(params)
+}
+
+// Position in ASCII input. Unlike `Peekable<Chars>` used by `Parser`, it can borrow from the input.
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    // Advances past the longest prefix of chars matching `predicate`, returning it.
+    fn take_while<P: FnMut(char) -> bool>(&mut self, mut predicate: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn parse_string(&mut self, options: &ParseOptions) -> SFVResult<Cow<'a, str>> {
+        // https://httpwg.org/specs/rfc8941.html#parse-string
+        let rest = self.rest();
+        let mut has_escapes = false;
+        let mut len = 0;
+        let mut rest_chars = rest.char_indices().skip(1);
+
+        while let Some((idx, curr_char)) = rest_chars.next() {
+            match curr_char {
+                '\"' => {
+                    self.pos += idx + 1;
+                    return if has_escapes {
+                        unescape_string(&rest[..=idx]).map(Cow::Owned)
+                    } else {
+                        Ok(Cow::Borrowed(&rest[1..idx]))
+                    };
+                }
+                c if !chars::is_allowed_string_char(c) => {
+                    return Err("parse_string: not a visible character")
+                }
+                '\\' => match rest_chars.next() {
+                    Some((_, '\\')) | Some((_, '\"')) => has_escapes = true,
+                    None => return Err("parse_string: last input character is '\\'"),
+                    _ => return Err("parse_string: disallowed character after '\\'"),
+                },
+                _ => (),
+            }
+            len += 1;
+            ParseLimits::check(
+                options.limits.max_string_len,
+                len,
+                "parse_string: string is too long",
+            )?;
+        }
+        Err("parse_string: no closing '\"'")
+    }
+
+    // Advances past a number successfully parsed by `Parser::parse_number`, which ends before a second '.'.
+    fn skip_number(&mut self) {
+        if self.peek_char() == Some('-') {
+            self.pos += 1;
+        }
+        let mut has_dot = false;
+        self.take_while(|c| match c {
+            '.' if !has_dot => {
+                has_dot = true;
+                true
+            }
+            c => c.is_ascii_digit(),
+        });
+    }
+}
+
+impl<'a> Input for Cursor<'a> {
+    type Key = &'a str;
+    type BareItem = BareItemRef<'a>;
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.input
+            .as_bytes()
+            .get(self.pos)
+            .map(|&byte| byte as char)
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let next = self.peek_char();
+        if next.is_some() {
+            self.pos += 1;
+        }
+        next
+    }
+
+    fn parse_key(&mut self) -> SFVResult<&'a str> {
+        match self.peek_char() {
+            Some(c) if chars::is_allowed_key_first_char(c) => (),
+            _ => return Err("parse_key: first character is not lcalpha or '*'"),
+        }
+        Ok(self.take_while(chars::is_allowed_key_char))
+    }
+
+    fn parse_bare_item(&mut self, options: &ParseOptions) -> SFVResult<BareItemRef<'a>> {
+        // https://httpwg.org/specs/rfc8941.html#parse-bare-item
+        let rest = self.rest();
+        match self.peek_char() {
+            None => Err("parse_bare_item: empty item"),
+            Some('?') => {
+                let value = Parser::parse_bool(&mut rest.chars().peekable())?;
+                self.pos += 2;
+                Ok(BareItemRef::Boolean(value))
+            }
+            Some('"') => self.parse_string(options).map(BareItemRef::String),
+            Some(':') => {
+                let value = Parser::parse_byte_sequence(&mut rest.chars().peekable(), options)?;
+                // Successfully parsed byte sequence has a closing ':'
+                self.pos += rest[1..].find(':').map_or(rest.len(), |idx| idx + 2);
+                Ok(BareItemRef::ByteSeq(value))
+            }
+            Some(c) if chars::is_allowed_token_first_char(c) => {
+                Ok(BareItemRef::Token(self.take_while(|c| {
+                    chars::is_allowed_token_char(c) && !options.is_terminator(c)
+                })))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let value = Parser::parse_number(&mut rest.chars().peekable())?;
+                self.skip_number();
+                match value {
+                    Number::Decimal(val) => Ok(BareItemRef::Decimal(val)),
+                    Number::Integer(val) => Ok(BareItemRef::Integer(val)),
+                }
+            }
+            _ => Err("parse_bare_item: item type can't be identified"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dictionary, DuplicateKeys, List};
+
+    #[test]
+    fn borrowed_values_match_owned() {
+        let inputs = [
+            "a=1, b=-2.5;p, c=\"str\", d=\"\\\\es\\\"c\", e=:AQI=:, f=?0, g=*tok/a:b",
+            "  h=(1 (2) ), i=();q=tok, j;k=\"v\";l=-0.1, h=3",
+            "a=1.23.4",
+            "a=(1 2",
+            "a=(1 2)x",
+            "a=\"unterminated",
+            "a=\"tab\t\"",
+            "a=:AQI=",
+            "a=?2",
+            "A=1",
+            "a=1,",
+            "a=1 b",
+            "a=1234567890123456",
+            "a=-",
+            "a=\"\\x\"",
+            "a=@",
+            "",
+        ];
+        for input in inputs.iter() {
+            let expected = Parser::parse_dictionary(input.as_bytes());
+            let borrowed = Parser::parse_dictionary_borrowed(input.as_bytes());
+            let owned = borrowed.map(|dict| {
+                dict.into_iter()
+                    .map(|(key, value)| (key.to_owned(), value.into()))
+                    .collect::<Dictionary>()
+            });
+            assert_eq!(expected, owned, "input: {}", input);
+
+            let expected = Parser::parse_list(input.as_bytes());
+            let borrowed = Parser::parse_list_borrowed(input.as_bytes());
+            let owned =
+                borrowed.map(|list| list.into_iter().map(ListEntry::from).collect::<List>());
+            assert_eq!(expected, owned, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn options_match_owned() {
+        let options = [
+            ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::Reject),
+            ParseOptions::new().duplicate_parameter_keys(DuplicateKeys::KeepFirst),
+            ParseOptions::new().reject_parameters(true),
+            ParseOptions::new().strict_base64(true),
+            ParseOptions::new().terminators(b"|"),
+            ParseOptions::new().max_input_len(20),
+            ParseOptions::new().limits(
+                ParseLimits::new()
+                    .max_list_members(2)
+                    .max_dictionary_members(2)
+                    .max_parameters(1)
+                    .max_string_len(3)
+                    .max_byte_seq_len(1),
+            ),
+        ];
+        let inputs = [
+            "a=1, b;p=1;p=2, a=3",
+            "a=(1 2 3), b=:AQI=:",
+            "a=\"abcd\", b=:AR==:",
+            "a=tok|b, c=1",
+            "a, b",
+        ];
+        for options in options.iter() {
+            for input in inputs.iter() {
+                let expected = Parser::parse_dictionary_with_options(input.as_bytes(), options);
+                let borrowed =
+                    Parser::parse_dictionary_borrowed_with_options(input.as_bytes(), options);
+                let owned = borrowed.map(|dict| {
+                    dict.into_iter()
+                        .map(|(key, value)| (key.to_owned(), value.into()))
+                        .collect::<Dictionary>()
+                });
+                assert_eq!(expected, owned, "input: {}, options: {:?}", input, options);
+
+                let expected = Parser::parse_list_with_options(input.as_bytes(), options);
+                let borrowed = Parser::parse_list_borrowed_with_options(input.as_bytes(), options);
+                let owned =
+                    borrowed.map(|list| list.into_iter().map(ListEntry::from).collect::<List>());
+                assert_eq!(expected, owned, "input: {}, options: {:?}", input, options);
+            }
+        }
+    }
+
+    #[test]
+    fn strings_without_escapes_are_borrowed() -> SFVResult<()> {
+        let item = Parser::parse_item_borrowed("\"abc\";a=\"\\\"\";b=tok ".as_bytes())?;
+        assert_eq!(BareItemRef::String(Cow::Borrowed("abc")), item.bare_item);
+        assert_eq!(
+            BareItemRef::String(Cow::Owned("\"".to_owned())),
+            item.params["a"]
+        );
+        assert_eq!(BareItemRef::Token("tok"), item.params["b"]);
+        assert_eq!(
+            Err("parse: trailing characters after parsed value"),
+            Parser::parse_item_borrowed("1 2".as_bytes())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use crate::serializer::Serializer;
+use crate::{Dictionary, List, SFVResult};
+
+/// `List` or `Dictionary` serialized within a byte budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budgeted {
+    /// Serialized members that fit within the budget, in their original order.
+    /// Empty if no member fits, in which case the field should be omitted.
+    pub value: String,
+    /// Indices of members left out of `value`, in ascending order.
+    pub omitted: Vec<usize>,
+}
+
+/// Serializes as many members of `List` or `Dictionary` as fit within a byte budget.
+pub trait SerializeWithin {
+    /// Serializes members in order until the next one doesn't fit within `max_len` bytes,
+    /// omitting it and all the following members.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeWithin};
+    /// let list = Parser::parse_list("a, bbbb, c".as_bytes()).unwrap();
+    /// let budgeted = list.serialize_within(8).unwrap();
+    /// assert_eq!(budgeted.value, "a, bbbb");
+    /// assert_eq!(budgeted.omitted, vec![2]);
+    /// ```
+    fn serialize_within(&self, max_len: usize) -> SFVResult<Budgeted>;
+
+    /// Picks members in order of descending `priority`, given each member's index,
+    /// skipping every member that doesn't fit within `max_len` bytes together with the members already picked.
+    /// Members with equal priority are picked in their original order, and picked members are serialized in their original order.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeWithin};
+    /// let dict = Parser::parse_dictionary("debug=\"long details\", hit, ttl=30".as_bytes()).unwrap();
+    /// let budgeted = dict
+    ///     .serialize_within_by_priority(12, |idx| dict.get_index(idx).unwrap().0 != "debug")
+    ///     .unwrap();
+    /// assert_eq!(budgeted.value, "hit, ttl=30");
+    /// assert_eq!(budgeted.omitted, vec![0]);
+    /// ```
+    fn serialize_within_by_priority<P, F>(
+        &self,
+        max_len: usize,
+        priority: F,
+    ) -> SFVResult<Budgeted>
+    where
+        P: Ord,
+        F: FnMut(usize) -> P;
+
+    /// Serializes members into as few field lines as possible, each at most `max_len` bytes long,
+    /// splitting only between members. Lines sent as separate field lines of the same field
+    /// are combined by the recipient into the original value.
+    /// Returns no lines for an empty `List` or `Dictionary`, in which case the field should be omitted,
+    /// or an error if any member is longer than `max_len` bytes on its own.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeWithin};
+    /// let list = Parser::parse_list("a, bbbb, c, d".as_bytes()).unwrap();
+    /// assert_eq!(list.serialize_split(7).unwrap(), vec!["a, bbbb", "c, d"]);
+    /// assert!(list.serialize_split(3).is_err());
+    /// ```
+    fn serialize_split(&self, max_len: usize) -> SFVResult<Vec<String>>;
+}
+
+impl SerializeWithin for List {
+    fn serialize_within(&self, max_len: usize) -> SFVResult<Budgeted> {
+        let members = serialize_list_members(self)?;
+        Ok(select_in_order(&members, max_len))
+    }
+
+    fn serialize_within_by_priority<P, F>(&self, max_len: usize, priority: F) -> SFVResult<Budgeted>
+    where
+        P: Ord,
+        F: FnMut(usize) -> P,
+    {
+        let members = serialize_list_members(self)?;
+        Ok(select_by_priority(&members, max_len, priority))
+    }
+
+    fn serialize_split(&self, max_len: usize) -> SFVResult<Vec<String>> {
+        let members = serialize_list_members(self)?;
+        split(&members, max_len)
+    }
+}
+
+impl SerializeWithin for Dictionary {
+    fn serialize_within(&self, max_len: usize) -> SFVResult<Budgeted> {
+        let members = serialize_dict_members(self)?;
+        Ok(select_in_order(&members, max_len))
+    }
+
+    fn serialize_within_by_priority<P, F>(&self, max_len: usize, priority: F) -> SFVResult<Budgeted>
+    where
+        P: Ord,
+        F: FnMut(usize) -> P,
+    {
+        let members = serialize_dict_members(self)?;
+        Ok(select_by_priority(&members, max_len, priority))
+    }
+
+    fn serialize_split(&self, max_len: usize) -> SFVResult<Vec<String>> {
+        let members = serialize_dict_members(self)?;
+        split(&members, max_len)
+    }
+}
+
+fn serialize_list_members(list: &List) -> SFVResult<Vec<String>> {
+    list.iter()
+        .map(|member| {
+            let mut output = String::new();
+            Serializer::serialize_list_member(member, &mut output)?;
+            Ok(output)
+        })
+        .collect()
+}
+
+fn serialize_dict_members(dict: &Dictionary) -> SFVResult<Vec<String>> {
+    dict.iter()
+        .map(|(member_name, member_value)| {
+            let mut output = String::new();
+            Serializer::serialize_dict_member(member_name, member_value, &mut output)?;
+            Ok(output)
+        })
+        .collect()
+}
+
+fn select_in_order(members: &[String], max_len: usize) -> Budgeted {
+    let mut len = 0;
+    let mut fitting = 0;
+    for member in members {
+        match fitting_len(len, fitting, member, max_len) {
+            Some(new_len) => {
+                len = new_len;
+                fitting += 1;
+            }
+            None => break,
+        }
+    }
+    join(members, &vec![true; fitting])
+}
+
+fn select_by_priority<P, F>(members: &[String], max_len: usize, priority: F) -> Budgeted
+where
+    P: Ord,
+    F: FnMut(usize) -> P,
+{
+    let priorities: Vec<P> = (0..members.len()).map(priority).collect();
+    let mut order: Vec<usize> = (0..members.len()).collect();
+    order.sort_by(|a, b| priorities[*b].cmp(&priorities[*a]));
+
+    let mut picked = vec![false; members.len()];
+    let mut len = 0;
+    let mut count = 0;
+    for idx in order {
+        if let Some(new_len) = fitting_len(len, count, &members[idx], max_len) {
+            len = new_len;
+            count += 1;
+            picked[idx] = true;
+        }
+    }
+    join(members, &picked)
+}
+
+fn split(members: &[String], max_len: usize) -> SFVResult<Vec<String>> {
+    let mut lines: Vec<String> = vec![];
+    let mut count = 0;
+    for member in members {
+        if member.len() > max_len {
+            return Err("serialize_split: member is longer than max_len");
+        }
+        match lines.last_mut() {
+            Some(line) if fitting_len(line.len(), count, member, max_len).is_some() => {
+                line.push_str(", ");
+                line.push_str(member);
+                count += 1;
+            }
+            _ => {
+                lines.push(member.clone());
+                count = 1;
+            }
+        }
+    }
+    Ok(lines)
+}
+
+// Returns the output length after appending `member`, if it fits within `max_len`.
+fn fitting_len(len: usize, count: usize, member: &str, max_len: usize) -> Option<usize> {
+    let separator_len = if count == 0 { 0 } else { 2 };
+    let new_len = len + separator_len + member.len();
+    if new_len <= max_len {
+        Some(new_len)
+    } else {
+        None
+    }
+}
+
+// Joins members whose `picked` flag is set. Members past the end of `picked` are omitted.
+fn join(members: &[String], picked: &[bool]) -> Budgeted {
+    let mut value = String::new();
+    let mut omitted = vec![];
+    for (idx, member) in members.iter().enumerate() {
+        if picked.get(idx).copied().unwrap_or(false) {
+            if !value.is_empty() {
+                value.push_str(", ");
+            }
+            value.push_str(member);
+        } else {
+            omitted.push(idx);
+        }
+    }
+    Budgeted { value, omitted }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn serialize_within_stops_at_first_member_over_budget() -> SFVResult<()> {
+        let list = Parser::parse_list("aaaa, b, c".as_bytes())?;
+        assert_eq!(
+            Budgeted {
+                value: "aaaa, b, c".to_owned(),
+                omitted: vec![],
+            },
+            list.serialize_within(10)?
+        );
+        assert_eq!(
+            Budgeted {
+                value: "aaaa".to_owned(),
+                omitted: vec![1, 2],
+            },
+            list.serialize_within(6)?
+        );
+        assert_eq!(
+            Budgeted {
+                value: String::new(),
+                omitted: vec![0, 1, 2],
+            },
+            list.serialize_within(3)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_within_by_priority_fills_budget() -> SFVResult<()> {
+        let dict = Parser::parse_dictionary("a=1, long=\"text\", b, c=2".as_bytes())?;
+        let budgeted = dict.serialize_within_by_priority(8, |idx| idx == 3)?;
+        assert_eq!("a=1, c=2", budgeted.value);
+        assert_eq!(vec![1, 2], budgeted.omitted);
+
+        let budgeted = dict.serialize_within_by_priority(11, |_| 0)?;
+        assert_eq!("a=1, b, c=2", budgeted.value);
+        assert_eq!(vec![1], budgeted.omitted);
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_split_fills_lines_in_order() -> SFVResult<()> {
+        let dict = Parser::parse_dictionary("a=1, b=22, c, d=(1 2)".as_bytes())?;
+        assert_eq!(vec!["a=1, b=22", "c, d=(1 2)"], dict.serialize_split(10)?);
+        assert_eq!(vec!["a=1, b=22, c, d=(1 2)"], dict.serialize_split(100)?);
+        assert_eq!(vec!["a=1", "b=22, c", "d=(1 2)"], dict.serialize_split(7)?);
+        assert_eq!(
+            Err("serialize_split: member is longer than max_len"),
+            dict.serialize_split(6)
+        );
+
+        for line in dict.serialize_split(10)? {
+            assert!(Parser::parse_dictionary(line.as_bytes()).is_ok());
+        }
+        assert!(List::new().serialize_split(10)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_within_reports_invalid_members() {
+        let mut dict = Dictionary::new();
+        dict.insert("A".to_owned(), crate::Item::new(1.into()).into());
+        assert_eq!(
+            Err("serialize_key: disallowed character in input"),
+            dict.serialize_within(100)
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    BareItem, Decimal, Dictionary, InnerList, Item, ListEntry, Parameters, Parser, SFVResult,
+};
+
+/// Builds a `Dictionary` member by member, validating keys as they are added.
+///
+/// Owned equivalent of `RefDictSerializer`. The first invalid key is reported by `build`,
+/// and the member or parameter with that key is left out. Values are checked when the `Dictionary` is serialized.
+/// # Examples
+/// ```
+/// # use sfv::{Decimal, DictionaryBuilder, SerializeValue};
+/// let dict = DictionaryBuilder::new()
+///     .entry("a").integer(5).param("q", true)
+///     .entry("b").inner_list(vec![]).param("p", Decimal::new(5, 1))
+///     .entry("c")
+///     .build()
+///     .unwrap();
+/// assert_eq!(dict.serialize_value().unwrap(), "a=5;q, b=();p=0.5, c");
+///
+/// assert!(DictionaryBuilder::new().entry("A").build().is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryBuilder {
+    dict: Dictionary,
+    error: Option<&'static str>,
+}
+
+impl DictionaryBuilder {
+    /// Returns new `DictionaryBuilder` without members.
+    pub fn new() -> DictionaryBuilder {
+        DictionaryBuilder::default()
+    }
+
+    /// Starts member `key`, with `true` value unless another one is set.
+    /// A member with the same key added before is replaced, keeping its position.
+    pub fn entry(mut self, key: &str) -> DictionaryEntryBuilder {
+        let valid = self.check(Parser::validate_key(key));
+        DictionaryEntryBuilder {
+            builder: self,
+            key: key.to_owned(),
+            valid,
+            bare_item: BareItem::Boolean(true),
+            items: None,
+            params: Parameters::new(),
+        }
+    }
+
+    /// Returns the built `Dictionary`, or the error caused by the first invalid key.
+    pub fn build(self) -> SFVResult<Dictionary> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.dict),
+        }
+    }
+
+    fn check(&mut self, result: SFVResult<()>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                self.error.get_or_insert(err);
+                false
+            }
+        }
+    }
+}
+
+/// Member of `Dictionary` being built by `DictionaryBuilder`.
+#[derive(Debug, Clone)]
+pub struct DictionaryEntryBuilder {
+    builder: DictionaryBuilder,
+    key: String,
+    valid: bool,
+    bare_item: BareItem,
+    // Set if the member is an inner list
+    items: Option<Vec<Item>>,
+    params: Parameters,
+}
+
+impl DictionaryEntryBuilder {
+    /// Sets member value to `BareItem::Integer`.
+    pub fn integer(self, value: i64) -> Self {
+        self.bare_item(BareItem::Integer(value))
+    }
+
+    /// Sets member value to `BareItem::Decimal`.
+    pub fn decimal(self, value: Decimal) -> Self {
+        self.bare_item(BareItem::Decimal(value))
+    }
+
+    /// Sets member value to `BareItem::String`.
+    pub fn string(self, value: &str) -> Self {
+        self.bare_item(BareItem::String(value.to_owned()))
+    }
+
+    /// Sets member value to `BareItem::Token`.
+    pub fn token(self, value: &str) -> Self {
+        self.bare_item(BareItem::Token(value.to_owned()))
+    }
+
+    /// Sets member value to `BareItem::ByteSeq`.
+    pub fn byte_seq(self, value: &[u8]) -> Self {
+        self.bare_item(BareItem::ByteSeq(value.to_vec()))
+    }
+
+    /// Sets member value to `BareItem::Boolean`.
+    pub fn boolean(self, value: bool) -> Self {
+        self.bare_item(BareItem::Boolean(value))
+    }
+
+    /// Sets member value to an `Item` with `value`.
+    pub fn bare_item<V: Into<BareItem>>(mut self, value: V) -> Self {
+        self.bare_item = value.into();
+        self.items = None;
+        self
+    }
+
+    /// Sets member value to an `InnerList` of `items`.
+    pub fn inner_list<I: IntoIterator<Item = Item>>(mut self, items: I) -> Self {
+        self.items = Some(items.into_iter().collect());
+        self
+    }
+
+    /// Adds parameter `key` of the member, validating the key.
+    pub fn param<V: Into<BareItem>>(mut self, key: &str, value: V) -> Self {
+        if self.builder.check(Parser::validate_key(key)) {
+            self.params.insert(key.to_owned(), value.into());
+        }
+        self
+    }
+
+    /// Finishes the member and starts member `key`.
+    pub fn entry(self, key: &str) -> DictionaryEntryBuilder {
+        self.finish().entry(key)
+    }
+
+    /// Finishes the member and returns the built `Dictionary`, or the error caused by the first invalid key.
+    pub fn build(self) -> SFVResult<Dictionary> {
+        self.finish().build()
+    }
+
+    /// Finishes the member, returning `DictionaryBuilder` to add more members with.
+    pub fn finish(self) -> DictionaryBuilder {
+        let mut builder = self.builder;
+        if self.valid {
+            let member: ListEntry = match self.items {
+                Some(items) => InnerList::with_params(items, self.params).into(),
+                None => Item::with_params(self.bare_item, self.params).into(),
+            };
+            builder.dict.insert(self.key, member);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerializeValue;
+
+    #[test]
+    fn build_dictionary() -> SFVResult<()> {
+        let dict = DictionaryBuilder::new()
+            .entry("a")
+            .string("x")
+            .entry("b")
+            .token("t")
+            .param("p", 1)
+            .inner_list(vec![Item::new(BareItem::Integer(1))])
+            .entry("c")
+            .byte_seq(&[1])
+            .entry("a")
+            .boolean(false)
+            .param("q", Decimal::new(5, 1))
+            .build()?;
+        assert_eq!("a=?0;q=0.5, b=(1);p=1, c=:AQ==:", dict.serialize_value()?);
+        assert_eq!(Dictionary::new(), DictionaryBuilder::new().build()?);
+        Ok(())
+    }
+
+    #[test]
+    fn build_reports_first_invalid_key() {
+        let builder = DictionaryBuilder::new()
+            .entry("a")
+            .param("Q", 1)
+            .entry("")
+            .finish();
+        assert_eq!(
+            Err("parse_key: first character is not lcalpha or '*'"),
+            builder.build()
+        );
+    }
+}
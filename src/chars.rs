@@ -0,0 +1,130 @@
+/*!
+Character classes used by the structured field value grammar.
+
+These are the exact predicates the parser and serializer use, exposed so that
+adjacent grammars or incremental validators don't have to duplicate them.
+*/
+
+/// Returns `true` if `c` is a `tchar` as defined in [RFC 7230](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+/// ```
+/// # use sfv::chars::is_tchar;
+/// assert!(is_tchar('!'));
+/// assert!(!is_tchar(':'));
+/// ```
+pub fn is_tchar(c: char) -> bool {
+    // See tchar values list in https://tools.ietf.org/html/rfc7230#section-3.2.6
+    let tchars = "!#$%&'*+-.^_`|~";
+    tchars.contains(c) || c.is_ascii_alphanumeric()
+}
+
+/// Returns `true` if `c` can start an `sf-token`.
+// sf-token = ( ALPHA / "*" ) *( tchar / ":" / "/" )
+pub fn is_allowed_token_first_char(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '*'
+}
+
+/// Returns `true` if `c` can appear in an `sf-token` after its first character.
+/// ```
+/// # use sfv::chars::is_allowed_token_char;
+/// assert!(is_allowed_token_char('/'));
+/// assert!(!is_allowed_token_char(' '));
+/// ```
+pub fn is_allowed_token_char(c: char) -> bool {
+    is_tchar(c) || c == ':' || c == '/'
+}
+
+/// Returns `true` if `c` can start a `key`.
+// key = ( lcalpha / "*" ) *( lcalpha / DIGIT / "_" / "-" / "." / "*" )
+pub fn is_allowed_key_first_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c == '*'
+}
+
+/// Returns `true` if `c` can appear in a `key` after its first character.
+/// ```
+/// # use sfv::chars::is_allowed_key_char;
+/// assert!(is_allowed_key_char('_'));
+/// assert!(!is_allowed_key_char('A'));
+/// ```
+pub fn is_allowed_key_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || "_-*.".contains(c)
+}
+
+/// Returns `true` if `c` can appear in an `sf-string` value, either as is or escaped.
+// unescaped = %x20-21 / %x23-5B / %x5D-7E
+// escaped   = "\" ( DQUOTE / "\" )
+pub fn is_allowed_string_char(c: char) -> bool {
+    (' '..='~').contains(&c)
+}
+
+/// Returns `true` if `c` can appear in the base64 content of an `sf-binary`.
+// base64    = ALPHA / DIGIT / "+" / "/" / "="
+pub fn is_allowed_b64_content(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '+' || c == '=' || c == '/'
+}
+
+/// Returns `true` if `value` is a valid `key`, usable as a Dictionary member or parameter name.
+/// Can be evaluated in constant context.
+/// ```
+/// # use sfv::chars::is_valid_key;
+/// const VALID: bool = is_valid_key("max-age");
+/// assert!(VALID);
+/// assert!(!is_valid_key("Max-Age"));
+/// assert!(!is_valid_key(""));
+/// ```
+pub const fn is_valid_key(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || !matches!(bytes[0], b'a'..=b'z' | b'*') {
+        return false;
+    }
+    let mut idx = 1;
+    while idx < bytes.len() {
+        if !matches!(bytes[idx], b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*') {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Returns `true` if `value` is a valid `sf-token`.
+/// Can be evaluated in constant context.
+/// ```
+/// # use sfv::chars::is_valid_token;
+/// const VALID: bool = is_valid_token("text/html");
+/// assert!(VALID);
+/// assert!(!is_valid_token("1a"));
+/// assert!(!is_valid_token("a b"));
+/// ```
+pub const fn is_valid_token(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || !(bytes[0].is_ascii_alphabetic() || bytes[0] == b'*') {
+        return false;
+    }
+    let mut idx = 1;
+    while idx < bytes.len() {
+        let byte = bytes[idx];
+        let is_tchar = byte.is_ascii_alphanumeric()
+            || matches!(
+                byte,
+                b'!' | b'#'
+                    | b'$'
+                    | b'%'
+                    | b'&'
+                    | b'\''
+                    | b'*'
+                    | b'+'
+                    | b'-'
+                    | b'.'
+                    | b'^'
+                    | b'_'
+                    | b'`'
+                    | b'|'
+                    | b'~'
+            );
+        if !(is_tchar || byte == b':' || byte == b'/') {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
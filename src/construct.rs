@@ -0,0 +1,321 @@
+/*!
+Support for `sfv_item!`, `sfv_list!` and `sfv_dict!` macros. Not part of the public API.
+*/
+
+use crate::{BareItem, Decimal};
+
+/// Values that can be written as bare items in construction macros.
+pub trait IntoBareItem {
+    fn into_bare_item(self) -> BareItem;
+}
+
+impl IntoBareItem for BareItem {
+    fn into_bare_item(self) -> BareItem {
+        self
+    }
+}
+
+impl IntoBareItem for i64 {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::Integer(self)
+    }
+}
+
+// Unsuffixed integer literals default to `i32`
+impl IntoBareItem for i32 {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::Integer(self.into())
+    }
+}
+
+impl IntoBareItem for Decimal {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::Decimal(self)
+    }
+}
+
+impl IntoBareItem for bool {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::Boolean(self)
+    }
+}
+
+impl IntoBareItem for &str {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::String(self.to_owned())
+    }
+}
+
+impl IntoBareItem for String {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::String(self)
+    }
+}
+
+impl IntoBareItem for Vec<u8> {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::ByteSeq(self)
+    }
+}
+
+impl IntoBareItem for &[u8] {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::ByteSeq(self.to_vec())
+    }
+}
+
+impl<const N: usize> IntoBareItem for &[u8; N] {
+    fn into_bare_item(self) -> BareItem {
+        BareItem::ByteSeq(self.to_vec())
+    }
+}
+
+pub fn bare_item<T: IntoBareItem>(value: T) -> BareItem {
+    value.into_bare_item()
+}
+
+/// Builds an `Item`.
+///
+/// The value is followed by parameters, each written as `; "key" = value`, or `; "key"` for `true`.
+/// Values are written as:
+/// - `@"token"` for tokens,
+/// - literals, with string literals for strings and byte string literals for byte sequences,
+/// - variables or `{ expressions }` of `BareItem`, `i64`, `Decimal`, `bool`, `String` or `Vec<u8>` type.
+///
+/// Keys and tokens are validated at compile time.
+/// ```
+/// # use sfv::{sfv_item, BareItem, SerializeValue};
+/// let q = 5;
+/// let item = sfv_item!(@"text/html"; "q" = q; "charset" = "utf-8"; "fast"; "id" = b"\x01");
+/// assert_eq!(item.serialize_value().unwrap(), "text/html;q=5;charset=\"utf-8\";fast;id=:AQ==:");
+/// assert_eq!(sfv_item!(-1).bare_item, BareItem::Integer(-1));
+/// ```
+/// Invalid keys and tokens don't compile:
+/// ```compile_fail
+/// let item = sfv::sfv_item!(1; "Key" = 2);
+/// ```
+/// ```compile_fail
+/// let item = sfv::sfv_item!(@"a b");
+/// ```
+#[macro_export]
+macro_rules! sfv_item {
+    (@ $token:literal $($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut item = $crate::Item::new($crate::__sfv_bare_item!(@ $token));
+        $crate::__sfv_params!((item.params) [[item]] $($rest)*);
+        item
+    }};
+    (- $value:literal $($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut item = $crate::Item::new($crate::__sfv_bare_item!(- $value));
+        $crate::__sfv_params!((item.params) [[item]] $($rest)*);
+        item
+    }};
+    ($value:tt $($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut item = $crate::Item::new($crate::__sfv_bare_item!($value));
+        $crate::__sfv_params!((item.params) [[item]] $($rest)*);
+        item
+    }};
+}
+
+/// Builds a `List`.
+///
+/// Members are separated by commas. Each member is an item, written as in `sfv_item!`,
+/// or an inner list of space-separated items in parentheses, optionally followed by parameters.
+/// ```
+/// # use sfv::{sfv_list, SerializeValue};
+/// let list = sfv_list![@"gzip"; "q" = 1, (@"br" "x"; "a") ; "q" = false, 12];
+/// assert_eq!(list.serialize_value().unwrap(), "gzip;q=1, (br \"x\";a);q=?0, 12");
+/// ```
+#[macro_export]
+macro_rules! sfv_list {
+    ($($tt:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut list = $crate::List::new();
+        $crate::__sfv_members!([list list], $($tt)*);
+        list
+    }};
+}
+
+/// Builds a `Dictionary`.
+///
+/// Members are separated by commas. Each member is written as `"key" = value`, or `"key"` for `true`,
+/// where value is an item or an inner list as in `sfv_list!`, optionally followed by parameters.
+/// ```
+/// # use sfv::{sfv_dict, SerializeValue};
+/// let dict = sfv_dict! { "a" = 1; "x", "b" = (@"t1" @"t2"), "c" };
+/// assert_eq!(dict.serialize_value().unwrap(), "a=1;x, b=(t1 t2), c");
+/// ```
+#[macro_export]
+macro_rules! sfv_dict {
+    ($($tt:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut dict = $crate::Dictionary::new();
+        $crate::__sfv_members!([dict dict], $($tt)*);
+        dict
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv_key {
+    ($key:literal) => {{
+        const _: () = ::std::assert!($crate::chars::is_valid_key($key), "invalid key");
+        ::std::string::String::from($key)
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv_bare_item {
+    (@ $token:literal) => {{
+        const _: () = ::std::assert!($crate::chars::is_valid_token($token), "invalid token");
+        $crate::BareItem::Token(::std::string::String::from($token))
+    }};
+    (- $value:literal) => {
+        $crate::construct::bare_item(-$value)
+    };
+    ($value:expr) => {
+        $crate::construct::bare_item($value)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv_inner_list {
+    ($($tt:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut items = ::std::vec::Vec::<$crate::Item>::new();
+        $crate::__sfv_members!([inner items]; $($tt)*);
+        $crate::InnerList::new(items)
+    }};
+}
+
+// Parses members of `$out` collection, given by its kind, passing their parameters to `__sfv_params`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv_members {
+    ([list $out:ident], $(,)?) => {};
+    ([list $out:ident], ($($inner:tt)*) $($rest:tt)*) => {
+        $out.push($crate::ListEntry::InnerList($crate::__sfv_inner_list!($($inner)*)));
+        $crate::__sfv_params!(($out.last_mut().unwrap().params_mut()) [[list $out]] $($rest)*);
+    };
+    ([list $out:ident], @ $token:literal $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!(@ $token)).into());
+        $crate::__sfv_params!(($out.last_mut().unwrap().params_mut()) [[list $out]] $($rest)*);
+    };
+    ([list $out:ident], - $value:literal $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!(- $value)).into());
+        $crate::__sfv_params!(($out.last_mut().unwrap().params_mut()) [[list $out]] $($rest)*);
+    };
+    ([list $out:ident], $value:tt $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!($value)).into());
+        $crate::__sfv_params!(($out.last_mut().unwrap().params_mut()) [[list $out]] $($rest)*);
+    };
+
+    ([dict $out:ident], $(,)?) => {};
+    ([dict $out:ident], $key:literal = ($($inner:tt)*) $($rest:tt)*) => {
+        $out.insert($crate::__sfv_key!($key), $crate::__sfv_inner_list!($($inner)*).into());
+        $crate::__sfv_params!(($out.get_mut($key).unwrap().params_mut()) [[dict $out]] $($rest)*);
+    };
+    ([dict $out:ident], $key:literal = @ $token:literal $($rest:tt)*) => {
+        $out.insert($crate::__sfv_key!($key), $crate::Item::new($crate::__sfv_bare_item!(@ $token)).into());
+        $crate::__sfv_params!(($out.get_mut($key).unwrap().params_mut()) [[dict $out]] $($rest)*);
+    };
+    ([dict $out:ident], $key:literal = - $value:literal $($rest:tt)*) => {
+        $out.insert($crate::__sfv_key!($key), $crate::Item::new($crate::__sfv_bare_item!(- $value)).into());
+        $crate::__sfv_params!(($out.get_mut($key).unwrap().params_mut()) [[dict $out]] $($rest)*);
+    };
+    ([dict $out:ident], $key:literal = $value:tt $($rest:tt)*) => {
+        $out.insert($crate::__sfv_key!($key), $crate::Item::new($crate::__sfv_bare_item!($value)).into());
+        $crate::__sfv_params!(($out.get_mut($key).unwrap().params_mut()) [[dict $out]] $($rest)*);
+    };
+    ([dict $out:ident], $key:literal $($rest:tt)*) => {
+        $out.insert($crate::__sfv_key!($key), $crate::Item::new($crate::BareItem::Boolean(true)).into());
+        $crate::__sfv_params!(($out.get_mut($key).unwrap().params_mut()) [[dict $out]] $($rest)*);
+    };
+
+    ([inner $out:ident];) => {};
+    ([inner $out:ident]; @ $token:literal $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!(@ $token)));
+        $crate::__sfv_params!(($out.last_mut().unwrap().params) [[inner $out]] $($rest)*);
+    };
+    ([inner $out:ident]; - $value:literal $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!(- $value)));
+        $crate::__sfv_params!(($out.last_mut().unwrap().params) [[inner $out]] $($rest)*);
+    };
+    ([inner $out:ident]; $value:tt $($rest:tt)*) => {
+        $out.push($crate::Item::new($crate::__sfv_bare_item!($value)));
+        $crate::__sfv_params!(($out.last_mut().unwrap().params) [[inner $out]] $($rest)*);
+    };
+}
+
+// Inserts parameters into `$target`, then passes the remaining input back to `__sfv_members`,
+// after a comma for List and Dictionary members, or directly for inner list items.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv_params {
+    (($($target:tt)*) [$($cont:tt)*]) => {};
+    (($($target:tt)*) [$($cont:tt)*] ; $key:literal = @ $token:literal $($rest:tt)*) => {
+        $($target)*.insert($crate::__sfv_key!($key), $crate::__sfv_bare_item!(@ $token));
+        $crate::__sfv_params!(($($target)*) [$($cont)*] $($rest)*);
+    };
+    (($($target:tt)*) [$($cont:tt)*] ; $key:literal = - $value:literal $($rest:tt)*) => {
+        $($target)*.insert($crate::__sfv_key!($key), $crate::__sfv_bare_item!(- $value));
+        $crate::__sfv_params!(($($target)*) [$($cont)*] $($rest)*);
+    };
+    (($($target:tt)*) [$($cont:tt)*] ; $key:literal = $value:tt $($rest:tt)*) => {
+        $($target)*.insert($crate::__sfv_key!($key), $crate::__sfv_bare_item!($value));
+        $crate::__sfv_params!(($($target)*) [$($cont)*] $($rest)*);
+    };
+    (($($target:tt)*) [$($cont:tt)*] ; $key:literal $($rest:tt)*) => {
+        $($target)*.insert($crate::__sfv_key!($key), $crate::BareItem::Boolean(true));
+        $crate::__sfv_params!(($($target)*) [$($cont)*] $($rest)*);
+    };
+    (($($target:tt)*) [$($cont:tt)*] , $($rest:tt)*) => {
+        $crate::__sfv_members!($($cont)*, $($rest)*);
+    };
+    (($($target:tt)*) [$($cont:tt)*] $($rest:tt)+) => {
+        $crate::__sfv_members!($($cont)*; $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BareItem, Decimal, Dictionary, InnerList, Item, List, Parser, SerializeValue};
+
+    #[test]
+    fn macros_match_parsed_values() {
+        let expected = Parser::parse_item("12;a=tok;b=\"s\";c;d=?0;e=-1;f=1.5".as_bytes()).unwrap();
+        let item = sfv_item!(12; "a" = @"tok"; "b" = "s"; "c"; "d" = false; "e" = -1; "f" = {Decimal::new(15, 1)});
+        assert_eq!(expected, item);
+
+        let expected = Parser::parse_list("a, (1 -2;x \"y\");p, :AQ==:, ()".as_bytes()).unwrap();
+        let bytes = vec![1];
+        let list: List = sfv_list![@"a", (1 -2; "x" "y"); "p", bytes, (),];
+        assert_eq!(expected, list);
+
+        let expected =
+            Parser::parse_dictionary("a, b=(c d);e=1, f=-1.5;g, h=z".as_bytes()).unwrap();
+        let dict: Dictionary = sfv_dict! {
+            "a",
+            "b" = (@"c" @"d"); "e" = 1,
+            "f" = {Decimal::new(-15, 1)}; "g",
+            "h" = {BareItem::Token("z".into())}
+        };
+        assert_eq!(expected, dict);
+    }
+
+    #[test]
+    fn macros_build_empty_values() {
+        assert_eq!(List::new(), sfv_list![]);
+        assert_eq!(Dictionary::new(), sfv_dict! {});
+        assert_eq!(vec![InnerList::new(vec![]).into()] as List, sfv_list![()]);
+        assert_eq!(
+            Err("serialize_list: serializing empty field is not allowed"),
+            sfv_list![].serialize_value()
+        );
+        assert_eq!(Item::new(BareItem::Boolean(true)), sfv_item!(true));
+    }
+}
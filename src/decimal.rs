@@ -0,0 +1,249 @@
+use crate::{Decimal, FromPrimitive, FromStr, SFVResult};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+
+const MAX_INTEGER_COMPONENT: i64 = 999_999_999_999;
+
+/// How to round a value to the three fractional digits allowed in `Decimal` items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecimalRounding {
+    /// Rounds to the nearest value, ties to the even digit. The serializer rounds this way.
+    HalfEven,
+    /// Rounds to the nearest value, ties away from zero.
+    HalfAwayFromZero,
+    /// Truncates digits beyond the third fractional one.
+    TowardZero,
+    /// Returns an error if the value has more than three fractional digits.
+    Exact,
+}
+
+/// Converts floating point numbers into `Decimal` values that can be serialized as they are.
+pub trait ToDecimal {
+    /// Returns the value as `Decimal` with at most three fractional digits, rounded as `rounding` specifies.
+    /// Returns an error if the value is not finite or its integer component has more than 12 digits.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Decimal, DecimalRounding, FromStr, ToDecimal};
+    /// assert_eq!(1.0625.to_decimal(DecimalRounding::HalfEven), Ok(Decimal::from_str("1.062").unwrap()));
+    /// assert_eq!(1.0625.to_decimal(DecimalRounding::HalfAwayFromZero), Ok(Decimal::from_str("1.063").unwrap()));
+    /// assert_eq!((-1.0629).to_decimal(DecimalRounding::TowardZero), Ok(Decimal::from_str("-1.062").unwrap()));
+    /// assert!(1.0625.to_decimal(DecimalRounding::Exact).is_err());
+    /// ```
+    fn to_decimal(&self, rounding: DecimalRounding) -> SFVResult<Decimal>;
+}
+
+impl ToDecimal for f64 {
+    fn to_decimal(&self, rounding: DecimalRounding) -> SFVResult<Decimal> {
+        if !self.is_finite() {
+            return Err("to_decimal: value is not finite");
+        }
+        let value = Decimal::from_f64(*self).ok_or("to_decimal: integer component > 12 digits")?;
+        round_decimal(value, rounding)
+    }
+}
+
+impl ToDecimal for f32 {
+    fn to_decimal(&self, rounding: DecimalRounding) -> SFVResult<Decimal> {
+        if !self.is_finite() {
+            return Err("to_decimal: value is not finite");
+        }
+        // Widening to f64 would bring in binary digits beyond f32 precision, e.g. 0.1f32 is 0.100000001490116...,
+        // so the shortest decimal representation of the f32 value is used instead
+        let value = Decimal::from_str(&self.to_string())
+            .map_err(|_| "to_decimal: integer component > 12 digits")?;
+        round_decimal(value, rounding)
+    }
+}
+
+fn round_decimal(value: Decimal, rounding: DecimalRounding) -> SFVResult<Decimal> {
+    let strategy = match rounding {
+        DecimalRounding::HalfEven | DecimalRounding::Exact => RoundingStrategy::MidpointNearestEven,
+        DecimalRounding::HalfAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+        DecimalRounding::TowardZero => RoundingStrategy::ToZero,
+    };
+    let rounded = value.round_dp_with_strategy(3, strategy);
+    if rounding == DecimalRounding::Exact && rounded != value {
+        return Err("to_decimal: value has more than 3 fractional digits");
+    }
+    if rounded.trunc().abs() > Decimal::from(MAX_INTEGER_COMPONENT) {
+        return Err("to_decimal: integer component > 12 digits");
+    }
+    Ok(rounded)
+}
+
+/// Builds and splits `Decimal` values as a whole number and thousandths, the precision of `Decimal` items.
+///
+/// The whole part is rounded down, so the thousandths are always non-negative, e.g. -1.25 is -2 and 750 thousandths.
+/// This keeps `from_int_millis(value.int_part(), value.frac_millis())` equal to `value`.
+pub trait DecimalParts: Sized {
+    /// Returns `int + millis / 1000`.
+    /// Returns an error if `millis` is not less than 1000, or the integer component of the result has more than 12 digits.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Decimal, DecimalParts, FromStr};
+    /// assert_eq!(Decimal::from_int_millis(12, 50), Ok(Decimal::from_str("12.05").unwrap()));
+    /// assert_eq!(Decimal::from_int_millis(-1, 500), Ok(Decimal::from_str("-0.5").unwrap()));
+    /// assert!(Decimal::from_int_millis(0, 1000).is_err());
+    /// ```
+    fn from_int_millis(int: i64, millis: u16) -> SFVResult<Self>;
+
+    /// Returns the largest whole number not greater than the value rounded to thousandths.
+    /// Saturates at `i64` bounds.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Decimal, DecimalParts, FromStr};
+    /// assert_eq!(Decimal::from_str("3.25").unwrap().int_part(), 3);
+    /// assert_eq!(Decimal::from_str("-3.25").unwrap().int_part(), -4);
+    /// ```
+    fn int_part(&self) -> i64;
+
+    /// Returns thousandths above `int_part`, from 0 to 999.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Decimal, DecimalParts, FromStr};
+    /// assert_eq!(Decimal::from_str("3.25").unwrap().frac_millis(), 250);
+    /// assert_eq!(Decimal::from_str("-3.25").unwrap().frac_millis(), 750);
+    /// ```
+    fn frac_millis(&self) -> u16;
+}
+
+impl DecimalParts for Decimal {
+    fn from_int_millis(int: i64, millis: u16) -> SFVResult<Decimal> {
+        if millis >= 1000 {
+            return Err("from_int_millis: millis is not less than 1000");
+        }
+        let value = Decimal::from(int) + Decimal::new(i64::from(millis), 3);
+        if value.trunc().abs() > Decimal::from(MAX_INTEGER_COMPONENT) {
+            return Err("from_int_millis: integer component > 12 digits");
+        }
+        Ok(value)
+    }
+
+    fn int_part(&self) -> i64 {
+        let floor = self.round_dp(3).floor();
+        floor.to_i64().unwrap_or(if floor.is_sign_negative() {
+            i64::MIN
+        } else {
+            i64::MAX
+        })
+    }
+
+    fn frac_millis(&self) -> u16 {
+        let rounded = self.round_dp(3);
+        let millis = (rounded - rounded.floor()) * Decimal::from(1000);
+        // Fraction of a rounded value is a whole number of thousandths below 1000
+        millis.to_u16().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_decimal_rounding() {
+        let decimal = |value| Ok(Decimal::from_str(value).unwrap());
+        assert_eq!(decimal("2.5"), 2.5.to_decimal(DecimalRounding::Exact));
+        assert_eq!(decimal("0.1"), 0.1.to_decimal(DecimalRounding::Exact));
+        assert_eq!(
+            decimal("-0.002"),
+            (-0.0025).to_decimal(DecimalRounding::HalfEven)
+        );
+        assert_eq!(
+            decimal("-0.003"),
+            (-0.0025).to_decimal(DecimalRounding::HalfAwayFromZero)
+        );
+        assert_eq!(
+            decimal("0.999"),
+            0.9999.to_decimal(DecimalRounding::TowardZero)
+        );
+        assert_eq!(decimal("1"), 0.9999.to_decimal(DecimalRounding::HalfEven));
+        assert_eq!(
+            Err("to_decimal: value has more than 3 fractional digits"),
+            0.0001.to_decimal(DecimalRounding::Exact)
+        );
+    }
+
+    #[test]
+    fn f32_to_decimal() {
+        let decimal = |value| Ok(Decimal::from_str(value).unwrap());
+        assert_eq!(decimal("0.1"), 0.1f32.to_decimal(DecimalRounding::Exact));
+        assert_eq!(
+            decimal("-2.675"),
+            (-2.675f32).to_decimal(DecimalRounding::Exact)
+        );
+        assert_eq!(
+            decimal("0.062"),
+            0.0625f32.to_decimal(DecimalRounding::HalfEven)
+        );
+        assert_eq!(
+            Err("to_decimal: value has more than 3 fractional digits"),
+            0.0001f32.to_decimal(DecimalRounding::Exact)
+        );
+        assert_eq!(
+            Err("to_decimal: integer component > 12 digits"),
+            f32::MAX.to_decimal(DecimalRounding::HalfEven)
+        );
+    }
+
+    #[test]
+    fn to_decimal_range() {
+        assert_eq!(
+            Ok(Decimal::from_str("999999999999.5").unwrap()),
+            999_999_999_999.5.to_decimal(DecimalRounding::Exact)
+        );
+        assert_eq!(
+            Err("to_decimal: integer component > 12 digits"),
+            999_999_999_999.999_6.to_decimal(DecimalRounding::HalfEven)
+        );
+        assert_eq!(
+            Err("to_decimal: integer component > 12 digits"),
+            1e30.to_decimal(DecimalRounding::HalfEven)
+        );
+        assert_eq!(
+            Err("to_decimal: value is not finite"),
+            f64::NAN.to_decimal(DecimalRounding::HalfEven)
+        );
+        assert_eq!(
+            Err("to_decimal: value is not finite"),
+            f32::INFINITY.to_decimal(DecimalRounding::TowardZero)
+        );
+    }
+
+    #[test]
+    fn decimal_parts() {
+        for value in &[
+            "0",
+            "0.001",
+            "-0.001",
+            "12.3",
+            "-12.3",
+            "999999999999.999",
+            "-999999999999.999",
+        ] {
+            let value = Decimal::from_str(value).unwrap();
+            assert_eq!(
+                Ok(value),
+                Decimal::from_int_millis(value.int_part(), value.frac_millis())
+            );
+        }
+
+        let value = Decimal::from_str("1.0005").unwrap();
+        assert_eq!((1, 0), (value.int_part(), value.frac_millis()));
+        let value = Decimal::from_str("-0.9999").unwrap();
+        assert_eq!((-1, 0), (value.int_part(), value.frac_millis()));
+
+        assert_eq!(
+            Err("from_int_millis: millis is not less than 1000"),
+            Decimal::from_int_millis(1, 1000)
+        );
+        assert_eq!(
+            Err("from_int_millis: integer component > 12 digits"),
+            Decimal::from_int_millis(1_000_000_000_000, 0)
+        );
+        assert_eq!(
+            Err("from_int_millis: integer component > 12 digits"),
+            Decimal::from_int_millis(i64::MIN, 0)
+        );
+    }
+}
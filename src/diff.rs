@@ -0,0 +1,420 @@
+/*!
+Structured comparison of `List` and `Dictionary` values.
+
+`diff_lists` and `diff_dictionaries` report members and parameters that were added, removed, changed or moved,
+e.g. to tell why a cache validator no longer matches, or to explain a failed test assertion.
+`List` members are matched by position and `Dictionary` members and parameters by key.
+As order is significant, no changes are reported exactly when the values are equivalent per `Parser::equivalent`.
+
+```
+use sfv::diff::{diff_dictionaries, Change};
+use sfv::Parser;
+
+let old = Parser::parse_dictionary("a=1, b;p=1, c".as_bytes()).unwrap();
+let new = Parser::parse_dictionary("b;p=2, a=2, d".as_bytes()).unwrap();
+let changes = diff_dictionaries(&old, &new);
+let lines: Vec<String> = changes.iter().map(Change::to_string).collect();
+assert_eq!(
+    lines,
+    vec![
+        "b: moved from 1 to 0",
+        "b;p: 1 -> 2",
+        "a: moved from 0 to 1",
+        "a: 1 -> 2",
+        "d: added ?1",
+        "c: removed",
+    ]
+);
+```
+*/
+
+use crate::serializer::Serializer;
+use crate::{BareItem, Dictionary, List, ListEntry, Parameters};
+use indexmap::IndexMap;
+use std::fmt;
+
+/// Member of `List` or `Dictionary` a `Change` applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Member<'a> {
+    /// Key of `Dictionary` member, `None` for `List` member.
+    pub key: Option<&'a str>,
+    /// Position of the member in the new value, or in the old value if it was removed.
+    pub position: usize,
+}
+
+/// Difference between old and new value of `List` or `Dictionary`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<'a> {
+    /// Member present only in the new value.
+    Added {
+        member: Member<'a>,
+        entry: &'a ListEntry,
+    },
+    /// Member present only in the old value.
+    Removed {
+        member: Member<'a>,
+        entry: &'a ListEntry,
+    },
+    /// Dictionary member present in both values whose order relative to the other such members changed.
+    /// `member.position` is its position in the new value.
+    Moved {
+        member: Member<'a>,
+        old_position: usize,
+    },
+    /// Member whose bare item or inner list items differ, including changes between `Item` and `InnerList`.
+    /// Parameters of the member are compared separately.
+    ValueChanged {
+        member: Member<'a>,
+        old: &'a ListEntry,
+        new: &'a ListEntry,
+    },
+    /// Parameter present only in the new value of the member.
+    ParamAdded {
+        member: Member<'a>,
+        key: &'a str,
+        value: &'a BareItem,
+    },
+    /// Parameter present only in the old value of the member.
+    ParamRemoved {
+        member: Member<'a>,
+        key: &'a str,
+        value: &'a BareItem,
+    },
+    /// Parameter with different values in the old and new value of the member.
+    ParamChanged {
+        member: Member<'a>,
+        key: &'a str,
+        old: &'a BareItem,
+        new: &'a BareItem,
+    },
+    /// Parameter present in both values of the member whose order relative to the other such parameters changed.
+    ParamMoved {
+        member: Member<'a>,
+        key: &'a str,
+        old_position: usize,
+        new_position: usize,
+    },
+}
+
+/// Compares `List` members at the same positions.
+/// Members past the end of the shorter list are reported as added or removed.
+pub fn diff_lists<'a>(old: &'a List, new: &'a List) -> Vec<Change<'a>> {
+    let mut changes = vec![];
+    for position in 0..old.len().max(new.len()) {
+        let member = Member {
+            key: None,
+            position,
+        };
+        match (old.get(position), new.get(position)) {
+            (Some(old), Some(new)) => diff_entries(member, old, new, &mut changes),
+            (Some(entry), None) => changes.push(Change::Removed { member, entry }),
+            (None, Some(entry)) => changes.push(Change::Added { member, entry }),
+            (None, None) => {}
+        }
+    }
+    changes
+}
+
+/// Compares `Dictionary` members with the same keys.
+/// Members present in both values are reported as moved if their order relative to each other changed,
+/// while added or removed members alone don't move the others.
+/// Changes of members present in the new value are reported in its order, followed by removed members in their old order.
+pub fn diff_dictionaries<'a>(old: &'a Dictionary, new: &'a Dictionary) -> Vec<Change<'a>> {
+    let mut changes = vec![];
+    let moved = moved_positions(old, new);
+    for (position, (key, new_entry)) in new.iter().enumerate() {
+        let member = Member {
+            key: Some(key),
+            position,
+        };
+        if let Some(old_position) = moved[position] {
+            changes.push(Change::Moved {
+                member,
+                old_position,
+            });
+        }
+        match old.get(key) {
+            Some(old_entry) => diff_entries(member, old_entry, new_entry, &mut changes),
+            None => changes.push(Change::Added {
+                member,
+                entry: new_entry,
+            }),
+        }
+    }
+    for (position, (key, entry)) in old.iter().enumerate() {
+        if !new.contains_key(key) {
+            let member = Member {
+                key: Some(key),
+                position,
+            };
+            changes.push(Change::Removed { member, entry });
+        }
+    }
+    changes
+}
+
+fn diff_entries<'a>(
+    member: Member<'a>,
+    old: &'a ListEntry,
+    new: &'a ListEntry,
+    changes: &mut Vec<Change<'a>>,
+) {
+    let value_changed = match (old, new) {
+        (ListEntry::Item(old), ListEntry::Item(new)) => old.bare_item != new.bare_item,
+        (ListEntry::InnerList(old), ListEntry::InnerList(new)) => old.items != new.items,
+        _ => true,
+    };
+    if value_changed {
+        changes.push(Change::ValueChanged { member, old, new });
+    }
+    diff_params(member, old.params(), new.params(), changes);
+}
+
+fn diff_params<'a>(
+    member: Member<'a>,
+    old: &'a Parameters,
+    new: &'a Parameters,
+    changes: &mut Vec<Change<'a>>,
+) {
+    let moved = moved_positions(old, new);
+    for (new_position, (key, new_value)) in new.iter().enumerate() {
+        if let Some(old_position) = moved[new_position] {
+            changes.push(Change::ParamMoved {
+                member,
+                key,
+                old_position,
+                new_position,
+            });
+        }
+        match old.get(key) {
+            Some(old_value) if old_value != new_value => changes.push(Change::ParamChanged {
+                member,
+                key,
+                old: old_value,
+                new: new_value,
+            }),
+            Some(_) => {}
+            None => changes.push(Change::ParamAdded {
+                member,
+                key,
+                value: new_value,
+            }),
+        }
+    }
+    for (key, value) in old {
+        if !new.contains_key(key) {
+            changes.push(Change::ParamRemoved { member, key, value });
+        }
+    }
+}
+
+// Old positions of keys present in both maps whose rank among such keys differs, indexed by new position
+fn moved_positions<V>(old: &IndexMap<String, V>, new: &IndexMap<String, V>) -> Vec<Option<usize>> {
+    let common: Vec<usize> = new.keys().filter_map(|key| old.get_index_of(key)).collect();
+    let mut in_old_order = common.clone();
+    in_old_order.sort_unstable();
+
+    let mut rank = 0;
+    new.keys()
+        .map(|key| {
+            let old_position = old.get_index_of(key)?;
+            rank += 1;
+            Some(old_position).filter(|_| in_old_order[rank - 1] != old_position)
+        })
+        .collect()
+}
+
+impl fmt::Display for Member<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.key {
+            Some(key) => f.write_str(key),
+            None => write!(f, "[{}]", self.position),
+        }
+    }
+}
+
+impl fmt::Display for Change<'_> {
+    /// Formats the change as a line like `a;q: 1 -> 2`, with values in their serialized form.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { member, entry } => write!(f, "{}: added {}", member, Entry(entry)),
+            Change::Removed { member, .. } => write!(f, "{}: removed", member),
+            Change::Moved {
+                member,
+                old_position,
+            } => write!(
+                f,
+                "{}: moved from {} to {}",
+                member, old_position, member.position
+            ),
+            Change::ValueChanged { member, old, new } => {
+                write!(f, "{}: {} -> {}", member, Entry(old), Entry(new))
+            }
+            Change::ParamAdded { member, key, value } => {
+                write!(f, "{};{}: added {}", member, key, Value(value))
+            }
+            Change::ParamRemoved { member, key, .. } => write!(f, "{};{}: removed", member, key),
+            Change::ParamChanged {
+                member,
+                key,
+                old,
+                new,
+            } => write!(f, "{};{}: {} -> {}", member, key, Value(old), Value(new)),
+            Change::ParamMoved {
+                member,
+                key,
+                old_position,
+                new_position,
+            } => write!(
+                f,
+                "{};{}: moved from {} to {}",
+                member, key, old_position, new_position
+            ),
+        }
+    }
+}
+
+// Serialized member value, or its debug representation if it can't be serialized
+struct Entry<'a>(&'a ListEntry);
+
+impl fmt::Display for Entry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+        match Serializer::serialize_list_member(self.0, &mut output) {
+            Ok(()) => f.write_str(&output),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+// Serialized bare item, or its debug representation if it can't be serialized
+struct Value<'a>(&'a BareItem);
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+        match Serializer::serialize_bare_item(self.0, &mut output) {
+            Ok(()) => f.write_str(&output),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, SFVResult};
+
+    fn lines(changes: &[Change<'_>]) -> Vec<String> {
+        changes.iter().map(Change::to_string).collect()
+    }
+
+    #[test]
+    fn diff_lists_by_position() -> SFVResult<()> {
+        let old = Parser::parse_list("a, (1 2);p, c;x=1".as_bytes())?;
+        let new = Parser::parse_list("a, (1 3);p, c;y, d".as_bytes())?;
+        assert_eq!(
+            vec![
+                "[1]: (1 2);p -> (1 3);p",
+                "[2];y: added ?1",
+                "[2];x: removed",
+                "[3]: added d",
+            ],
+            lines(&diff_lists(&old, &new))
+        );
+        assert_eq!(
+            vec!["[1]: removed", "[2]: removed", "[3]: removed"],
+            lines(&diff_lists(&new, &List::new()))[1..]
+        );
+        assert!(diff_lists(&old, &old).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn diff_dictionaries_by_key() -> SFVResult<()> {
+        let old = Parser::parse_dictionary("a=1;p, b=(1), c".as_bytes())?;
+        let new = Parser::parse_dictionary("c, b=1, a=1;p=?0".as_bytes())?;
+        let changes = diff_dictionaries(&old, &new);
+        assert_eq!(
+            vec![
+                "c: moved from 2 to 0",
+                "b: (1) -> 1",
+                "a: moved from 0 to 2",
+                "a;p: ?1 -> ?0",
+            ],
+            lines(&changes)
+        );
+        assert_eq!(
+            Change::ValueChanged {
+                member: Member {
+                    key: Some("b"),
+                    position: 1
+                },
+                old: &old["b"],
+                new: &new["b"],
+            },
+            changes[1]
+        );
+
+        let empty = Dictionary::new();
+        let removed = diff_dictionaries(&old, &empty);
+        assert_eq!(
+            Change::Removed {
+                member: Member {
+                    key: Some("c"),
+                    position: 2
+                },
+                entry: &old["c"],
+            },
+            removed[2]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_dictionaries_reports_reordering() -> SFVResult<()> {
+        let old = Parser::parse_dictionary("a=1, b;x;y".as_bytes())?;
+        let new = Parser::parse_dictionary("b;y;x, a=1".as_bytes())?;
+        assert!(!Parser::equivalent::<Dictionary>(
+            "a=1, b;x;y".as_bytes(),
+            "b;y;x, a=1".as_bytes()
+        )?);
+        let changes = diff_dictionaries(&old, &new);
+        assert_eq!(
+            vec![
+                "b: moved from 1 to 0",
+                "b;y: moved from 1 to 0",
+                "b;x: moved from 0 to 1",
+                "a: moved from 0 to 1",
+            ],
+            lines(&changes)
+        );
+        assert_eq!(
+            Change::ParamMoved {
+                member: Member {
+                    key: Some("b"),
+                    position: 0
+                },
+                key: "y",
+                old_position: 1,
+                new_position: 0,
+            },
+            changes[1]
+        );
+
+        // Adding or removing members doesn't move the ones around them
+        let new = Parser::parse_dictionary("c, a=1;p, b;x;y".as_bytes())?;
+        assert_eq!(
+            vec!["c: added ?1", "a;p: added ?1"],
+            lines(&diff_dictionaries(&old, &new))
+        );
+
+        let old = Parser::parse_list("a;x;y".as_bytes())?;
+        let new = Parser::parse_list("a;y;x".as_bytes())?;
+        assert_eq!(
+            vec!["[0];y: moved from 1 to 0", "[0];x: moved from 0 to 1"],
+            lines(&diff_lists(&old, &new))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use crate::utils;
+use crate::{BareItem, Item, ListEntry, Parameters, ParseOptions, Parser, SFVResult};
+use std::collections::VecDeque;
+use std::iter::Peekable;
+use std::str::{from_utf8, Chars};
+
+/// Event produced by `Events` pull parser.
+///
+/// Parameters follow the bare item, inner list or dictionary member they belong to,
+/// each as `ParamKey` followed by `BareItem` with its value.
+/// Dictionary members and parameters without a value are followed by `BareItem::Boolean(true)`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Event {
+    /// Name of a dictionary member, followed by its value.
+    DictKey(String),
+    /// Bare item of an item, inner list member or parameter value.
+    BareItem(BareItem),
+    /// Name of a parameter, followed by its value.
+    ParamKey(String),
+    /// Beginning of an inner list, followed by its items and `InnerListEnd`.
+    InnerListStart,
+    /// End of an inner list, followed by the inner list's parameters.
+    InnerListEnd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FieldKind {
+    Item,
+    List,
+    Dictionary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    Members,
+    Done,
+}
+
+/// Iterator of `Event`s of a structured field value, returned by `Parser::*_events` methods.
+///
+/// Input is parsed lazily, one top-level member at a time, so events of the members preceding
+/// an invalid one are yielded before the error. The iterator ends after yielding an error.
+/// # Examples
+/// ```
+/// # use sfv::{BareItem, Event, Parser};
+/// let events: Result<Vec<Event>, _> = Parser::dictionary_events("a=(1 2);q, b".as_bytes()).collect();
+/// assert_eq!(
+///     events.unwrap(),
+///     vec![
+///         Event::DictKey("a".into()),
+///         Event::InnerListStart,
+///         Event::BareItem(BareItem::Integer(1)),
+///         Event::BareItem(BareItem::Integer(2)),
+///         Event::InnerListEnd,
+///         Event::ParamKey("q".into()),
+///         Event::BareItem(BareItem::Boolean(true)),
+///         Event::DictKey("b".into()),
+///         Event::BareItem(BareItem::Boolean(true)),
+///     ]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Events<'a> {
+    input_chars: Peekable<Chars<'a>>,
+    field_type: FieldKind,
+    state: State,
+    pending: VecDeque<Event>,
+    // Error following pending events
+    error: Option<&'static str>,
+}
+
+impl<'a> Events<'a> {
+    fn new(input_bytes: &'a [u8], field_type: FieldKind) -> Events<'a> {
+        // https://httpwg.org/specs/rfc8941.html#text-parse
+        let (input, error) = match from_utf8(input_bytes) {
+            Ok(input) if input.is_ascii() => (input, None),
+            _ => ("", Some("parse: non-ascii characters in input")),
+        };
+        Events {
+            input_chars: input.chars().peekable(),
+            field_type,
+            state: State::Start,
+            pending: VecDeque::new(),
+            error,
+        }
+    }
+
+    // Parses the next top-level member into pending events.
+    // Errors after a complete member are deferred until its events are yielded.
+    fn parse_member(&mut self) -> SFVResult<()> {
+        let options = ParseOptions::default();
+        let input_chars = &mut self.input_chars;
+
+        if self.state == State::Start {
+            utils::consume_sp_chars(input_chars);
+            self.state = State::Members;
+        }
+
+        match self.field_type {
+            FieldKind::Item => {
+                let item = Parser::parse_item_value(input_chars, &options)?;
+                push_item(&mut self.pending, item);
+
+                utils::consume_sp_chars(input_chars);
+                if input_chars.next().is_some() {
+                    self.error = Some("parse: trailing characters after parsed value");
+                }
+                self.state = State::Done;
+                return Ok(());
+            }
+            FieldKind::List | FieldKind::Dictionary => {
+                if input_chars.peek().is_none() {
+                    self.state = State::Done;
+                    return Ok(());
+                }
+                parse_member_events(input_chars, self.field_type, &mut self.pending)?;
+            }
+        }
+
+        let (trailing_characters, trailing_comma) = separator_errors(self.field_type);
+
+        utils::consume_ows_chars(input_chars);
+        match input_chars.next() {
+            None => {
+                self.state = State::Done;
+                return Ok(());
+            }
+            Some(',') => (),
+            Some(_) => self.error = Some(trailing_characters),
+        }
+        utils::consume_ows_chars(input_chars);
+        if self.error.is_none() && input_chars.peek().is_none() {
+            self.error = Some(trailing_comma);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = SFVResult<Event>;
+
+    fn next(&mut self) -> Option<SFVResult<Event>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+
+            if let Some(err) = self.error.take() {
+                self.state = State::Done;
+                return Some(Err(err));
+            }
+
+            match self.state {
+                State::Done => return None,
+                State::Start | State::Members => {
+                    if let Err(err) = self.parse_member() {
+                        self.pending.clear();
+                        self.state = State::Done;
+                        return Some(Err(err));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Parses a single member of List or Dictionary, appending its events to `pending`.
+pub(crate) fn parse_member_events(
+    input_chars: &mut Peekable<Chars>,
+    field_type: FieldKind,
+    pending: &mut VecDeque<Event>,
+) -> SFVResult<()> {
+    let options = ParseOptions::default();
+    let member = if field_type == FieldKind::Dictionary {
+        let this_key = Parser::parse_key(input_chars)?;
+        pending.push_back(Event::DictKey(this_key));
+        if let Some('=') = input_chars.peek() {
+            input_chars.next();
+            Parser::parse_list_entry(input_chars, &options)?
+        } else {
+            let params = Parser::parse_parameters(input_chars, &options)?;
+            Item::with_params(BareItem::Boolean(true), params).into()
+        }
+    } else {
+        Parser::parse_list_entry(input_chars, &options)?
+    };
+    push_entry(pending, member);
+    Ok(())
+}
+
+// Returns errors for trailing characters after a member and for a trailing comma.
+pub(crate) fn separator_errors(field_type: FieldKind) -> (&'static str, &'static str) {
+    match field_type {
+        FieldKind::Dictionary => (
+            "parse_dict: trailing characters after dictionary member",
+            "parse_dict: trailing comma",
+        ),
+        _ => (
+            "parse_list: trailing characters after list member",
+            "parse_list: trailing comma",
+        ),
+    }
+}
+
+fn push_entry(pending: &mut VecDeque<Event>, entry: ListEntry) {
+    match entry {
+        ListEntry::Item(item) => push_item(pending, item),
+        ListEntry::InnerList(inner_list) => {
+            pending.push_back(Event::InnerListStart);
+            for item in inner_list.items {
+                push_item(pending, item);
+            }
+            pending.push_back(Event::InnerListEnd);
+            push_params(pending, inner_list.params);
+        }
+    }
+}
+
+fn push_item(pending: &mut VecDeque<Event>, item: Item) {
+    pending.push_back(Event::BareItem(item.bare_item));
+    push_params(pending, item.params);
+}
+
+fn push_params(pending: &mut VecDeque<Event>, params: Parameters) {
+    for (key, value) in params {
+        pending.push_back(Event::ParamKey(key));
+        pending.push_back(Event::BareItem(value));
+    }
+}
+
+impl Parser {
+    /// Returns iterator of `Event`s of input parsed as structured field value of Dictionary type.
+    pub fn dictionary_events(input_bytes: &[u8]) -> Events<'_> {
+        Events::new(input_bytes, FieldKind::Dictionary)
+    }
+
+    /// Returns iterator of `Event`s of input parsed as structured field value of List type.
+    pub fn list_events(input_bytes: &[u8]) -> Events<'_> {
+        Events::new(input_bytes, FieldKind::List)
+    }
+
+    /// Returns iterator of `Event`s of input parsed as structured field value of Item type.
+    pub fn item_events(input_bytes: &[u8]) -> Events<'_> {
+        Events::new(input_bytes, FieldKind::Item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_events_are_yielded_before_error() {
+        let mut events = Parser::list_events(" tok;a=1, \"s\", (x);b, 1 2".as_bytes());
+        let expected = vec![
+            Event::BareItem(BareItem::Token("tok".into())),
+            Event::ParamKey("a".into()),
+            Event::BareItem(BareItem::Integer(1)),
+            Event::BareItem(BareItem::String("s".into())),
+            Event::InnerListStart,
+            Event::BareItem(BareItem::Token("x".into())),
+            Event::InnerListEnd,
+            Event::ParamKey("b".into()),
+            Event::BareItem(BareItem::Boolean(true)),
+            Event::BareItem(BareItem::Integer(1)),
+        ];
+        for event in expected {
+            assert_eq!(Some(Ok(event)), events.next());
+        }
+        assert_eq!(
+            Some(Err("parse_list: trailing characters after list member")),
+            events.next()
+        );
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn empty_and_invalid_inputs() {
+        assert_eq!(None, Parser::list_events("  ".as_bytes()).next());
+        assert_eq!(None, Parser::dictionary_events("".as_bytes()).next());
+        assert_eq!(
+            Some(Err("parse_bare_item: empty item")),
+            Parser::item_events("".as_bytes()).next()
+        );
+        assert_eq!(
+            vec![
+                Ok(Event::DictKey("a".into())),
+                Ok(Event::BareItem(BareItem::Boolean(true))),
+                Err("parse_dict: trailing comma"),
+            ],
+            Parser::dictionary_events("a,".as_bytes()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Err("parse: non-ascii characters in input")],
+            Parser::item_events("\"ü\"".as_bytes()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn item_events() {
+        let events: SFVResult<Vec<Event>> =
+            Parser::item_events("?0;p=:AQ==: ".as_bytes()).collect();
+        assert_eq!(
+            vec![
+                Event::BareItem(BareItem::Boolean(false)),
+                Event::ParamKey("p".into()),
+                Event::BareItem(BareItem::ByteSeq(vec![1])),
+            ],
+            events.unwrap()
+        );
+        assert_eq!(
+            Some(Err("parse: trailing characters after parsed value")),
+            Parser::item_events("1 2".as_bytes()).nth(1)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{Dictionary, Item, List, ListEntry, Parameters, Parser, SFVResult, SerializeValue};
+
+/// Structured field type that can be parsed from and serialized into a field value.
+///
+/// Implemented for `Item`, `List` and `Dictionary`. Implement it for typed fields built on top of them
+/// to parse them with `Parser::parse_field` and to use them in code generic over field types.
+/// # Examples
+/// ```
+/// # use sfv::{FieldType, Item, Parser};
+/// struct Age(i64);
+///
+/// impl FieldType for Age {
+///     fn parse_field(input_bytes: &[u8]) -> Result<Self, &'static str> {
+///         let item = Item::parse_field(input_bytes)?;
+///         item.bare_item.as_int().map(Age).ok_or("parse_field: age is not an integer")
+///     }
+///
+///     fn serialize_field(&self) -> Result<String, &'static str> {
+///         Item::new(self.0.into()).serialize_field()
+///     }
+/// }
+///
+/// let age: Age = Parser::parse_field("3600".as_bytes()).unwrap();
+/// assert_eq!(age.0, 3600);
+/// assert_eq!(age.serialize_field().unwrap(), "3600");
+/// ```
+pub trait FieldType: Sized {
+    /// Parses field value into `Self`.
+    fn parse_field(input_bytes: &[u8]) -> SFVResult<Self>;
+
+    /// Serializes `Self` into field value.
+    fn serialize_field(&self) -> SFVResult<String>;
+
+    /// Returns `true` if `self` and `other` are the same field value.
+    /// Defaults to `PartialEq`. `Item`, `List` and `Dictionary` override it to compare
+    /// `Dictionary` members and parameters in order, as they are ordered maps.
+    fn eq_value(&self, other: &Self) -> bool
+    where
+        Self: PartialEq,
+    {
+        self == other
+    }
+
+    /// Returns `true` if `input_bytes` parses into the same value as `self`, as `Parser::equivalent` compares them.
+    /// Returns `false` if `input_bytes` can't be parsed.
+    /// # Examples
+    /// ```
+    /// # use sfv::{FieldType, Parser};
+    /// let list = Parser::parse_list("a;q=0.5;v, b".as_bytes()).unwrap();
+    /// assert!(list.eq_field("a;q=0.50;v,   b".as_bytes()));
+    /// assert!(!list.eq_field("a;v;q=0.5, b".as_bytes()));
+    /// assert!(!list.eq_field("a, b".as_bytes()));
+    /// ```
+    fn eq_field(&self, input_bytes: &[u8]) -> bool
+    where
+        Self: PartialEq,
+    {
+        match Self::parse_field(input_bytes) {
+            Ok(parsed) => parsed.eq_value(self),
+            Err(_) => false,
+        }
+    }
+}
+
+impl FieldType for Item {
+    fn parse_field(input_bytes: &[u8]) -> SFVResult<Self> {
+        Parser::parse_item(input_bytes)
+    }
+
+    fn serialize_field(&self) -> SFVResult<String> {
+        self.serialize_value()
+    }
+
+    fn eq_value(&self, other: &Self) -> bool {
+        items_eq(self, other)
+    }
+}
+
+impl FieldType for List {
+    fn parse_field(input_bytes: &[u8]) -> SFVResult<Self> {
+        Parser::parse_list(input_bytes)
+    }
+
+    fn serialize_field(&self) -> SFVResult<String> {
+        self.serialize_value()
+    }
+
+    fn eq_value(&self, other: &Self) -> bool {
+        lists_eq(self, other)
+    }
+}
+
+impl FieldType for Dictionary {
+    fn parse_field(input_bytes: &[u8]) -> SFVResult<Self> {
+        Parser::parse_dictionary(input_bytes)
+    }
+
+    fn serialize_field(&self) -> SFVResult<String> {
+        self.serialize_value()
+    }
+
+    fn eq_value(&self, other: &Self) -> bool {
+        dicts_eq(self, other)
+    }
+}
+
+impl Parser {
+    /// Parses input into field value of type `T`.
+    pub fn parse_field<T: FieldType>(input_bytes: &[u8]) -> SFVResult<T> {
+        T::parse_field(input_bytes)
+    }
+
+    /// Returns `true` if both inputs parse into the same field value of type `T`, as `FieldType::eq_value` compares them,
+    /// i.e. they differ at most in whitespace, number formatting or string escaping.
+    /// Members and parameters in a different order make a different value.
+    /// Returns an error if either input can't be parsed.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Dictionary, Item, Parser};
+    /// assert_eq!(Parser::equivalent::<Dictionary>(b"a=1.50,b=?1", b"a=1.5, b"), Ok(true));
+    /// assert_eq!(Parser::equivalent::<Dictionary>(b"a, b", b"b, a"), Ok(false));
+    /// assert_eq!(Parser::equivalent::<Item>(b"1", b"1.0"), Ok(false));
+    /// assert!(Parser::equivalent::<Item>(b"1", b"1 2").is_err());
+    /// ```
+    pub fn equivalent<T: FieldType + PartialEq>(a: &[u8], b: &[u8]) -> SFVResult<bool> {
+        Ok(T::parse_field(a)?.eq_value(&T::parse_field(b)?))
+    }
+}
+
+// `PartialEq` of `IndexMap` ignores order, so maps are compared entry by entry
+
+fn params_eq(a: &Parameters, b: &Parameters) -> bool {
+    a.iter().eq(b.iter())
+}
+
+fn items_eq(a: &Item, b: &Item) -> bool {
+    a.bare_item == b.bare_item && params_eq(&a.params, &b.params)
+}
+
+fn entries_eq(a: &ListEntry, b: &ListEntry) -> bool {
+    match (a, b) {
+        (ListEntry::Item(a), ListEntry::Item(b)) => items_eq(a, b),
+        (ListEntry::InnerList(a), ListEntry::InnerList(b)) => {
+            a.items.len() == b.items.len()
+                && a.items.iter().zip(&b.items).all(|(a, b)| items_eq(a, b))
+                && params_eq(&a.params, &b.params)
+        }
+        _ => false,
+    }
+}
+
+fn lists_eq(a: &List, b: &List) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| entries_eq(a, b))
+}
+
+fn dicts_eq(a: &Dictionary, b: &Dictionary) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((a_key, a), (b_key, b))| a_key == b_key && entries_eq(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: FieldType>(input: &str) -> SFVResult<String> {
+        Parser::parse_field::<T>(input.as_bytes())?.serialize_field()
+    }
+
+    #[test]
+    fn generic_over_field_types() {
+        assert_eq!(Ok("1;a".to_owned()), round_trip::<Item>("1;a"));
+        assert_eq!(Ok("1, (2)".to_owned()), round_trip::<List>("1,(2)"));
+        assert_eq!(Ok("a=1, b".to_owned()), round_trip::<Dictionary>("a=1,b"));
+        assert_eq!(
+            Err("serialize_list: serializing empty field is not allowed"),
+            round_trip::<List>("")
+        );
+    }
+
+    #[test]
+    fn equivalent_field_values() {
+        assert_eq!(
+            Ok(true),
+            Parser::equivalent::<List>(b"\"a\",  (1 2);p", b"\"a\", (1   2);p")
+        );
+        // Dictionaries and parameters are ordered maps
+        assert_eq!(
+            Ok(false),
+            Parser::equivalent::<Dictionary>(b"a, b;x;y", b"b;x;y, a")
+        );
+        assert_eq!(
+            Ok(false),
+            Parser::equivalent::<Dictionary>(b"a, b;x;y", b"a, b;y;x")
+        );
+        assert_eq!(
+            Ok(false),
+            Parser::equivalent::<List>(b"(1;x;y)", b"(1;y;x)")
+        );
+        assert_eq!(
+            Ok(true),
+            Parser::equivalent::<Dictionary>(b"a=(1;x 2), b;x", b"a=(1;x  2),b;x=?1")
+        );
+        assert_eq!(Ok(false), Parser::equivalent::<List>(b"a, b", b"b, a"));
+        assert_eq!(
+            Err("parse_list: trailing comma"),
+            Parser::equivalent::<List>(b"a", b"a,")
+        );
+
+        let item = Parser::parse_item(b"tok;a=1").unwrap();
+        assert!(!item.eq_field(b"tok;a=1.0"));
+        assert!(item.eq_field(b"tok;a=1 "));
+        assert!(!item.eq_field(b"tok;"));
+    }
+}
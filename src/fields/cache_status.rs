@@ -0,0 +1,246 @@
+use super::token_or_string;
+use crate::{BareItem, Item, List, ListEntry, Parameters, Parser, SFVResult, SerializeValue};
+
+/// `Cache-Status` field, describing how caches handled a request, as defined in RFC 9211.
+///
+/// Wire form is a List with one item per cache, in the order the response passed through them,
+/// from the origin server towards the client. Each item identifies the cache and has parameters describing its handling.
+/// # Examples
+/// ```
+/// # use sfv::fields::{CacheStatus, ForwardReason};
+/// # use sfv::SerializeValue;
+/// let status = CacheStatus::parse("OriginCache; hit; ttl=1100, \"CDN Company Here\"; fwd=uri-miss; stored".as_bytes()).unwrap();
+/// assert!(status.entries[0].hit);
+/// assert_eq!(status.entries[0].ttl, Some(1100));
+/// assert_eq!(status.entries[1].cache, "CDN Company Here");
+/// assert_eq!(status.entries[1].fwd, Some(ForwardReason::UriMiss));
+/// assert_eq!(
+///     status.serialize_value().unwrap(),
+///     "OriginCache;hit;ttl=1100, \"CDN Company Here\";fwd=uri-miss;stored"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CacheStatus {
+    /// Entries of each cache, from the origin server towards the client.
+    pub entries: Vec<CacheStatusEntry>,
+}
+
+/// Handling of a request by a single cache, a member of `CacheStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CacheStatusEntry {
+    /// Identifier of the cache.
+    pub cache: String,
+    /// `hit` parameter, whether the request was satisfied by the cache.
+    pub hit: bool,
+    /// `fwd` parameter, why the request was forwarded towards the origin.
+    pub fwd: Option<ForwardReason>,
+    /// `fwd-status` parameter, status code of the forwarded request's response.
+    pub fwd_status: Option<u16>,
+    /// `ttl` parameter, remaining freshness lifetime of the response in seconds. Negative if it's stale.
+    pub ttl: Option<i64>,
+    /// `stored` parameter, whether the forwarded request's response was stored.
+    pub stored: bool,
+    /// `collapsed` parameter, whether the forwarded request was collapsed with another one.
+    pub collapsed: bool,
+    /// `key` parameter, implementation-specific cache key.
+    pub key: Option<String>,
+    /// `detail` parameter, implementation-specific details.
+    pub detail: Option<String>,
+}
+
+/// Value of `fwd` parameter of `CacheStatusEntry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ForwardReason {
+    /// The cache was configured to not handle the request.
+    Bypass,
+    /// The request method's semantics require it to be forwarded.
+    Method,
+    /// The cache did not contain any responses matching the request URI.
+    UriMiss,
+    /// The cache contained responses for the URI, but not matching the selecting header fields.
+    VaryMiss,
+    /// The cache did not contain any responses that could be used.
+    Miss,
+    /// The cache was able to select a fresh response, but the request's semantics prevented its use.
+    Request,
+    /// The cache was able to select a response, but it was stale.
+    Stale,
+    /// The cache was able to select a partial response, but it did not contain all requested ranges.
+    Partial,
+}
+
+impl ForwardReason {
+    /// Returns token of the reason.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForwardReason::Bypass => "bypass",
+            ForwardReason::Method => "method",
+            ForwardReason::UriMiss => "uri-miss",
+            ForwardReason::VaryMiss => "vary-miss",
+            ForwardReason::Miss => "miss",
+            ForwardReason::Request => "request",
+            ForwardReason::Stale => "stale",
+            ForwardReason::Partial => "partial",
+        }
+    }
+
+    /// Returns reason with the given token, or `None` if the token is unknown.
+    pub fn from_token(token: &str) -> Option<ForwardReason> {
+        match token {
+            "bypass" => Some(ForwardReason::Bypass),
+            "method" => Some(ForwardReason::Method),
+            "uri-miss" => Some(ForwardReason::UriMiss),
+            "vary-miss" => Some(ForwardReason::VaryMiss),
+            "miss" => Some(ForwardReason::Miss),
+            "request" => Some(ForwardReason::Request),
+            "stale" => Some(ForwardReason::Stale),
+            "partial" => Some(ForwardReason::Partial),
+            _ => None,
+        }
+    }
+}
+
+impl CacheStatus {
+    /// Parses `Cache-Status` field value.
+    /// Unknown parameters and parameters with values of unexpected types are ignored.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<CacheStatus> {
+        let list = Parser::parse_list(input_bytes)?;
+        let mut entries = Vec::with_capacity(list.len());
+        for member in list {
+            let item = match member {
+                ListEntry::Item(item) => item,
+                ListEntry::InnerList(_) => return Err("parse_cache_status: member is not an item"),
+            };
+            entries.push(CacheStatusEntry::from_item(item)?);
+        }
+        Ok(CacheStatus { entries })
+    }
+}
+
+impl CacheStatusEntry {
+    /// Returns new `CacheStatusEntry` of `cache` without parameters.
+    pub fn new<T: Into<String>>(cache: T) -> CacheStatusEntry {
+        CacheStatusEntry {
+            cache: cache.into(),
+            ..CacheStatusEntry::default()
+        }
+    }
+
+    fn from_item(item: Item) -> SFVResult<CacheStatusEntry> {
+        let cache = match item.bare_item {
+            BareItem::Token(cache) | BareItem::String(cache) => cache,
+            _ => return Err("parse_cache_status: cache is not a token or string"),
+        };
+
+        let mut entry = CacheStatusEntry::new(cache);
+        for (name, value) in item.params {
+            match (name.as_str(), value) {
+                ("hit", BareItem::Boolean(hit)) => entry.hit = hit,
+                ("fwd", BareItem::Token(fwd)) => entry.fwd = ForwardReason::from_token(&fwd),
+                ("fwd-status", BareItem::Integer(status)) => {
+                    entry.fwd_status = match status {
+                        100..=599 => Some(status as u16),
+                        _ => None,
+                    }
+                }
+                ("ttl", BareItem::Integer(ttl)) => entry.ttl = Some(ttl),
+                ("stored", BareItem::Boolean(stored)) => entry.stored = stored,
+                ("collapsed", BareItem::Boolean(collapsed)) => entry.collapsed = collapsed,
+                ("key", BareItem::String(key)) => entry.key = Some(key),
+                ("detail", BareItem::Token(detail)) | ("detail", BareItem::String(detail)) => {
+                    entry.detail = Some(detail)
+                }
+                _ => (),
+            }
+        }
+        Ok(entry)
+    }
+
+    fn to_item(&self) -> Item {
+        let mut params = Parameters::new();
+        if self.hit {
+            params.insert("hit".to_owned(), true.into());
+        }
+        if let Some(fwd) = self.fwd {
+            params.insert("fwd".to_owned(), BareItem::Token(fwd.as_str().to_owned()));
+        }
+        if let Some(status) = self.fwd_status {
+            params.insert("fwd-status".to_owned(), i64::from(status).into());
+        }
+        if let Some(ttl) = self.ttl {
+            params.insert("ttl".to_owned(), ttl.into());
+        }
+        if self.stored {
+            params.insert("stored".to_owned(), true.into());
+        }
+        if self.collapsed {
+            params.insert("collapsed".to_owned(), true.into());
+        }
+        if let Some(key) = &self.key {
+            params.insert("key".to_owned(), BareItem::String(key.clone()));
+        }
+        if let Some(detail) = &self.detail {
+            params.insert("detail".to_owned(), token_or_string(detail));
+        }
+        Item::with_params(token_or_string(&self.cache), params)
+    }
+}
+
+impl SerializeValue for CacheStatus {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let list: List = self
+            .entries
+            .iter()
+            .map(|entry| ListEntry::from(entry.to_item()))
+            .collect();
+        list.serialize_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cache_status_parameters() -> SFVResult<()> {
+        let input = "ExampleCache; fwd=stale; fwd-status=304; ttl=-20; collapsed; key=\"/a\"; detail=limit; x=1, \
+                     Other; fwd=unknown; fwd-status=700; hit=1; key=tok";
+        let status = CacheStatus::parse(input.as_bytes())?;
+        assert_eq!(
+            CacheStatusEntry {
+                cache: "ExampleCache".to_owned(),
+                hit: false,
+                fwd: Some(ForwardReason::Stale),
+                fwd_status: Some(304),
+                ttl: Some(-20),
+                stored: false,
+                collapsed: true,
+                key: Some("/a".to_owned()),
+                detail: Some("limit".to_owned()),
+            },
+            status.entries[0]
+        );
+        assert_eq!(CacheStatusEntry::new("Other"), status.entries[1]);
+        assert_eq!(
+            "ExampleCache;fwd=stale;fwd-status=304;ttl=-20;collapsed;key=\"/a\";detail=limit, Other",
+            status.serialize_value()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cache_status_errors() {
+        assert_eq!(
+            Err("parse_cache_status: member is not an item"),
+            CacheStatus::parse("(a b)".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_cache_status: cache is not a token or string"),
+            CacheStatus::parse("a, 1".as_bytes())
+        );
+        assert_eq!(
+            Err("serialize_list: serializing empty field is not allowed"),
+            CacheStatus::default().serialize_value()
+        );
+    }
+}
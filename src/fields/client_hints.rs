@@ -0,0 +1,200 @@
+use crate::{BareItem, Item, List, ListEntry, Parser, SFVResult, SerializeValue};
+
+/// `Sec-CH-UA` or `Sec-CH-UA-Full-Version-List` field of User-Agent Client Hints.
+///
+/// Wire form is a List of brand name strings, each with a `v` parameter holding the brand's version as a string.
+/// # Examples
+/// ```
+/// # use sfv::fields::{Brand, UserAgentBrands};
+/// # use sfv::SerializeValue;
+/// let brands = UserAgentBrands::parse("\"Chromium\";v=\"119\", \"Not?A_Brand\";v=\"24\"".as_bytes()).unwrap();
+/// assert_eq!(brands.brands[0], Brand::new("Chromium", "119"));
+/// assert_eq!(brands.serialize_value().unwrap(), "\"Chromium\";v=\"119\", \"Not?A_Brand\";v=\"24\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UserAgentBrands {
+    /// Brands, in the order sent by the user agent.
+    pub brands: Vec<Brand>,
+}
+
+/// Brand and its version, a member of `UserAgentBrands`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Brand {
+    /// Brand name.
+    pub brand: String,
+    /// `v` parameter, significant or full version of the brand.
+    pub version: String,
+}
+
+/// `Sec-CH-UA-Mobile` field of User-Agent Client Hints, whether the user agent prefers a mobile experience.
+///
+/// Wire form is a Boolean item.
+/// # Examples
+/// ```
+/// # use sfv::fields::UserAgentMobile;
+/// # use sfv::SerializeValue;
+/// assert_eq!(UserAgentMobile::parse("?1".as_bytes()).unwrap(), UserAgentMobile(true));
+/// assert_eq!(UserAgentMobile(false).serialize_value().unwrap(), "?0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UserAgentMobile(pub bool);
+
+/// `Sec-CH-UA-Platform` field of User-Agent Client Hints, the platform the user agent is running on.
+///
+/// Wire form is a String item.
+/// # Examples
+/// ```
+/// # use sfv::fields::UserAgentPlatform;
+/// # use sfv::SerializeValue;
+/// let platform = UserAgentPlatform::parse("\"Windows\"".as_bytes()).unwrap();
+/// assert_eq!(platform.0, "Windows");
+/// assert_eq!(platform.serialize_value().unwrap(), "\"Windows\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct UserAgentPlatform(pub String);
+
+impl UserAgentBrands {
+    /// Parses `Sec-CH-UA` or `Sec-CH-UA-Full-Version-List` field value.
+    /// Returns an error if a brand is not a string or has no string version.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<UserAgentBrands> {
+        let list = Parser::parse_list(input_bytes)?;
+        let mut brands = Vec::with_capacity(list.len());
+        for member in list {
+            let item = match member {
+                ListEntry::Item(item) => item,
+                ListEntry::InnerList(_) => return Err("parse_sec_ch_ua: member is not an item"),
+            };
+            let brand = match item.bare_item {
+                BareItem::String(brand) => brand,
+                _ => return Err("parse_sec_ch_ua: brand is not a string"),
+            };
+            let version = match item.params.get("v") {
+                Some(BareItem::String(version)) => version.clone(),
+                _ => return Err("parse_sec_ch_ua: version is missing or not a string"),
+            };
+            brands.push(Brand { brand, version });
+        }
+        Ok(UserAgentBrands { brands })
+    }
+}
+
+impl SerializeValue for UserAgentBrands {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let list: List = self
+            .brands
+            .iter()
+            .map(|brand| {
+                let mut item = Item::new(BareItem::String(brand.brand.clone()));
+                item.params
+                    .insert("v".to_owned(), BareItem::String(brand.version.clone()));
+                ListEntry::from(item)
+            })
+            .collect();
+        list.serialize_value()
+    }
+}
+
+impl Brand {
+    /// Returns new `Brand` of `brand` with `version`.
+    pub fn new<B: Into<String>, V: Into<String>>(brand: B, version: V) -> Brand {
+        Brand {
+            brand: brand.into(),
+            version: version.into(),
+        }
+    }
+}
+
+impl UserAgentMobile {
+    /// Parses `Sec-CH-UA-Mobile` field value.
+    /// Returns an error if the value is not a Boolean.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<UserAgentMobile> {
+        match Parser::parse_item(input_bytes)?.bare_item {
+            BareItem::Boolean(mobile) => Ok(UserAgentMobile(mobile)),
+            _ => Err("parse_sec_ch_ua_mobile: value is not a boolean"),
+        }
+    }
+}
+
+impl SerializeValue for UserAgentMobile {
+    fn serialize_value(&self) -> SFVResult<String> {
+        Item::new(BareItem::Boolean(self.0)).serialize_value()
+    }
+}
+
+impl UserAgentPlatform {
+    /// Parses `Sec-CH-UA-Platform` field value.
+    /// Returns an error if the value is not a String.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<UserAgentPlatform> {
+        match Parser::parse_item(input_bytes)?.bare_item {
+            BareItem::String(platform) => Ok(UserAgentPlatform(platform)),
+            _ => Err("parse_sec_ch_ua_platform: value is not a string"),
+        }
+    }
+}
+
+impl SerializeValue for UserAgentPlatform {
+    fn serialize_value(&self) -> SFVResult<String> {
+        Item::new(BareItem::String(self.0.clone())).serialize_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_user_agent_brands() -> SFVResult<()> {
+        let brands = UserAgentBrands::parse(
+            "\" Not A;Brand\";v=\"99.0.0.0\";x=1,   \"Chromium\";v=\"99.0.4844.51\"".as_bytes(),
+        )?;
+        assert_eq!(
+            vec![
+                Brand::new(" Not A;Brand", "99.0.0.0"),
+                Brand::new("Chromium", "99.0.4844.51")
+            ],
+            brands.brands
+        );
+        assert_eq!(
+            "\" Not A;Brand\";v=\"99.0.0.0\", \"Chromium\";v=\"99.0.4844.51\"",
+            brands.serialize_value()?
+        );
+
+        assert_eq!(
+            Err("parse_sec_ch_ua: member is not an item"),
+            UserAgentBrands::parse("(\"a\");v=\"1\"".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_sec_ch_ua: brand is not a string"),
+            UserAgentBrands::parse("Chromium;v=\"1\"".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_sec_ch_ua: version is missing or not a string"),
+            UserAgentBrands::parse("\"Chromium\";v=1".as_bytes())
+        );
+        assert_eq!(
+            Err("serialize_list: serializing empty field is not allowed"),
+            UserAgentBrands::default().serialize_value()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_user_agent_mobile_and_platform() {
+        assert_eq!(
+            Ok(UserAgentMobile(false)),
+            UserAgentMobile::parse("?0".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_sec_ch_ua_mobile: value is not a boolean"),
+            UserAgentMobile::parse("1".as_bytes())
+        );
+        assert_eq!(
+            Ok(UserAgentPlatform("macOS".to_owned())),
+            UserAgentPlatform::parse("\"macOS\"".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_sec_ch_ua_platform: value is not a string"),
+            UserAgentPlatform::parse("macOS".as_bytes())
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    BareItem, ListEntry, Parser, RefBareItem, RefDictSerializer, SFVResult, SerializeValue,
+};
+use indexmap::IndexMap;
+
+/// `Content-Digest` or `Repr-Digest` field, digests of message content or representation, as defined in RFC 9530.
+///
+/// Wire form is a Dictionary mapping hashing algorithm keys, e.g. `sha-256`, to byte sequences of digests.
+/// Hashing is left to the caller, which passes a function computing the digest of given algorithm,
+/// or returning `None` if the algorithm is not supported.
+/// # Examples
+/// ```
+/// # use sfv::fields::Digest;
+/// # use sfv::SerializeValue;
+/// // Not a real hashing algorithm
+/// fn digest(algorithm: &str, body: &[u8]) -> Option<Vec<u8>> {
+///     match algorithm {
+///         "sha-256" => Some(body.iter().rev().copied().collect()),
+///         _ => None,
+///     }
+/// }
+///
+/// let computed = Digest::compute(&["sha-256", "md5"], "body".as_bytes(), digest);
+/// assert_eq!(computed.serialize_value().unwrap(), "sha-256=:eWRvYg==:");
+///
+/// let received = Digest::parse("sha-512=:AA==:, sha-256=:eWRvYg==:".as_bytes()).unwrap();
+/// assert!(received.verify("body".as_bytes(), digest));
+/// assert!(!received.verify("tampered".as_bytes(), digest));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Digest {
+    /// Digests by hashing algorithm key.
+    pub digests: IndexMap<String, Vec<u8>>,
+}
+
+impl Digest {
+    /// Parses `Content-Digest` or `Repr-Digest` field value.
+    /// Returns an error if a digest is not a byte sequence.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<Digest> {
+        let dict = Parser::parse_dictionary(input_bytes)?;
+        let mut digests = IndexMap::with_capacity(dict.len());
+        for (algorithm, member) in dict {
+            match member {
+                ListEntry::Item(item) => match item.bare_item {
+                    BareItem::ByteSeq(digest) => {
+                        digests.insert(algorithm, digest);
+                    }
+                    _ => return Err("parse_digest: digest is not a byte sequence"),
+                },
+                ListEntry::InnerList(_) => {
+                    return Err("parse_digest: digest is not a byte sequence")
+                }
+            }
+        }
+        Ok(Digest { digests })
+    }
+
+    /// Computes digests of `body` with each of `algorithms`, using `digest` to hash it.
+    /// Algorithms for which `digest` returns `None` are skipped.
+    pub fn compute<F>(algorithms: &[&str], body: &[u8], mut digest: F) -> Digest
+    where
+        F: FnMut(&str, &[u8]) -> Option<Vec<u8>>,
+    {
+        let digests = algorithms
+            .iter()
+            .filter_map(|algorithm| Some((algorithm.to_string(), digest(algorithm, body)?)))
+            .collect();
+        Digest { digests }
+    }
+
+    /// Verifies digests of `body`, using `digest` to hash it.
+    /// Algorithms for which `digest` returns `None` are skipped.
+    /// Returns `true` only if at least one digest was verified, and all verified digests match.
+    pub fn verify<F>(&self, body: &[u8], mut digest: F) -> bool
+    where
+        F: FnMut(&str, &[u8]) -> Option<Vec<u8>>,
+    {
+        let mut verified = false;
+        for (algorithm, expected) in &self.digests {
+            match digest(algorithm, body) {
+                Some(actual) if &actual == expected => verified = true,
+                Some(_) => return false,
+                None => (),
+            }
+        }
+        verified
+    }
+}
+
+impl SerializeValue for Digest {
+    fn serialize_value(&self) -> SFVResult<String> {
+        if self.digests.is_empty() {
+            return Err("serialize_dictionary: serializing empty field is not allowed");
+        }
+
+        let mut output = String::new();
+        let mut serializer = RefDictSerializer::new(&mut output);
+        for (algorithm, digest) in &self.digests {
+            serializer = serializer.bare_item_member(algorithm, &RefBareItem::ByteSeq(digest))?;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(algorithm: &str, body: &[u8]) -> Option<Vec<u8>> {
+        match algorithm {
+            "len" => Some(vec![body.len() as u8]),
+            "first" => Some(body.iter().take(1).copied().collect()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse_digest() -> SFVResult<()> {
+        let parsed = Digest::parse("len=:Aw==:, unknown=:AAEC:".as_bytes())?;
+        assert_eq!(Some(&vec![3]), parsed.digests.get("len"));
+        assert_eq!(Some(&vec![0, 1, 2]), parsed.digests.get("unknown"));
+        assert_eq!(
+            Err("parse_digest: digest is not a byte sequence"),
+            Digest::parse("len=3".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_digest: digest is not a byte sequence"),
+            Digest::parse("len=(:Aw==:)".as_bytes())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn verify_digest() -> SFVResult<()> {
+        let body = "abc".as_bytes();
+        let computed = Digest::compute(&["first", "len", "unknown"], body, digest);
+        assert_eq!("first=:YQ==:, len=:Aw==:", computed.serialize_value()?);
+        assert!(computed.verify(body, digest));
+        assert!(!computed.verify("xyz".as_bytes(), digest));
+        assert!(!computed.verify("ab".as_bytes(), digest));
+
+        let unsupported = Digest::parse("unknown=:AAEC:".as_bytes())?;
+        assert!(!unsupported.verify(body, digest));
+        assert_eq!(
+            Err("serialize_dictionary: serializing empty field is not allowed"),
+            Digest::default().serialize_value()
+        );
+        Ok(())
+    }
+}
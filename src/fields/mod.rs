@@ -0,0 +1,39 @@
+/*!
+Typed representations of structured fields defined by other specifications.
+
+Each type parses from and serializes to the wire form of its field using the
+generic `Parser` and `SerializeValue`, and exposes the field's members as
+plain Rust values.
+*/
+
+mod cache_status;
+mod client_hints;
+mod digest;
+mod policy;
+mod priority;
+mod proxy_status;
+mod signature_input;
+mod variants;
+
+pub use cache_status::{CacheStatus, CacheStatusEntry, ForwardReason};
+pub use client_hints::{Brand, UserAgentBrands, UserAgentMobile, UserAgentPlatform};
+pub use digest::Digest;
+pub use policy::PolicyWithReporting;
+pub use priority::Priority;
+pub use proxy_status::{ProxyError, ProxyStatus, ProxyStatusEntry};
+pub use signature_input::{ComponentIdentifier, SignatureInput, SignatureParams};
+pub use variants::{VariantKey, VariantValue, Variants};
+
+use crate::{chars, BareItem};
+
+/// Returns `BareItem::Token` if `value` is a valid token, otherwise `BareItem::String`.
+fn token_or_string(value: &str) -> BareItem {
+    let mut value_chars = value.chars();
+    let is_token = matches!(value_chars.next(), Some(c) if chars::is_allowed_token_first_char(c))
+        && value_chars.all(chars::is_allowed_token_char);
+    if is_token {
+        BareItem::Token(value.to_owned())
+    } else {
+        BareItem::String(value.to_owned())
+    }
+}
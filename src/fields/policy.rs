@@ -0,0 +1,95 @@
+use crate::{BareItem, Item, Parser, SFVResult, SerializeValue};
+
+/// Policy token with an optional reporting endpoint, the form of `Cross-Origin-Embedder-Policy`,
+/// `Cross-Origin-Opener-Policy` and `Cross-Origin-Resource-Policy` fields and their report-only variants.
+///
+/// Wire form is a Token item with an optional `report-to` string parameter naming the reporting endpoint.
+/// # Examples
+/// ```
+/// # use sfv::fields::PolicyWithReporting;
+/// # use sfv::SerializeValue;
+/// let coep = PolicyWithReporting::parse("require-corp; report-to=\"coep\"".as_bytes()).unwrap();
+/// assert_eq!(coep.value, "require-corp");
+/// assert_eq!(coep.report_to.as_deref(), Some("coep"));
+/// assert_eq!(coep.serialize_value().unwrap(), "require-corp;report-to=\"coep\"");
+///
+/// let coop = PolicyWithReporting::new("same-origin");
+/// assert_eq!(coop.serialize_value().unwrap(), "same-origin");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PolicyWithReporting {
+    /// Policy token, e.g. `require-corp` or `same-origin`.
+    pub value: String,
+    /// `report-to` parameter, name of the reporting endpoint.
+    pub report_to: Option<String>,
+}
+
+impl PolicyWithReporting {
+    /// Returns new `PolicyWithReporting` of `value` without a reporting endpoint.
+    pub fn new<T: Into<String>>(value: T) -> PolicyWithReporting {
+        PolicyWithReporting {
+            value: value.into(),
+            report_to: None,
+        }
+    }
+
+    /// Parses policy field value.
+    /// Unknown parameters and `report-to` values of unexpected types are ignored.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<PolicyWithReporting> {
+        let item = Parser::parse_item(input_bytes)?;
+        let value = match item.bare_item {
+            BareItem::Token(value) => value,
+            _ => return Err("parse_policy: value is not a token"),
+        };
+        let report_to = match item.params.get("report-to") {
+            Some(BareItem::String(endpoint)) => Some(endpoint.clone()),
+            _ => None,
+        };
+        Ok(PolicyWithReporting { value, report_to })
+    }
+}
+
+impl SerializeValue for PolicyWithReporting {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let mut item = Item::new(BareItem::Token(self.value.clone()));
+        if let Some(endpoint) = &self.report_to {
+            item.params
+                .insert("report-to".to_owned(), BareItem::String(endpoint.clone()));
+        }
+        item.serialize_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_policy() -> SFVResult<()> {
+        assert_eq!(
+            PolicyWithReporting::new("same-origin-allow-popups"),
+            PolicyWithReporting::parse("same-origin-allow-popups;report-to=endpoint;x".as_bytes())?
+        );
+        assert_eq!(
+            Err("parse_policy: value is not a token"),
+            PolicyWithReporting::parse("\"require-corp\"".as_bytes())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_policy() {
+        let policy = PolicyWithReporting {
+            value: "credentialless".to_owned(),
+            report_to: Some("default".to_owned()),
+        };
+        assert_eq!(
+            Ok("credentialless;report-to=\"default\"".to_owned()),
+            policy.serialize_value()
+        );
+        assert_eq!(
+            Err("serialise_token: first character is not ALPHA or '*'"),
+            PolicyWithReporting::new("1").serialize_value()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{BareItem, Dictionary, Item, ListEntry, Parser, SFVResult, SerializeValue};
+
+/// `Priority` field of extensible prioritization scheme for HTTP, as defined in RFC 9218.
+///
+/// Wire form is a Dictionary with `u` urgency, an integer between 0 and 7, and `i` incremental boolean.
+/// # Examples
+/// ```
+/// # use sfv::fields::Priority;
+/// # use sfv::SerializeValue;
+/// let priority = Priority::parse("u=5, i, x=unknown".as_bytes()).unwrap();
+/// assert_eq!(priority, Priority { urgency: 5, incremental: true });
+/// assert_eq!(priority.serialize_value().unwrap(), "u=5, i");
+///
+/// assert_eq!(Priority::parse("u=9".as_bytes()).unwrap(), Priority::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Priority {
+    /// Urgency, from 0, the highest priority, to 7.
+    pub urgency: u8,
+    /// Whether the response can be processed incrementally.
+    pub incremental: bool,
+}
+
+impl Priority {
+    /// Urgency used when `u` is absent or invalid.
+    pub const DEFAULT_URGENCY: u8 = 3;
+    /// Lowest priority urgency.
+    pub const MAX_URGENCY: u8 = 7;
+
+    /// Parses `Priority` field value.
+    /// As the specification requires, unknown members and members with out-of-range values
+    /// or values of unexpected types are ignored, leaving the defaults.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<Priority> {
+        let dict = Parser::parse_dictionary(input_bytes)?;
+        let mut priority = Priority::default();
+
+        if let Some(ListEntry::Item(item)) = dict.get("u") {
+            match item.bare_item {
+                BareItem::Integer(urgency) if (0..=Self::MAX_URGENCY as i64).contains(&urgency) => {
+                    priority.urgency = urgency as u8
+                }
+                _ => (),
+            }
+        }
+        if let Some(ListEntry::Item(item)) = dict.get("i") {
+            if let BareItem::Boolean(incremental) = item.bare_item {
+                priority.incremental = incremental;
+            }
+        }
+        Ok(priority)
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority {
+            urgency: Self::DEFAULT_URGENCY,
+            incremental: false,
+        }
+    }
+}
+
+impl SerializeValue for Priority {
+    /// Serializes urgency, followed by `i` if the response is incremental.
+    /// Returns an error if urgency is greater than 7.
+    fn serialize_value(&self) -> SFVResult<String> {
+        if self.urgency > Self::MAX_URGENCY {
+            return Err("serialize_priority: urgency is out of range");
+        }
+
+        let mut dict = Dictionary::new();
+        dict.insert(
+            "u".to_owned(),
+            Item::new(i64::from(self.urgency).into()).into(),
+        );
+        if self.incremental {
+            dict.insert("i".to_owned(), Item::new(true.into()).into());
+        }
+        dict.serialize_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_priority_defaults() -> SFVResult<()> {
+        assert_eq!(Priority::default(), Priority::parse("".as_bytes())?);
+        assert_eq!(
+            Priority {
+                urgency: 0,
+                incremental: false
+            },
+            Priority::parse("u=0, i=?0".as_bytes())?
+        );
+        assert_eq!(
+            Priority::default(),
+            Priority::parse("u=-1, i=1".as_bytes())?
+        );
+        assert_eq!(
+            Priority::default(),
+            Priority::parse("u=(1), i=(?1)".as_bytes())?
+        );
+        assert_eq!(
+            Err("parse_dict: trailing comma"),
+            Priority::parse("u=1,".as_bytes())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_priority() -> SFVResult<()> {
+        assert_eq!("u=3", Priority::default().serialize_value()?);
+        let priority = Priority {
+            urgency: 8,
+            incremental: true,
+        };
+        assert_eq!(
+            Err("serialize_priority: urgency is out of range"),
+            priority.serialize_value()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use super::token_or_string;
+use crate::{BareItem, Item, List, ListEntry, Parameters, Parser, SFVResult, SerializeValue};
+
+/// `Proxy-Status` field, describing how intermediaries handled a response, as defined in RFC 9209.
+///
+/// Wire form is a List with one item per intermediary, in the order the response passed through them,
+/// from the origin server towards the client. Each item identifies the intermediary and has parameters describing its handling.
+/// # Examples
+/// ```
+/// # use sfv::fields::{ProxyError, ProxyStatus, ProxyStatusEntry};
+/// # use sfv::SerializeValue;
+/// let status = ProxyStatus::parse("ExampleCDN; error=http_protocol_error; details=\"Malformed response header: space before colon\"".as_bytes()).unwrap();
+/// assert_eq!(status.entries[0].proxy, "ExampleCDN");
+/// assert_eq!(status.entries[0].error, Some(ProxyError::HttpProtocolError));
+///
+/// let mut entry = ProxyStatusEntry::new("SomeReverseProxy");
+/// entry.error = Some(ProxyError::ConnectionTimeout);
+/// entry.next_hop = Some("backend example".into());
+/// let status = ProxyStatus { entries: vec![entry] };
+/// assert_eq!(
+///     status.serialize_value().unwrap(),
+///     "SomeReverseProxy;error=connection_timeout;next-hop=\"backend example\""
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProxyStatus {
+    /// Entries of each intermediary, from the origin server towards the client.
+    pub entries: Vec<ProxyStatusEntry>,
+}
+
+/// Handling of a response by a single intermediary, a member of `ProxyStatus`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProxyStatusEntry {
+    /// Identifier of the intermediary.
+    pub proxy: String,
+    /// `error` parameter, type of error the intermediary encountered.
+    pub error: Option<ProxyError>,
+    /// `next-hop` parameter, hostname, IP address or alias of the next hop.
+    pub next_hop: Option<String>,
+    /// `next-protocol` parameter, ALPN protocol identifier used with the next hop.
+    pub next_protocol: Option<Vec<u8>>,
+    /// `received-status` parameter, status code received from the next hop.
+    pub received_status: Option<u16>,
+    /// `details` parameter, additional details about the error.
+    pub details: Option<String>,
+    /// Other parameters, e.g. the ones specific to the error type, such as `rcode` or `alert-id`.
+    pub extra: Parameters,
+}
+
+macro_rules! proxy_errors {
+    ($($variant:ident => $token:literal,)*) => {
+        /// Value of `error` parameter of `ProxyStatusEntry`, as registered in the HTTP Proxy-Status Parameters registry.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ProxyError {
+            $(
+                #[doc = concat!("`", $token, "` error type.")]
+                $variant,
+            )*
+            /// Error type not known to this crate.
+            Other(String),
+        }
+
+        impl ProxyError {
+            /// Returns token of the error type.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ProxyError::$variant => $token,)*
+                    ProxyError::Other(token) => token,
+                }
+            }
+
+            /// Returns error type with the given token.
+            pub fn from_token(token: &str) -> ProxyError {
+                match token {
+                    $($token => ProxyError::$variant,)*
+                    _ => ProxyError::Other(token.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+proxy_errors! {
+    DnsTimeout => "dns_timeout",
+    DnsError => "dns_error",
+    DestinationNotFound => "destination_not_found",
+    DestinationUnavailable => "destination_unavailable",
+    DestinationIpProhibited => "destination_ip_prohibited",
+    DestinationIpUnroutable => "destination_ip_unroutable",
+    ConnectionRefused => "connection_refused",
+    ConnectionTerminated => "connection_terminated",
+    ConnectionTimeout => "connection_timeout",
+    ConnectionReadTimeout => "connection_read_timeout",
+    ConnectionWriteTimeout => "connection_write_timeout",
+    ConnectionLimitReached => "connection_limit_reached",
+    TlsProtocolError => "tls_protocol_error",
+    TlsCertificateError => "tls_certificate_error",
+    TlsAlertReceived => "tls_alert_received",
+    HttpRequestError => "http_request_error",
+    HttpRequestDenied => "http_request_denied",
+    HttpResponseIncomplete => "http_response_incomplete",
+    HttpResponseHeaderSectionSize => "http_response_header_section_size",
+    HttpResponseHeaderSize => "http_response_header_size",
+    HttpResponseBodySize => "http_response_body_size",
+    HttpResponseTrailerSectionSize => "http_response_trailer_section_size",
+    HttpResponseTrailerSize => "http_response_trailer_size",
+    HttpResponseTransferCoding => "http_response_transfer_coding",
+    HttpResponseContentCoding => "http_response_content_coding",
+    HttpResponseTimeout => "http_response_timeout",
+    HttpUpgradeFailed => "http_upgrade_failed",
+    HttpProtocolError => "http_protocol_error",
+    ProxyInternalResponse => "proxy_internal_response",
+    ProxyInternalError => "proxy_internal_error",
+    ProxyConfigurationError => "proxy_configuration_error",
+    ProxyLoopDetected => "proxy_loop_detected",
+}
+
+impl ProxyStatus {
+    /// Parses `Proxy-Status` field value.
+    /// Parameters with values of unexpected types are kept in `ProxyStatusEntry::extra`.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<ProxyStatus> {
+        let list = Parser::parse_list(input_bytes)?;
+        let mut entries = Vec::with_capacity(list.len());
+        for member in list {
+            let item = match member {
+                ListEntry::Item(item) => item,
+                ListEntry::InnerList(_) => return Err("parse_proxy_status: member is not an item"),
+            };
+            entries.push(ProxyStatusEntry::from_item(item)?);
+        }
+        Ok(ProxyStatus { entries })
+    }
+}
+
+impl ProxyStatusEntry {
+    /// Returns new `ProxyStatusEntry` of `proxy` without parameters.
+    pub fn new<T: Into<String>>(proxy: T) -> ProxyStatusEntry {
+        ProxyStatusEntry {
+            proxy: proxy.into(),
+            ..ProxyStatusEntry::default()
+        }
+    }
+
+    fn from_item(item: Item) -> SFVResult<ProxyStatusEntry> {
+        let proxy = match item.bare_item {
+            BareItem::Token(proxy) | BareItem::String(proxy) => proxy,
+            _ => return Err("parse_proxy_status: proxy is not a token or string"),
+        };
+
+        let mut entry = ProxyStatusEntry::new(proxy);
+        for (name, value) in item.params {
+            match (name.as_str(), value) {
+                ("error", BareItem::Token(error)) => {
+                    entry.error = Some(ProxyError::from_token(&error))
+                }
+                ("next-hop", BareItem::Token(next_hop))
+                | ("next-hop", BareItem::String(next_hop)) => entry.next_hop = Some(next_hop),
+                ("next-protocol", BareItem::Token(protocol)) => {
+                    entry.next_protocol = Some(protocol.into_bytes())
+                }
+                ("next-protocol", BareItem::ByteSeq(protocol)) => {
+                    entry.next_protocol = Some(protocol)
+                }
+                ("received-status", BareItem::Integer(status @ 100..=599)) => {
+                    entry.received_status = Some(status as u16)
+                }
+                ("details", BareItem::String(details)) => entry.details = Some(details),
+                (_, value) => {
+                    entry.extra.insert(name, value);
+                }
+            }
+        }
+        Ok(entry)
+    }
+
+    fn to_item(&self) -> Item {
+        let mut params = Parameters::new();
+        if let Some(error) = &self.error {
+            params.insert(
+                "error".to_owned(),
+                BareItem::Token(error.as_str().to_owned()),
+            );
+        }
+        if let Some(next_hop) = &self.next_hop {
+            params.insert("next-hop".to_owned(), token_or_string(next_hop));
+        }
+        if let Some(protocol) = &self.next_protocol {
+            // ALPN identifiers that are valid tokens are sent as tokens, others as byte sequences
+            let value = match std::str::from_utf8(protocol) {
+                Ok(protocol) => match token_or_string(protocol) {
+                    BareItem::Token(token) => BareItem::Token(token),
+                    _ => BareItem::ByteSeq(protocol.as_bytes().to_vec()),
+                },
+                Err(_) => BareItem::ByteSeq(protocol.clone()),
+            };
+            params.insert("next-protocol".to_owned(), value);
+        }
+        if let Some(status) = self.received_status {
+            params.insert("received-status".to_owned(), i64::from(status).into());
+        }
+        if let Some(details) = &self.details {
+            params.insert("details".to_owned(), BareItem::String(details.clone()));
+        }
+        for (name, value) in &self.extra {
+            params.insert(name.clone(), value.clone());
+        }
+        Item::with_params(token_or_string(&self.proxy), params)
+    }
+}
+
+impl SerializeValue for ProxyStatus {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let list: List = self
+            .entries
+            .iter()
+            .map(|entry| ListEntry::from(entry.to_item()))
+            .collect();
+        list.serialize_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proxy_status_parameters() -> SFVResult<()> {
+        let input = "r34.example.net; error=http_response_header_size; field-name=\"Example-Header\", \
+                     ExampleCDN; error=new_error; next-hop=\"192.0.2.1\"; next-protocol=h2; received-status=503, \
+                     proxy; next-protocol=:AAE=:; received-status=99";
+        let status = ProxyStatus::parse(input.as_bytes())?;
+
+        let first = &status.entries[0];
+        assert_eq!(Some(ProxyError::HttpResponseHeaderSize), first.error);
+        assert_eq!(
+            Some(&BareItem::String("Example-Header".to_owned())),
+            first.extra.get("field-name")
+        );
+
+        let second = &status.entries[1];
+        assert_eq!(
+            Some(ProxyError::Other("new_error".to_owned())),
+            second.error
+        );
+        assert_eq!(Some("192.0.2.1"), second.next_hop.as_deref());
+        assert_eq!(Some("h2".as_bytes()), second.next_protocol.as_deref());
+        assert_eq!(Some(503), second.received_status);
+
+        let third = &status.entries[2];
+        assert_eq!(Some(vec![0, 1]), third.next_protocol);
+        assert_eq!(None, third.received_status);
+        assert_eq!(
+            Some(&BareItem::Integer(99)),
+            third.extra.get("received-status")
+        );
+
+        assert_eq!(
+            "r34.example.net;error=http_response_header_size;field-name=\"Example-Header\", \
+             ExampleCDN;error=new_error;next-hop=\"192.0.2.1\";next-protocol=h2;received-status=503, \
+             proxy;next-protocol=:AAE=:;received-status=99",
+            status.serialize_value()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_proxy_status_errors() {
+        assert_eq!(
+            Err("parse_proxy_status: member is not an item"),
+            ProxyStatus::parse("(a)".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_proxy_status: proxy is not a token or string"),
+            ProxyStatus::parse("?1".as_bytes())
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::serializer::Serializer;
+use crate::{BareItem, InnerList, Item, ListEntry, Parameters, Parser, SFVResult, SerializeValue};
+use indexmap::IndexMap;
+
+/// `Signature-Input` field of HTTP Message Signatures, as defined in RFC 9421.
+///
+/// Wire form is a Dictionary mapping signature labels to inner lists of covered component identifiers,
+/// with signature parameters as parameters of the inner list.
+/// Order of components and parameters is kept as received, since it is part of the signature base.
+/// # Examples
+/// ```
+/// # use sfv::fields::{ComponentIdentifier, SignatureInput, SignatureParams};
+/// # use sfv::SerializeValue;
+/// let input = SignatureInput::parse(
+///     "sig1=(\"@method\" \"content-digest\" \"example-dict\";sf);created=1618884473;keyid=\"test-key-rsa-pss\"".as_bytes(),
+/// ).unwrap();
+/// let sig1 = &input.signatures["sig1"];
+/// assert_eq!(sig1.components[1].name, "content-digest");
+/// assert!(sig1.components[2].is_structured());
+/// assert_eq!(sig1.created(), Some(1618884473));
+/// assert_eq!(sig1.keyid(), Some("test-key-rsa-pss"));
+///
+/// let mut params = SignatureParams::new(vec![ComponentIdentifier::new("@authority")]);
+/// params.set_created(1618884475);
+/// params.set_alg("ed25519");
+/// assert_eq!(params.serialize_value().unwrap(), "(\"@authority\");created=1618884475;alg=\"ed25519\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SignatureInput {
+    /// Signature parameters by signature label.
+    pub signatures: IndexMap<String, SignatureParams>,
+}
+
+/// Covered components and parameters of a single signature, a member of `SignatureInput`.
+///
+/// Serializes to the value of `@signature-params` component.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SignatureParams {
+    /// Covered component identifiers, in order.
+    pub components: Vec<ComponentIdentifier>,
+    /// Signature parameters, in order.
+    pub params: Parameters,
+}
+
+/// Identifier of a component covered by a signature, e.g. `"@method"` or `"content-type"`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComponentIdentifier {
+    /// Component name, a derived component name or a lowercased field name.
+    pub name: String,
+    /// Component parameters, e.g. `sf` or `key`.
+    pub params: Parameters,
+}
+
+impl SignatureInput {
+    /// Parses `Signature-Input` field value.
+    /// Returns an error if a member is not an inner list, or a component identifier is not a string.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<SignatureInput> {
+        let dict = Parser::parse_dictionary(input_bytes)?;
+        let mut signatures = IndexMap::with_capacity(dict.len());
+        for (label, member) in dict {
+            let inner_list = match member {
+                ListEntry::InnerList(inner_list) => inner_list,
+                ListEntry::Item(_) => {
+                    return Err("parse_signature_input: signature parameters are not an inner list")
+                }
+            };
+            signatures.insert(label, SignatureParams::from_inner_list(inner_list)?);
+        }
+        Ok(SignatureInput { signatures })
+    }
+}
+
+impl SerializeValue for SignatureInput {
+    fn serialize_value(&self) -> SFVResult<String> {
+        if self.signatures.is_empty() {
+            return Err("serialize_dictionary: serializing empty field is not allowed");
+        }
+
+        let mut output = String::new();
+        for (idx, (label, params)) in self.signatures.iter().enumerate() {
+            if idx > 0 {
+                output.push_str(", ");
+            }
+            Serializer::serialize_key(label, &mut output)?;
+            output.push('=');
+            Serializer::serialize_list_member(&params.to_inner_list().into(), &mut output)?;
+        }
+        Ok(output)
+    }
+}
+
+impl SignatureParams {
+    /// Returns new `SignatureParams` covering `components`, without parameters.
+    pub fn new(components: Vec<ComponentIdentifier>) -> SignatureParams {
+        SignatureParams {
+            components,
+            params: Parameters::new(),
+        }
+    }
+
+    /// Returns `created` parameter, creation time as a UNIX timestamp.
+    pub fn created(&self) -> Option<i64> {
+        self.integer_param("created")
+    }
+
+    /// Returns `expires` parameter, expiration time as a UNIX timestamp.
+    pub fn expires(&self) -> Option<i64> {
+        self.integer_param("expires")
+    }
+
+    /// Returns `nonce` parameter.
+    pub fn nonce(&self) -> Option<&str> {
+        self.string_param("nonce")
+    }
+
+    /// Returns `alg` parameter, signature algorithm name.
+    pub fn alg(&self) -> Option<&str> {
+        self.string_param("alg")
+    }
+
+    /// Returns `keyid` parameter, identifier of the key material.
+    pub fn keyid(&self) -> Option<&str> {
+        self.string_param("keyid")
+    }
+
+    /// Returns `tag` parameter, application-specific tag.
+    pub fn tag(&self) -> Option<&str> {
+        self.string_param("tag")
+    }
+
+    /// Sets `created` parameter.
+    pub fn set_created(&mut self, created: i64) {
+        self.params.insert("created".to_owned(), created.into());
+    }
+
+    /// Sets `expires` parameter.
+    pub fn set_expires(&mut self, expires: i64) {
+        self.params.insert("expires".to_owned(), expires.into());
+    }
+
+    /// Sets `nonce` parameter.
+    pub fn set_nonce(&mut self, nonce: &str) {
+        self.params
+            .insert("nonce".to_owned(), BareItem::String(nonce.to_owned()));
+    }
+
+    /// Sets `alg` parameter.
+    pub fn set_alg(&mut self, alg: &str) {
+        self.params
+            .insert("alg".to_owned(), BareItem::String(alg.to_owned()));
+    }
+
+    /// Sets `keyid` parameter.
+    pub fn set_keyid(&mut self, keyid: &str) {
+        self.params
+            .insert("keyid".to_owned(), BareItem::String(keyid.to_owned()));
+    }
+
+    /// Sets `tag` parameter.
+    pub fn set_tag(&mut self, tag: &str) {
+        self.params
+            .insert("tag".to_owned(), BareItem::String(tag.to_owned()));
+    }
+
+    fn integer_param(&self, name: &str) -> Option<i64> {
+        match self.params.get(name) {
+            Some(BareItem::Integer(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn string_param(&self, name: &str) -> Option<&str> {
+        match self.params.get(name) {
+            Some(BareItem::String(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn from_inner_list(inner_list: InnerList) -> SFVResult<SignatureParams> {
+        let mut components = Vec::with_capacity(inner_list.items.len());
+        for item in inner_list.items {
+            let name = match item.bare_item {
+                BareItem::String(name) => name,
+                _ => return Err("parse_signature_input: component identifier is not a string"),
+            };
+            components.push(ComponentIdentifier {
+                name,
+                params: item.params,
+            });
+        }
+        Ok(SignatureParams {
+            components,
+            params: inner_list.params,
+        })
+    }
+
+    fn to_inner_list(&self) -> InnerList {
+        let items = self
+            .components
+            .iter()
+            .map(|component| {
+                Item::with_params(
+                    BareItem::String(component.name.clone()),
+                    component.params.clone(),
+                )
+            })
+            .collect();
+        InnerList::with_params(items, self.params.clone())
+    }
+}
+
+impl SerializeValue for SignatureParams {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let mut output = String::new();
+        Serializer::serialize_list_member(&self.to_inner_list().into(), &mut output)?;
+        Ok(output)
+    }
+}
+
+impl ComponentIdentifier {
+    /// Returns new `ComponentIdentifier` of `name` without parameters.
+    pub fn new<T: Into<String>>(name: T) -> ComponentIdentifier {
+        ComponentIdentifier {
+            name: name.into(),
+            params: Parameters::new(),
+        }
+    }
+
+    /// Returns `key` parameter, the Dictionary member key to cover.
+    pub fn key(&self) -> Option<&str> {
+        match self.params.get("key") {
+            Some(BareItem::String(key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Returns whether `sf` parameter is set, i.e. the field is covered in its strict structured field serialization.
+    pub fn is_structured(&self) -> bool {
+        self.flag("sf")
+    }
+
+    /// Returns whether `bs` parameter is set, i.e. the field lines are covered as byte sequences.
+    pub fn is_byte_sequence(&self) -> bool {
+        self.flag("bs")
+    }
+
+    /// Returns whether `req` parameter is set, i.e. the component is taken from the related request.
+    pub fn is_request(&self) -> bool {
+        self.flag("req")
+    }
+
+    /// Returns whether `tr` parameter is set, i.e. the field is taken from the trailers.
+    pub fn is_trailer(&self) -> bool {
+        self.flag("tr")
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        matches!(self.params.get(name), Some(BareItem::Boolean(true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_signature_input() -> SFVResult<()> {
+        let input = "sig-b21=();created=1618884473;keyid=\"test-key-rsa-pss\";nonce=\"b3k2pp5k7z-50gnwp.yemd\", \
+                     sig-b26=(\"date\" \"@method\" \"@path\" \"@authority\" \"content-type\" \"content-length\");\
+                     created=1618884473;keyid=\"test-key-ed25519\", \
+                     reqres=(\"@status\" \"example-dict\";key=\"a\" \"@authority\";req);created=1618884479;expires=1;x=?1";
+        let parsed = SignatureInput::parse(input.as_bytes())?;
+
+        let sig = &parsed.signatures["sig-b21"];
+        assert!(sig.components.is_empty());
+        assert_eq!(Some("b3k2pp5k7z-50gnwp.yemd"), sig.nonce());
+        assert_eq!(None, sig.alg());
+
+        assert_eq!(6, parsed.signatures["sig-b26"].components.len());
+
+        let sig = &parsed.signatures["reqres"];
+        assert_eq!(Some("a"), sig.components[1].key());
+        assert!(!sig.components[1].is_request());
+        assert!(sig.components[2].is_request());
+        assert_eq!(Some(1), sig.expires());
+        assert_eq!(
+            "(\"@status\" \"example-dict\";key=\"a\" \"@authority\";req);created=1618884479;expires=1;x",
+            sig.serialize_value()?
+        );
+
+        assert_eq!(
+            input.replace("; ", ";").replace(";x=?1", ";x"),
+            parsed.serialize_value()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_signature_input_errors() {
+        assert_eq!(
+            Err("parse_signature_input: signature parameters are not an inner list"),
+            SignatureInput::parse("sig1=\"@method\"".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_signature_input: component identifier is not a string"),
+            SignatureInput::parse("sig1=(method)".as_bytes())
+        );
+    }
+
+    #[test]
+    fn mistyped_params_are_ignored_by_accessors() -> SFVResult<()> {
+        let parsed = SignatureInput::parse("sig1=(\"a\";sf=1);created=\"1\";tag=t".as_bytes())?;
+        let sig = &parsed.signatures["sig1"];
+        assert_eq!(None, sig.created());
+        assert_eq!(None, sig.tag());
+        assert!(!sig.components[0].is_structured());
+
+        let mut sig = sig.clone();
+        sig.set_created(2);
+        sig.set_tag("app");
+        assert_eq!(Some(2), sig.created());
+        assert_eq!("(\"a\";sf=1);created=2;tag=\"app\"", sig.serialize_value()?);
+        Ok(())
+    }
+}
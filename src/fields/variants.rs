@@ -0,0 +1,256 @@
+use crate::{
+    BareItem, Dictionary, InnerList, Item, List, ListEntry, Parser, SFVResult, SerializeValue,
+};
+use indexmap::IndexMap;
+
+/// `Variants` field, listing the available values of each content negotiation axis of a cached response.
+///
+/// Wire form is a Dictionary mapping each axis, e.g. `accept-language`, to an inner list of tokens or strings.
+/// Values keep their type, so that they serialize as they were parsed.
+/// # Examples
+/// ```
+/// # use sfv::fields::Variants;
+/// # use sfv::SerializeValue;
+/// let variants = Variants::parse("accept-encoding=(gzip br), accept-language=(en fr)".as_bytes()).unwrap();
+/// assert_eq!(variants.axes["accept-language"], vec!["en", "fr"]);
+/// assert_eq!(variants.variant_keys().keys.len(), 4);
+/// assert_eq!(variants.serialize_value().unwrap(), "accept-encoding=(gzip br), accept-language=(en fr)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Variants {
+    /// Available values of each axis, in field order.
+    pub axes: IndexMap<String, Vec<VariantValue>>,
+}
+
+/// Value of a `Variants` axis or of a `Variant-Key` member, either a token or a string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariantValue {
+    /// Token value, e.g. `gzip`.
+    Token(String),
+    /// String value, e.g. `"en"` or `"fr CA"`.
+    String(String),
+}
+
+impl VariantValue {
+    /// Returns the value, regardless of its type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            VariantValue::Token(value) | VariantValue::String(value) => value,
+        }
+    }
+
+    fn to_bare_item(&self) -> BareItem {
+        match self {
+            VariantValue::Token(value) => BareItem::Token(value.clone()),
+            VariantValue::String(value) => BareItem::String(value.clone()),
+        }
+    }
+}
+
+impl PartialEq<&str> for VariantValue {
+    /// Compares the value with `other`, regardless of its type.
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Variants {
+    /// Parses `Variants` field value. Parameters on members are ignored.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<Variants> {
+        let dict = Parser::parse_dictionary(input_bytes)?;
+        let mut axes = IndexMap::with_capacity(dict.len());
+        for (axis, member) in dict {
+            let inner_list = match member {
+                ListEntry::InnerList(inner_list) => inner_list,
+                ListEntry::Item(_) => return Err("parse_variants: member is not an inner list"),
+            };
+            axes.insert(
+                axis,
+                values_from_items(
+                    inner_list.items,
+                    "parse_variants: value is not a token or string",
+                )?,
+            );
+        }
+        Ok(Variants { axes })
+    }
+
+    /// Returns every possible variant key, i.e. the cross product of all axis values.
+    ///
+    /// Combinations are ordered so that the values of the last axis vary fastest.
+    /// # Examples
+    /// ```
+    /// # use sfv::fields::Variants;
+    /// let variants = Variants::parse("a=(x y), b=(z)".as_bytes()).unwrap();
+    /// assert_eq!(variants.variant_keys().keys, vec![vec!["x", "z"], vec!["y", "z"]]);
+    /// ```
+    pub fn variant_keys(&self) -> VariantKey {
+        let mut keys: Vec<Vec<VariantValue>> = vec![vec![]];
+        for values in self.axes.values() {
+            keys = keys
+                .iter()
+                .flat_map(|key| {
+                    values.iter().map(move |value| {
+                        let mut key = key.clone();
+                        key.push(value.clone());
+                        key
+                    })
+                })
+                .collect();
+        }
+        VariantKey { keys }
+    }
+}
+
+impl SerializeValue for Variants {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let dict: Dictionary = self
+            .axes
+            .iter()
+            .map(|(axis, values)| {
+                (
+                    axis.clone(),
+                    ListEntry::from(inner_list_from_values(values)),
+                )
+            })
+            .collect();
+        dict.serialize_value()
+    }
+}
+
+/// `Variant-Key` field, identifying the variants a cached response represents.
+///
+/// Wire form is a List of inner lists, each holding one value per axis of the `Variants` field, in the same order.
+/// # Examples
+/// ```
+/// # use sfv::fields::{VariantKey, Variants};
+/// let variants = Variants::parse("accept-encoding=(gzip br), accept-language=(en fr)".as_bytes()).unwrap();
+/// let variant_key = VariantKey::parse("(gzip fr), (br fr)".as_bytes()).unwrap();
+/// assert!(variant_key.keys.iter().all(|key| variants.variant_keys().keys.contains(key)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VariantKey {
+    /// Variant keys, each holding one value per axis.
+    pub keys: Vec<Vec<VariantValue>>,
+}
+
+impl VariantKey {
+    /// Parses `Variant-Key` field value. Parameters on members are ignored.
+    pub fn parse(input_bytes: &[u8]) -> SFVResult<VariantKey> {
+        let list = Parser::parse_list(input_bytes)?;
+        let mut keys = Vec::with_capacity(list.len());
+        for member in list {
+            let inner_list = match member {
+                ListEntry::InnerList(inner_list) => inner_list,
+                ListEntry::Item(_) => return Err("parse_variant_key: member is not an inner list"),
+            };
+            keys.push(values_from_items(
+                inner_list.items,
+                "parse_variant_key: value is not a token or string",
+            )?);
+        }
+        Ok(VariantKey { keys })
+    }
+}
+
+impl SerializeValue for VariantKey {
+    fn serialize_value(&self) -> SFVResult<String> {
+        let list: List = self
+            .keys
+            .iter()
+            .map(|key| ListEntry::from(inner_list_from_values(key)))
+            .collect();
+        list.serialize_value()
+    }
+}
+
+fn values_from_items(items: Vec<Item>, type_error: &'static str) -> SFVResult<Vec<VariantValue>> {
+    items
+        .into_iter()
+        .map(|item| match item.bare_item {
+            BareItem::Token(value) => Ok(VariantValue::Token(value)),
+            BareItem::String(value) => Ok(VariantValue::String(value)),
+            _ => Err(type_error),
+        })
+        .collect()
+}
+
+fn inner_list_from_values(values: &[VariantValue]) -> InnerList {
+    values
+        .iter()
+        .map(|value| Item::new(value.to_bare_item()))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_serialize_variants() -> Result<(), &'static str> {
+        let variants = Variants::parse("a=(x \"needs space\");p, b=()".as_bytes())?;
+        assert_eq!(variants.axes["a"], vec!["x", "needs space"]);
+        assert!(variants.axes["b"].is_empty());
+        assert_eq!("a=(x \"needs space\"), b=()", variants.serialize_value()?);
+        assert!(variants.variant_keys().keys.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn variants_without_axes_have_single_empty_key() {
+        let variants = Variants::default();
+        assert_eq!(
+            vec![Vec::<VariantValue>::new()],
+            variants.variant_keys().keys
+        );
+    }
+
+    #[test]
+    fn parse_variants_errors() {
+        assert_eq!(
+            Err("parse_variants: member is not an inner list"),
+            Variants::parse("a=x".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_variants: value is not a token or string"),
+            Variants::parse("a=(x 1)".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_variant_key: member is not an inner list"),
+            VariantKey::parse("(x), y".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_variant_key: value is not a token or string"),
+            VariantKey::parse("(x ?1)".as_bytes())
+        );
+    }
+
+    #[test]
+    fn round_trip_variant_key() -> Result<(), &'static str> {
+        let variant_key = VariantKey::parse("(gzip en),  (br \"fr CA\")".as_bytes())?;
+        assert_eq!(
+            variant_key.keys,
+            vec![vec!["gzip", "en"], vec!["br", "fr CA"]]
+        );
+        assert_eq!("(gzip en), (br \"fr CA\")", variant_key.serialize_value()?);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_value_types() -> Result<(), &'static str> {
+        let variants = Variants::parse("accept-language=(\"en\" fr)".as_bytes())?;
+        assert_eq!(
+            vec![
+                VariantValue::String("en".to_owned()),
+                VariantValue::Token("fr".to_owned())
+            ],
+            variants.axes["accept-language"]
+        );
+        assert_eq!("accept-language=(\"en\" fr)", variants.serialize_value()?);
+
+        let variant_key = VariantKey::parse("(\"gzip\" en)".as_bytes())?;
+        assert_eq!("(\"gzip\" en)", variant_key.serialize_value()?);
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+/*!
+Generator of GREASE values for structured fields.
+
+GREASE ("Generate Random Extensions And Sustain Extensibility") values are
+random but valid tokens, keys and parameters that a sender mixes into a field,
+so that recipients which fail on unknown members get noticed early.
+
+`Grease` is deterministic for a given seed: seed it from a random source
+when generating values for real traffic. Generation gives up with `None`
+after a bounded number of attempts, e.g. when reserved values cover every
+value of the configured length.
+
+```
+use sfv::grease::Grease;
+use sfv::{BareItem, Item, SerializeValue};
+
+let mut grease = Grease::new(0x5eed).with_reserved(vec!["q", "v"]);
+let mut item = Item::new(BareItem::Token("gzip".into()));
+assert!(grease.insert_parameter(&mut item.params).is_some());
+assert_eq!(item.params.len(), 1);
+assert!(item.serialize_value().is_ok());
+```
+*/
+
+use crate::{BareItem, Parameters};
+
+const KEY_FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz*";
+const KEY_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_-.*";
+const TOKEN_FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ*";
+const TOKEN_CHARS: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!#$%&'*+-.^_`|~:/";
+const STRING_CHARS: &[u8] = b" !#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+// Attempts at generating a value that is not reserved or already present before giving up
+const MAX_ATTEMPTS: usize = 1_000;
+
+/// Generates random but valid structured field tokens, keys and parameters.
+#[derive(Debug, Clone)]
+pub struct Grease {
+    state: u64,
+    reserved: Vec<String>,
+    min_len: usize,
+    max_len: usize,
+}
+
+impl Grease {
+    /// Returns new `Grease` generator seeded with `seed`, producing values of 3 to 8 characters.
+    pub fn new(seed: u64) -> Grease {
+        Grease {
+            // Mix the seed so that small or zero seeds still produce a well-distributed state.
+            state: splitmix64(seed) | 1,
+            reserved: vec![],
+            min_len: 3,
+            max_len: 8,
+        }
+    }
+
+    /// Sets tokens and keys that must never be generated, e.g. names with a defined meaning for the field.
+    pub fn with_reserved<I, S>(mut self, reserved: I) -> Grease
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.reserved = reserved.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the inclusive range of generated token and key lengths. Lengths below 1 are raised to 1.
+    pub fn with_length(mut self, min_len: usize, max_len: usize) -> Grease {
+        self.min_len = min_len.max(1);
+        self.max_len = max_len.max(self.min_len);
+        self
+    }
+
+    /// Returns random valid key that is not reserved,
+    /// or `None` if none was found in a bounded number of attempts.
+    pub fn key(&mut self) -> Option<String> {
+        self.unreserved_word(KEY_FIRST_CHARS, KEY_CHARS)
+    }
+
+    /// Returns random valid token that is not reserved,
+    /// or `None` if none was found in a bounded number of attempts.
+    pub fn token(&mut self) -> Option<String> {
+        self.unreserved_word(TOKEN_FIRST_CHARS, TOKEN_CHARS)
+    }
+
+    /// Returns random `BareItem` of `Token`, `String`, `Integer` or `Boolean` type.
+    /// Falls back to `String` if no unreserved token was found.
+    pub fn bare_item(&mut self) -> BareItem {
+        match self.next_below(4) {
+            0 => match self.token() {
+                Some(token) => BareItem::Token(token),
+                None => BareItem::String(self.word(STRING_CHARS, STRING_CHARS)),
+            },
+            1 => BareItem::String(self.word(STRING_CHARS, STRING_CHARS)),
+            2 => BareItem::Integer(self.next_below(1_000) as i64),
+            _ => BareItem::Boolean(self.next_below(2) == 1),
+        }
+    }
+
+    /// Returns random parameter with a key that is not reserved,
+    /// or `None` if no such key was found in a bounded number of attempts.
+    pub fn parameter(&mut self) -> Option<(String, BareItem)> {
+        Some((self.key()?, self.bare_item()))
+    }
+
+    /// Inserts random parameter into `params` without replacing any of its existing members.
+    /// Returns the key of inserted parameter, or `None` if no unreserved and unused key was found
+    /// in a bounded number of attempts, leaving `params` unchanged.
+    pub fn insert_parameter(&mut self, params: &mut Parameters) -> Option<String> {
+        for _ in 0..MAX_ATTEMPTS {
+            let key = self.key()?;
+            if !params.contains_key(&key) {
+                params.insert(key.clone(), self.bare_item());
+                return Some(key);
+            }
+        }
+        None
+    }
+
+    fn is_reserved(&self, value: &str) -> bool {
+        self.reserved.iter().any(|reserved| reserved == value)
+    }
+
+    fn unreserved_word(&mut self, first_chars: &[u8], chars: &[u8]) -> Option<String> {
+        for _ in 0..MAX_ATTEMPTS {
+            let word = self.word(first_chars, chars);
+            if !self.is_reserved(&word) {
+                return Some(word);
+            }
+        }
+        None
+    }
+
+    fn word(&mut self, first_chars: &[u8], chars: &[u8]) -> String {
+        let len = self.min_len + self.next_below((self.max_len - self.min_len + 1) as u64) as usize;
+        let mut output = String::with_capacity(len);
+        output.push(self.pick(first_chars));
+        for _ in 1..len {
+            output.push(self.pick(chars));
+        }
+        output
+    }
+
+    fn pick(&mut self, chars: &[u8]) -> char {
+        chars[self.next_below(chars.len() as u64) as usize] as char
+    }
+
+    fn next_below(&mut self, bound: u64) -> u64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) % bound
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chars;
+    use crate::{Item, Parser, SerializeValue};
+
+    #[test]
+    fn generates_valid_values() -> Result<(), &'static str> {
+        let mut grease = Grease::new(0);
+        for _ in 0..500 {
+            Parser::validate_key(&grease.key().unwrap())?;
+
+            let token = grease.token().unwrap();
+            assert_eq!(
+                BareItem::Token(token.clone()),
+                Parser::parse_item(token.as_bytes())?.bare_item
+            );
+
+            let mut item = Item::new(grease.bare_item());
+            assert!(grease.insert_parameter(&mut item.params).is_some());
+            let serialized = item.serialize_value()?;
+            assert_eq!(item, Parser::parse_item(serialized.as_bytes())?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn respects_reserved_and_length() {
+        let mut grease = Grease::new(42)
+            .with_reserved(vec!["a", "b", "c"])
+            .with_length(1, 1);
+        for _ in 0..200 {
+            let key = grease.key().unwrap();
+            assert_eq!(1, key.len());
+            assert!(!["a", "b", "c"].contains(&key.as_str()));
+            assert!(key.chars().all(chars::is_allowed_key_char));
+        }
+    }
+
+    #[test]
+    fn gives_up_when_exhausted() {
+        let reserved: Vec<String> = KEY_FIRST_CHARS
+            .iter()
+            .map(|&c| (c as char).to_string())
+            .collect();
+        let mut grease = Grease::new(3).with_reserved(reserved).with_length(1, 1);
+        assert_eq!(None, grease.key());
+        assert_eq!(None, grease.parameter());
+
+        let mut grease = Grease::new(3).with_length(1, 1);
+        let mut params = Parameters::new();
+        while grease.insert_parameter(&mut params).is_some() {}
+        assert_eq!(KEY_FIRST_CHARS.len(), params.len());
+    }
+
+    #[test]
+    fn is_deterministic_for_seed() {
+        let mut first = Grease::new(7);
+        let mut second = Grease::new(7);
+        for _ in 0..10 {
+            assert_eq!(first.parameter(), second.parameter());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+/*!
+Typed header support for the `headers` crate.
+
+`StructuredField` implements `headers::Header` for any field described by `FieldName`,
+so structured fields can be used with `HeaderMapExt::typed_get` and `HeaderMapExt::typed_insert`.
+
+```
+use headers::{HeaderMap, HeaderMapExt, HeaderName};
+use sfv::headers::{FieldName, StructuredField};
+use sfv::{Dictionary, SerializeValue};
+
+struct Priority;
+
+impl FieldName for Priority {
+    type Value = Dictionary;
+
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = HeaderName::from_static("priority");
+        &NAME
+    }
+}
+
+let mut map = HeaderMap::new();
+map.append("priority", "u=2".parse().unwrap());
+map.append("priority", "i".parse().unwrap());
+
+let priority: StructuredField<Priority> = map.typed_get().unwrap();
+assert_eq!(priority.value.serialize_value().unwrap(), "u=2, i");
+
+map.typed_insert(priority);
+assert_eq!(map.get("priority").unwrap(), "u=2, i");
+```
+*/
+
+use crate::FieldType;
+use ::headers::{Error, Header, HeaderName, HeaderValue};
+use std::fmt;
+
+/// Describes a structured field: its header name and structured field value type.
+pub trait FieldName {
+    /// Structured field value type, i.e. `Item`, `List`, `Dictionary` or another `FieldType`.
+    type Value: FieldType;
+
+    /// Returns header name of the field.
+    fn name() -> &'static HeaderName;
+}
+
+/// Structured field value of the field described by `N`, usable as a typed header.
+pub struct StructuredField<N: FieldName> {
+    /// Parsed value of the field.
+    pub value: N::Value,
+}
+
+impl<N: FieldName> StructuredField<N> {
+    /// Returns new `StructuredField` with the given value.
+    pub fn new(value: N::Value) -> StructuredField<N> {
+        StructuredField { value }
+    }
+}
+
+impl<N: FieldName> Header for StructuredField<N> {
+    fn name() -> &'static HeaderName {
+        N::name()
+    }
+
+    /// Parses all field lines, combined with commas as the specification requires.
+    fn decode<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut input_bytes = vec![];
+        for (idx, value) in values.enumerate() {
+            if idx > 0 {
+                input_bytes.extend_from_slice(b", ");
+            }
+            input_bytes.extend_from_slice(value.as_bytes());
+        }
+
+        let value = N::Value::parse_field(&input_bytes).map_err(|_| Error::invalid())?;
+        Ok(StructuredField { value })
+    }
+
+    /// Encodes the value as a single field line.
+    /// If the value can't be serialized, nothing is encoded and so the header is omitted.
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let header_value = self
+            .value
+            .serialize_field()
+            .ok()
+            .and_then(|output| HeaderValue::from_str(&output).ok());
+        if let Some(header_value) = header_value {
+            values.extend(std::iter::once(header_value));
+        }
+    }
+}
+
+impl<N: FieldName> Clone for StructuredField<N>
+where
+    N::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        StructuredField {
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<N: FieldName> fmt::Debug for StructuredField<N>
+where
+    N::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StructuredField")
+            .field("name", N::name())
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<N: FieldName> PartialEq for StructuredField<N>
+where
+    N::Value: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BareItem, Item, List};
+    use ::headers::{HeaderMap, HeaderMapExt};
+
+    struct AcceptCh;
+
+    impl FieldName for AcceptCh {
+        type Value = List;
+
+        fn name() -> &'static HeaderName {
+            static NAME: HeaderName = HeaderName::from_static("accept-ch");
+            &NAME
+        }
+    }
+
+    struct CacheStatus;
+
+    impl FieldName for CacheStatus {
+        type Value = Item;
+
+        fn name() -> &'static HeaderName {
+            static NAME: HeaderName = HeaderName::from_static("cache-status");
+            &NAME
+        }
+    }
+
+    #[test]
+    fn typed_get_combines_field_lines() {
+        let mut map = HeaderMap::new();
+        assert_eq!(None, map.typed_get::<StructuredField<AcceptCh>>());
+
+        map.append("accept-ch", "a".parse().unwrap());
+        map.append("accept-ch", "b, c".parse().unwrap());
+        let field: StructuredField<AcceptCh> = map.typed_get().unwrap();
+        assert_eq!(3, field.value.len());
+
+        map.append("cache-status", "hit".parse().unwrap());
+        assert!(map.typed_get::<StructuredField<CacheStatus>>().is_some());
+        map.append("cache-status", "miss".parse().unwrap());
+        assert!(map.typed_try_get::<StructuredField<CacheStatus>>().is_err());
+    }
+
+    #[test]
+    fn typed_insert_skips_invalid_values() {
+        let mut map = HeaderMap::new();
+        map.typed_insert(StructuredField::<CacheStatus>::new(Item::new(
+            BareItem::Token("hit".into()),
+        )));
+        assert_eq!("hit", map.get("cache-status").unwrap());
+
+        map.typed_insert(StructuredField::<AcceptCh>::new(vec![]));
+        assert!(map.get("accept-ch").is_none());
+    }
+}
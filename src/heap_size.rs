@@ -0,0 +1,110 @@
+use crate::{BareItem, Dictionary, InnerList, Item, List, ListEntry, Parameters};
+use indexmap::IndexMap;
+use std::mem::size_of;
+
+/// Estimates heap memory owned by a structured field value.
+pub trait HeapSize {
+    /// Returns the estimated number of heap bytes owned by the value, excluding the value itself.
+    /// Sums the capacity of every string, byte sequence, vector and map reachable from the value.
+    /// Map sizes are approximated, as the exact layout of `IndexMap` is an implementation detail.
+    /// # Examples
+    /// ```
+    /// # use sfv::{HeapSize, Parser};
+    /// let dict = Parser::parse_dictionary("a=\"some string\", b=(1 2 3)".as_bytes()).unwrap();
+    /// assert!(dict.heap_size() > "some string".len());
+    /// ```
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for BareItem {
+    fn heap_size(&self) -> usize {
+        match self {
+            BareItem::String(val) | BareItem::Token(val) => val.capacity(),
+            BareItem::ByteSeq(val) => val.capacity(),
+            BareItem::Decimal(_) | BareItem::Integer(_) | BareItem::Boolean(_) => 0,
+        }
+    }
+}
+
+impl HeapSize for Item {
+    fn heap_size(&self) -> usize {
+        self.bare_item.heap_size() + self.params.heap_size()
+    }
+}
+
+impl HeapSize for InnerList {
+    fn heap_size(&self) -> usize {
+        vec_heap_size(&self.items) + self.params.heap_size()
+    }
+}
+
+impl HeapSize for ListEntry {
+    fn heap_size(&self) -> usize {
+        match self {
+            ListEntry::Item(item) => item.heap_size(),
+            ListEntry::InnerList(inner_list) => inner_list.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for List {
+    fn heap_size(&self) -> usize {
+        vec_heap_size(self)
+    }
+}
+
+impl HeapSize for Parameters {
+    fn heap_size(&self) -> usize {
+        map_heap_size(self)
+    }
+}
+
+impl HeapSize for Dictionary {
+    fn heap_size(&self) -> usize {
+        map_heap_size(self)
+    }
+}
+
+fn vec_heap_size<T: HeapSize>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>() + vec.iter().map(HeapSize::heap_size).sum::<usize>()
+}
+
+fn map_heap_size<V: HeapSize>(map: &IndexMap<String, V>) -> usize {
+    // Each entry is stored with its hash, and the index table keeps a usize and a control byte per slot.
+    let slot_size =
+        size_of::<usize>() + size_of::<String>() + size_of::<V>() + size_of::<usize>() + 1;
+    map.capacity() * slot_size
+        + map
+            .iter()
+            .map(|(key, value)| key.capacity() + value.heap_size())
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heap_size_of_bare_items() {
+        assert_eq!(0, BareItem::Integer(1).heap_size());
+        assert_eq!(0, BareItem::Boolean(true).heap_size());
+        let string = String::with_capacity(20);
+        assert_eq!(20, BareItem::String(string).heap_size());
+        assert_eq!(3, BareItem::ByteSeq(vec![1, 2, 3]).heap_size());
+    }
+
+    #[test]
+    fn heap_size_includes_nested_members() {
+        let item = Item::new(BareItem::Token("token".to_owned()));
+        let mut params = Parameters::new();
+        params.insert("key".to_owned(), BareItem::String("value".to_owned()));
+        let item_with_params = Item::with_params(BareItem::Token("token".to_owned()), params);
+        assert!(item_with_params.heap_size() >= item.heap_size() + "key".len() + "value".len());
+
+        let list: List = vec![
+            item.clone().into(),
+            InnerList::new(vec![item.clone()]).into(),
+        ];
+        assert!(list.heap_size() >= 2 * item.heap_size() + 2 * size_of::<ListEntry>());
+    }
+}
@@ -16,6 +16,16 @@ There's also a few primitive types used to construct structured field values:
 - `InnerList` is an array of zero or more `Items`. Can have `Parameters`.
 - `ListEntry` represents either `Item` or `InnerList` as a member of `List` or as member-value in `Dictionary`.
 
+# Crate Features
+
+- `headers` - enables the `headers` module with `StructuredField` typed header. Implies `http`.
+- `http` - parses `http::HeaderValue` with `Parser::from_header_value` and serializes into it with `SerializeValue::serialize_header_value`.
+- `rayon` - parses inputs in parallel in the `batch` module.
+- `serde` - implements `Serialize` and `Deserialize` for `Item`, `InnerList`, `ListEntry` and `BareItem`, and so for `List`, `Dictionary` and `Parameters`,
+  and enables the `serde` module with adapters storing structured field values as strings in serde data structures.
+  Deserialized bare items and parameter keys are checked as the serializer checks them. Keys of `Dictionary` are not, as it's a plain `IndexMap`,
+  and are checked when it's serialized.
+
 # Examples
 
 ### Parsing
@@ -60,6 +70,7 @@ let dict_header = "u=2, n=(* foo 2)";
     }
 
     // Case 2 - matching on all possible types
+    // `BareItem` is non-exhaustive, so a wildcard arm is required for types added in the future
     match dict.get("u") {
         Some(ListEntry::Item(item)) => match &item.bare_item {
             BareItem::Token(val) => {
@@ -86,6 +97,10 @@ let dict_header = "u=2, n=(* foo 2)";
                 // do something if it's a ByteSeq
                 println!("{:?}", val);
             }
+            other => {
+                // do something if it's a type added in a future version
+                println!("{}", other.item_type());
+            }
         },
         Some(ListEntry::InnerList(inner_list)) => {
             // do something if it's an InnerList
@@ -164,25 +179,75 @@ assert_eq!(
 ```
 */
 
+pub mod batch;
+pub mod borrowed;
+mod budget;
+mod builder;
+pub mod chars;
+#[doc(hidden)]
+pub mod construct;
+mod decimal;
+pub mod diff;
+mod events;
+mod field_type;
+pub mod fields;
+pub mod grease;
+#[cfg(feature = "headers")]
+pub mod headers;
+mod heap_size;
+pub mod multi;
+mod parsed;
 mod parser;
 mod ref_serializer;
+mod rewrite;
+mod salvage;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod serialized_len;
 mod serializer;
+mod streaming;
+mod template;
+pub mod testing;
 mod utils;
+mod visit;
 
 #[cfg(test)]
 mod test_parser;
 #[cfg(test)]
 mod test_serializer;
 use indexmap::IndexMap;
+use rust_decimal::prelude::ToPrimitive;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+use std::time::Duration;
 
 pub use rust_decimal::{
     prelude::{FromPrimitive, FromStr},
     Decimal,
 };
 
-pub use parser::{ParseMore, ParseValue, Parser};
-pub use ref_serializer::{RefDictSerializer, RefItemSerializer, RefListSerializer};
-pub use serializer::SerializeValue;
+pub use budget::{Budgeted, SerializeWithin};
+pub use builder::{DictionaryBuilder, DictionaryEntryBuilder};
+pub use decimal::{DecimalParts, DecimalRounding, ToDecimal};
+pub use events::{Event, Events};
+pub use field_type::FieldType;
+pub use heap_size::HeapSize;
+pub use parsed::{DictionarySplice, Parsed};
+pub use parser::{
+    unescape_string, DuplicateKeys, ParseLimits, ParseMore, ParseOptions, ParseValue, Parser,
+};
+pub use ref_serializer::{
+    Checkpoint, FmtWriter, RefDictSerializer, RefItemSerializer, RefListSerializer,
+};
+pub use rewrite::{MemberRewrite, SerializeRewritten};
+pub use salvage::{Salvaged, Skipped};
+pub use serialized_len::SerializedLen;
+pub use serializer::{escape_string, SerializeValue};
+pub use streaming::StreamingParser;
+pub use template::FieldTemplate;
+pub use visit::{MutVisitor, VisitMut};
 
 type SFVResult<T> = std::result::Result<T, &'static str>;
 
@@ -192,6 +257,11 @@ type SFVResult<T> = std::result::Result<T, &'static str>;
 // bare-item = sf-integer / sf-decimal / sf-string / sf-token
 //             / sf-binary / sf-boolean
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "crate::serde::ItemData")
+)]
 pub struct Item {
     /// Value of `Item`.
     pub bare_item: BareItem,
@@ -211,9 +281,140 @@ impl Item {
     pub fn with_params(bare_item: BareItem, params: Parameters) -> Item {
         Item { bare_item, params }
     }
+
+    /// Returns new `Item` from its value and `Parameters`.
+    /// Same as `Item::with_params`, named to pair with `Item::into_parts`.
+    pub fn from_parts(bare_item: BareItem, params: Parameters) -> Item {
+        Item::with_params(bare_item, params)
+    }
+
+    /// Consumes `Item`, returning its value and `Parameters`.
+    /// ```
+    /// # use sfv::{BareItem, Item, Parameters};
+    /// let item = Item::new(BareItem::Integer(1));
+    /// let (bare_item, params) = item.into_parts();
+    /// assert_eq!(bare_item, BareItem::Integer(1));
+    /// assert!(params.is_empty());
+    /// ```
+    pub fn into_parts(self) -> (BareItem, Parameters) {
+        (self.bare_item, self.params)
+    }
+
+    /// Returns `Item` with parameter `key` set to `value`, for chaining.
+    /// The key is validated when `Item` is serialized.
+    /// ```
+    /// # use sfv::{BareItem, Item, SerializeValue};
+    /// let item = Item::new(BareItem::Token("gzip".into())).param("q", 1).param("fast", true);
+    /// assert_eq!(item.serialize_value().unwrap(), "gzip;q=1;fast");
+    /// ```
+    pub fn param<V: Into<BareItem>>(mut self, key: &str, value: V) -> Item {
+        self.params.insert(key.to_owned(), value.into());
+        self
+    }
+
+    /// Returns `true` if `Item`'s value is a token equal to `token`.
+    /// ```
+    /// # use sfv::Parser;
+    /// let item = Parser::parse_item("gzip;q=1".as_bytes()).unwrap();
+    /// assert!(item.matches_token("gzip"));
+    /// assert!(!item.matches_token("br"));
+    /// ```
+    pub fn matches_token(&self, token: &str) -> bool {
+        self.bare_item.as_token() == Some(token)
+    }
+
+    /// Returns `true` if `Item` has a parameter named `key` with a value equal to `value`.
+    /// ```
+    /// # use sfv::Parser;
+    /// let item = Parser::parse_item("gzip;q=1;fast".as_bytes()).unwrap();
+    /// assert!(item.has_param_eq("q", 1));
+    /// assert!(item.has_param_eq("fast", true));
+    /// assert!(!item.has_param_eq("q", 2));
+    /// ```
+    pub fn has_param_eq<V: Into<BareItem>>(&self, key: &str, value: V) -> bool {
+        self.params.get(key) == Some(&value.into())
+    }
+}
+
+impl FromStr for Item {
+    type Err = &'static str;
+
+    /// Parses `Item` from its serialized form, as `Parser::parse_item` does.
+    /// ```
+    /// # use sfv::{BareItem, Item};
+    /// let item: Item = "12;foo=bar".parse().unwrap();
+    /// assert_eq!(item.bare_item, BareItem::Integer(12));
+    /// assert!(item.has_param_eq("foo", BareItem::Token("bar".into())));
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Parser::parse_item(input.as_bytes())
+    }
+}
+
+impl TryFrom<&str> for Item {
+    type Error = &'static str;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+impl From<(BareItem, Parameters)> for Item {
+    /// Converts value and `Parameters` into `Item`.
+    /// ```
+    /// # use sfv::{BareItem, Item, Parameters};
+    /// let items: Vec<Item> = vec![(BareItem::Integer(1), Parameters::new())]
+    ///     .into_iter()
+    ///     .map(Item::from)
+    ///     .collect();
+    /// assert_eq!(items, vec![Item::new(BareItem::Integer(1))]);
+    /// ```
+    fn from((bare_item, params): (BareItem, Parameters)) -> Self {
+        Item::with_params(bare_item, params)
+    }
+}
+
+impl From<Item> for (BareItem, Parameters) {
+    /// Converts `Item` into its value and `Parameters`.
+    /// ```
+    /// # use sfv::{BareItem, Item, Parameters};
+    /// let (bare_item, params): (BareItem, Parameters) = Item::new(BareItem::Integer(1)).into();
+    /// assert_eq!(bare_item, BareItem::Integer(1));
+    /// assert!(params.is_empty());
+    /// ```
+    fn from(item: Item) -> Self {
+        item.into_parts()
+    }
+}
+
+/// Returns whether an `Item` matches a pattern for its value and, optionally, the values of some of its parameters.
+///
+/// The pattern is matched against a reference to the `BareItem`, and may have an `if` guard.
+/// Each following `key = value` pair requires a parameter equal to `value` converted into `BareItem`.
+/// ```
+/// # use sfv::{matches_item, BareItem, Parser};
+/// let item = Parser::parse_item("gzip;q=1;fast".as_bytes()).unwrap();
+/// assert!(matches_item!(item, BareItem::Token(t) if t == "gzip"));
+/// assert!(matches_item!(item, BareItem::Token(_), "q" = 1, "fast" = true));
+/// assert!(!matches_item!(item, BareItem::Token(_), "q" = 2));
+/// assert!(!matches_item!(item, BareItem::Integer(_)));
+/// ```
+#[macro_export]
+macro_rules! matches_item {
+    ($item:expr, $pattern:pat $(if $guard:expr)? $(, $key:literal = $value:expr)* $(,)?) => {{
+        let item: &$crate::Item = &$item;
+        match &item.bare_item {
+            $pattern $(if $guard)? => true $(&& item.has_param_eq($key, $value))*,
+            _ => false,
+        }
+    }};
 }
 
 /// Represents `Dictionary` type structured field value.
+///
+/// Member names are hashed with the DoS-resistant `RandomState`.
+/// Use `Parser::parse_dictionary_with_hasher` to parse into an `IndexMap` with another hasher for member names,
+/// or `multi::parse_dictionary` for a `Vec` of members.
 // sf-dictionary  = dict-member *( OWS "," OWS dict-member )
 // dict-member    = member-name [ "=" member-value ]
 // member-name    = key
@@ -235,8 +436,122 @@ pub type List = Vec<ListEntry>;
 // param-value   = bare-item
 pub type Parameters = IndexMap<String, BareItem>;
 
+/// Inserts members into `Parameters` or `Dictionary`, validating the key and converting the value.
+pub trait TryInsert {
+    /// Type of the member value stored in the collection.
+    type Value;
+
+    /// Validates `key` and inserts `value` converted into `Self::Value`.
+    /// Returns the previous value stored under `key`, if any, or an error if `key` is not a valid structured field key.
+    /// # Examples
+    /// ```
+    /// # use sfv::{BareItem, Parameters, SerializeValue, TryInsert, Item};
+    /// let mut params = Parameters::new();
+    /// params.try_insert("a", 1).unwrap();
+    /// params.try_insert("b", BareItem::Token("tok".into())).unwrap();
+    /// params.try_insert("c", true).unwrap();
+    /// assert!(params.try_insert("Invalid", 2).is_err());
+    ///
+    /// let item = Item::with_params(BareItem::Integer(5), params);
+    /// assert_eq!(item.serialize_value().unwrap(), "5;a=1;b=tok;c");
+    /// ```
+    fn try_insert<K, V>(&mut self, key: K, value: V) -> SFVResult<Option<Self::Value>>
+    where
+        K: Into<String>,
+        V: Into<Self::Value>;
+
+    /// Creates a collection from key-value pairs, validating every key.
+    /// Returns an error if any of the keys is not a valid structured field key.
+    /// # Examples
+    /// ```
+    /// # use sfv::{BareItem, Dictionary, Item, Parameters, SerializeValue, TryInsert};
+    /// let params = Parameters::try_from_iter(vec![("q", BareItem::Integer(1)), ("v", true.into())]).unwrap();
+    /// let dict = Dictionary::try_from_iter(vec![("a", Item::with_params(BareItem::Integer(5), params))]).unwrap();
+    /// assert_eq!(dict.serialize_value().unwrap(), "a=5;q=1;v");
+    ///
+    /// assert!(Parameters::try_from_iter(vec![("1a", 1)]).is_err());
+    /// ```
+    fn try_from_iter<I, K, V>(iter: I) -> SFVResult<Self>
+    where
+        Self: Sized + Default,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Self::Value>,
+    {
+        let mut output = Self::default();
+        for (key, value) in iter {
+            output.try_insert(key, value)?;
+        }
+        Ok(output)
+    }
+}
+
+impl TryInsert for Dictionary {
+    type Value = ListEntry;
+
+    fn try_insert<K, V>(&mut self, key: K, value: V) -> SFVResult<Option<ListEntry>>
+    where
+        K: Into<String>,
+        V: Into<ListEntry>,
+    {
+        let key = key.into();
+        Parser::validate_key(&key)?;
+        Ok(self.insert(key, value.into()))
+    }
+}
+
+impl TryInsert for Parameters {
+    type Value = BareItem;
+
+    fn try_insert<K, V>(&mut self, key: K, value: V) -> SFVResult<Option<BareItem>>
+    where
+        K: Into<String>,
+        V: Into<BareItem>,
+    {
+        let key = key.into();
+        Parser::validate_key(&key)?;
+        Ok(self.insert(key, value.into()))
+    }
+}
+
+/// Interprets integer parameters as durations, as in `max-age` or `ttl` style parameters.
+pub trait GetDuration {
+    /// Returns the value of parameter `key` as `Duration` of whole seconds.
+    /// Returns `None` if the parameter is missing or is not a non-negative integer.
+    /// # Examples
+    /// ```
+    /// # use sfv::{GetDuration, Parser};
+    /// # use std::time::Duration;
+    /// let item = Parser::parse_item("stale;ttl=30;age=-2".as_bytes()).unwrap();
+    /// assert_eq!(item.get_duration_secs("ttl"), Some(Duration::from_secs(30)));
+    /// assert_eq!(item.params.get_duration_secs("ttl"), Some(Duration::from_secs(30)));
+    /// assert_eq!(item.get_duration_secs("age"), None);
+    /// assert_eq!(item.get_duration_secs("missing"), None);
+    /// ```
+    fn get_duration_secs(&self, key: &str) -> Option<Duration>;
+}
+
+impl GetDuration for Parameters {
+    fn get_duration_secs(&self, key: &str) -> Option<Duration> {
+        self.get(key).and_then(BareItem::as_duration_secs)
+    }
+}
+
+impl GetDuration for Item {
+    fn get_duration_secs(&self, key: &str) -> Option<Duration> {
+        self.params.get_duration_secs(key)
+    }
+}
+
+impl GetDuration for InnerList {
+    fn get_duration_secs(&self, key: &str) -> Option<Duration> {
+        self.params.get_duration_secs(key)
+    }
+}
+
 /// Represents a member of `List` or `Dictionary` structured field value.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ListEntry {
     /// Member of `Item` type.
     Item(Item),
@@ -244,6 +559,66 @@ pub enum ListEntry {
     InnerList(InnerList),
 }
 
+impl ListEntry {
+    /// Returns parameters of the member, whether it is an `Item` or an `InnerList`.
+    /// ```
+    /// # use sfv::{BareItem, Parser};
+    /// let list = Parser::parse_list("a;q=1, (b c);q=2".as_bytes()).unwrap();
+    /// let qs: Vec<_> = list.iter().map(|entry| entry.params().get("q")).collect();
+    /// assert_eq!(qs, vec![Some(&BareItem::Integer(1)), Some(&BareItem::Integer(2))]);
+    /// ```
+    pub fn params(&self) -> &Parameters {
+        match self {
+            ListEntry::Item(item) => &item.params,
+            ListEntry::InnerList(inner_list) => &inner_list.params,
+        }
+    }
+
+    /// Returns mutable parameters of the member, whether it is an `Item` or an `InnerList`.
+    /// ```
+    /// # use sfv::{BareItem, Parser, SerializeValue};
+    /// let mut list = Parser::parse_list("a, (b c)".as_bytes()).unwrap();
+    /// for entry in list.iter_mut() {
+    ///     entry.params_mut().insert("q".into(), BareItem::Integer(1));
+    /// }
+    /// assert_eq!(list.serialize_value().unwrap(), "a;q=1, (b c);q=1");
+    /// ```
+    pub fn params_mut(&mut self) -> &mut Parameters {
+        match self {
+            ListEntry::Item(item) => &mut item.params,
+            ListEntry::InnerList(inner_list) => &mut inner_list.params,
+        }
+    }
+
+    /// If the member is an `Item`, returns it, otherwise returns `None`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, ListEntry};
+    /// let entry: ListEntry = Item::new(BareItem::Integer(1)).into();
+    /// assert_eq!(entry.as_item(), Some(&Item::new(BareItem::Integer(1))));
+    /// assert_eq!(entry.as_inner_list(), None);
+    /// ```
+    pub fn as_item(&self) -> Option<&Item> {
+        match self {
+            ListEntry::Item(item) => Some(item),
+            ListEntry::InnerList(_) => None,
+        }
+    }
+
+    /// If the member is an `InnerList`, returns it, otherwise returns `None`.
+    /// ```
+    /// # use sfv::{InnerList, ListEntry};
+    /// let entry: ListEntry = InnerList::new(vec![]).into();
+    /// assert_eq!(entry.as_inner_list(), Some(&InnerList::new(vec![])));
+    /// assert_eq!(entry.as_item(), None);
+    /// ```
+    pub fn as_inner_list(&self) -> Option<&InnerList> {
+        match self {
+            ListEntry::InnerList(inner_list) => Some(inner_list),
+            ListEntry::Item(_) => None,
+        }
+    }
+}
+
 impl From<Item> for ListEntry {
     fn from(item: Item) -> Self {
         ListEntry::Item(item)
@@ -256,10 +631,92 @@ impl From<InnerList> for ListEntry {
     }
 }
 
+impl FromIterator<Item> for List {
+    /// Creates `List` from `Items`, wrapping each of them into `ListEntry::Item`.
+    /// ```
+    /// # use sfv::{BareItem, Item, List, SerializeValue};
+    /// let items = vec![Item::new(BareItem::Integer(1)), Item::new(BareItem::Boolean(false))];
+    /// let list: List = items.into_iter().collect();
+    /// assert_eq!(list.serialize_value().unwrap(), "1, ?0");
+    /// ```
+    fn from_iter<I: IntoIterator<Item = Item>>(iter: I) -> Self {
+        iter.into_iter().map(ListEntry::Item).collect()
+    }
+}
+
+impl TryFrom<ListEntry> for Item {
+    type Error = ListEntry;
+
+    /// Converts `ListEntry::Item` into `Item`, otherwise returns the original `ListEntry`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, ListEntry};
+    /// # use std::convert::TryFrom;
+    /// let entry: ListEntry = Item::new(BareItem::Integer(1)).into();
+    /// assert_eq!(Item::try_from(entry).unwrap(), Item::new(BareItem::Integer(1)));
+    ///
+    /// let entry: ListEntry = InnerList::new(vec![]).into();
+    /// assert!(Item::try_from(entry).is_err());
+    /// ```
+    fn try_from(entry: ListEntry) -> Result<Self, Self::Error> {
+        match entry {
+            ListEntry::Item(item) => Ok(item),
+            _ => Err(entry),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ListEntry> for &'a Item {
+    type Error = &'a ListEntry;
+
+    fn try_from(entry: &'a ListEntry) -> Result<Self, Self::Error> {
+        match entry {
+            ListEntry::Item(item) => Ok(item),
+            _ => Err(entry),
+        }
+    }
+}
+
+impl TryFrom<ListEntry> for InnerList {
+    type Error = ListEntry;
+
+    /// Converts `ListEntry::InnerList` into `InnerList`, otherwise returns the original `ListEntry`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, ListEntry};
+    /// # use std::convert::TryFrom;
+    /// let entry: ListEntry = InnerList::new(vec![]).into();
+    /// assert_eq!(InnerList::try_from(entry).unwrap(), InnerList::new(vec![]));
+    ///
+    /// let entry: ListEntry = Item::new(BareItem::Integer(1)).into();
+    /// assert!(<&InnerList>::try_from(&entry).is_err());
+    /// ```
+    fn try_from(entry: ListEntry) -> Result<Self, Self::Error> {
+        match entry {
+            ListEntry::InnerList(inner_list) => Ok(inner_list),
+            _ => Err(entry),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ListEntry> for &'a InnerList {
+    type Error = &'a ListEntry;
+
+    fn try_from(entry: &'a ListEntry) -> Result<Self, Self::Error> {
+        match entry {
+            ListEntry::InnerList(inner_list) => Ok(inner_list),
+            _ => Err(entry),
+        }
+    }
+}
+
 /// Array of `Items` with associated `Parameters`.
 // inner-list    = "(" *SP [ sf-item *( 1*SP sf-item ) *SP ] ")"
 //                 parameters
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "crate::serde::InnerListData")
+)]
 pub struct InnerList {
     /// `Items` that `InnerList` contains. Can be empty.
     pub items: Vec<Item>,
@@ -280,10 +737,87 @@ impl InnerList {
     pub fn with_params(items: Vec<Item>, params: Parameters) -> InnerList {
         InnerList { items, params }
     }
+
+    /// Returns new `InnerList` from its `Items` and `Parameters`.
+    /// Same as `InnerList::with_params`, named to pair with `InnerList::into_parts`.
+    pub fn from_parts(items: Vec<Item>, params: Parameters) -> InnerList {
+        InnerList::with_params(items, params)
+    }
+
+    /// Consumes `InnerList`, returning its `Items` and `Parameters`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item};
+    /// let inner_list = InnerList::new(vec![Item::new(BareItem::Boolean(false))]);
+    /// let (items, params) = inner_list.into_parts();
+    /// assert_eq!(items, vec![Item::new(BareItem::Boolean(false))]);
+    /// assert!(params.is_empty());
+    /// ```
+    pub fn into_parts(self) -> (Vec<Item>, Parameters) {
+        (self.items, self.params)
+    }
+
+    /// Returns `InnerList` with parameter `key` set to `value`, for chaining.
+    /// The key is validated when `InnerList` is serialized.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, List, SerializeValue};
+    /// let inner_list = InnerList::new(vec![Item::new(BareItem::Integer(1))]).param("a", false);
+    /// let list: List = vec![inner_list.into()];
+    /// assert_eq!(list.serialize_value().unwrap(), "(1);a=?0");
+    /// ```
+    pub fn param<V: Into<BareItem>>(mut self, key: &str, value: V) -> InnerList {
+        self.params.insert(key.to_owned(), value.into());
+        self
+    }
+}
+
+impl From<(Vec<Item>, Parameters)> for InnerList {
+    /// Converts `Items` and `Parameters` into `InnerList`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, Parameters};
+    /// let inner_list = InnerList::from((vec![Item::new(BareItem::Integer(1))], Parameters::new()));
+    /// assert_eq!(inner_list, InnerList::new(vec![Item::new(BareItem::Integer(1))]));
+    /// ```
+    fn from((items, params): (Vec<Item>, Parameters)) -> Self {
+        InnerList::with_params(items, params)
+    }
+}
+
+impl From<InnerList> for (Vec<Item>, Parameters) {
+    /// Converts `InnerList` into its `Items` and `Parameters`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item, Parameters};
+    /// let (items, params): (Vec<Item>, Parameters) = InnerList::new(vec![]).into();
+    /// assert!(items.is_empty());
+    /// assert!(params.is_empty());
+    /// ```
+    fn from(inner_list: InnerList) -> Self {
+        inner_list.into_parts()
+    }
+}
+
+impl From<Vec<Item>> for InnerList {
+    /// Converts `Items` into `InnerList` with empty `Parameters`.
+    /// ```
+    /// # use sfv::{BareItem, InnerList, Item};
+    /// let inner_list: InnerList = vec![Item::new(BareItem::Integer(1))].into();
+    /// assert_eq!(inner_list, InnerList::new(vec![Item::new(BareItem::Integer(1))]));
+    /// ```
+    fn from(items: Vec<Item>) -> Self {
+        InnerList::new(items)
+    }
 }
 
 /// `BareItem` type is used to construct `Items` or `Parameters` values.
+///
+/// New bare item types may be added by future revisions of the specification, so this enum is non-exhaustive.
+/// Prefer `item_type()`, `as_*` and `into_*` accessors, or include a wildcard arm when matching on it.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "crate::serde::BareItemData")
+)]
+#[non_exhaustive]
 pub enum BareItem {
     /// Decimal number
     // sf-decimal  = ["-"] 1*12DIGIT "." 1*3DIGIT
@@ -306,7 +840,50 @@ pub enum BareItem {
     Token(String),
 }
 
+/// Type of `BareItem` or `RefBareItem` value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum BareItemType {
+    Decimal,
+    Integer,
+    String,
+    ByteSeq,
+    Boolean,
+    Token,
+}
+
+impl fmt::Display for BareItemType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BareItemType::Decimal => "decimal",
+            BareItemType::Integer => "integer",
+            BareItemType::String => "string",
+            BareItemType::ByteSeq => "byte sequence",
+            BareItemType::Boolean => "boolean",
+            BareItemType::Token => "token",
+        };
+        f.write_str(name)
+    }
+}
+
 impl BareItem {
+    /// Returns type of `BareItem` value.
+    /// ```
+    /// # use sfv::{BareItem, BareItemType};
+    /// let bare_item = BareItem::Token("foo".into());
+    /// assert_eq!(bare_item.item_type(), BareItemType::Token);
+    /// assert_eq!(bare_item.item_type().to_string(), "token");
+    /// ```
+    pub fn item_type(&self) -> BareItemType {
+        match self {
+            BareItem::Decimal(_) => BareItemType::Decimal,
+            BareItem::Integer(_) => BareItemType::Integer,
+            BareItem::String(_) => BareItemType::String,
+            BareItem::ByteSeq(_) => BareItemType::ByteSeq,
+            BareItem::Boolean(_) => BareItemType::Boolean,
+            BareItem::Token(_) => BareItemType::Token,
+        }
+    }
     /// If `BareItem` is a decimal, returns `Decimal`, otherwise returns `None`.
     /// ```
     /// # use sfv::{BareItem, Decimal, FromPrimitive};
@@ -332,6 +909,32 @@ impl BareItem {
             _ => None,
         }
     }
+    /// If `BareItem` is an integer or a decimal, returns `Number`, otherwise returns `None`.
+    /// ```
+    /// # use sfv::{BareItem, Number};
+    /// assert_eq!(BareItem::Integer(3).as_number(), Some(Number::Integer(3)));
+    /// assert_eq!(BareItem::Boolean(true).as_number(), None);
+    /// ```
+    pub fn as_number(&self) -> Option<Number> {
+        match *self {
+            BareItem::Decimal(val) => Some(Number::Decimal(val)),
+            BareItem::Integer(val) => Some(Number::Integer(val)),
+            _ => None,
+        }
+    }
+    /// If `BareItem` is a non-negative integer, returns it as `Duration` of whole seconds, otherwise returns `None`.
+    /// ```
+    /// # use sfv::BareItem;
+    /// # use std::time::Duration;
+    /// assert_eq!(BareItem::Integer(60).as_duration_secs(), Some(Duration::from_secs(60)));
+    /// assert_eq!(BareItem::Integer(-1).as_duration_secs(), None);
+    /// ```
+    pub fn as_duration_secs(&self) -> Option<Duration> {
+        match *self {
+            BareItem::Integer(val) if val >= 0 => Some(Duration::from_secs(val as u64)),
+            _ => None,
+        }
+    }
     /// If `BareItem` is `String`, returns `&str`, otherwise returns `None`.
     /// ```
     /// # use sfv::BareItem;
@@ -381,6 +984,48 @@ impl BareItem {
             _ => None,
         }
     }
+    /// If `BareItem` is `String`, returns owned `String`, otherwise returns the original `BareItem`.
+    /// ```
+    /// # use sfv::BareItem;
+    /// let bare_item = BareItem::String("foo".into());
+    /// assert_eq!(bare_item.into_string().unwrap(), "foo");
+    ///
+    /// let bare_item = BareItem::Integer(1);
+    /// assert_eq!(bare_item.into_string(), Err(BareItem::Integer(1)));
+    /// ```
+    pub fn into_string(self) -> Result<String, BareItem> {
+        match self {
+            BareItem::String(val) => Ok(val),
+            _ => Err(self),
+        }
+    }
+    /// If `BareItem` is a `ByteSeq`, returns owned `Vec<u8>`, otherwise returns the original `BareItem`.
+    /// ```
+    /// # use sfv::BareItem;
+    /// let bare_item = BareItem::ByteSeq("foo".to_owned().into_bytes());
+    /// assert_eq!(bare_item.into_byte_seq().unwrap(), "foo".as_bytes());
+    /// ```
+    pub fn into_byte_seq(self) -> Result<Vec<u8>, BareItem> {
+        match self {
+            BareItem::ByteSeq(val) => Ok(val),
+            _ => Err(self),
+        }
+    }
+    /// If `BareItem` is a `Token`, returns owned `String`, otherwise returns the original `BareItem`.
+    /// ```
+    /// # use sfv::BareItem;
+    /// let bare_item = BareItem::Token("*bar".into());
+    /// assert_eq!(bare_item.into_token().unwrap(), "*bar");
+    ///
+    /// let bare_item = BareItem::String("*bar".into());
+    /// assert!(bare_item.into_token().is_err());
+    /// ```
+    pub fn into_token(self) -> Result<String, BareItem> {
+        match self {
+            BareItem::Token(val) => Ok(val),
+            _ => Err(self),
+        }
+    }
 }
 
 impl From<i64> for BareItem {
@@ -408,14 +1053,189 @@ impl From<Decimal> for BareItem {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub(crate) enum Num {
+impl From<bool> for BareItem {
+    /// Converts `bool` into `BareItem::Boolean`.
+    /// ```
+    /// # use sfv::BareItem;
+    /// let bare_item: BareItem = true.into();
+    /// assert_eq!(bare_item.as_bool().unwrap(), true);
+    /// ```
+    fn from(item: bool) -> Self {
+        BareItem::Boolean(item)
+    }
+}
+
+macro_rules! impl_try_from_bare_item {
+    ($target:ty, $variant:ident, $error:literal) => {
+        impl TryFrom<BareItem> for $target {
+            type Error = &'static str;
+
+            fn try_from(bare_item: BareItem) -> Result<Self, Self::Error> {
+                match bare_item {
+                    BareItem::$variant(val) => Ok(val),
+                    _ => Err($error),
+                }
+            }
+        }
+
+        impl TryFrom<&BareItem> for $target {
+            type Error = &'static str;
+
+            fn try_from(bare_item: &BareItem) -> Result<Self, Self::Error> {
+                match bare_item {
+                    BareItem::$variant(val) => Ok(val.to_owned()),
+                    _ => Err($error),
+                }
+            }
+        }
+    };
+}
+
+// Conversions into native types, so that `?` can be used instead of `as_*` followed by `ok_or`
+impl_try_from_bare_item!(i64, Integer, "try_from: bare item is not an integer");
+impl_try_from_bare_item!(Decimal, Decimal, "try_from: bare item is not a decimal");
+impl_try_from_bare_item!(bool, Boolean, "try_from: bare item is not a boolean");
+impl_try_from_bare_item!(String, String, "try_from: bare item is not a string");
+impl_try_from_bare_item!(
+    Vec<u8>,
+    ByteSeq,
+    "try_from: bare item is not a byte sequence"
+);
+
+impl TryFrom<BareItem> for f64 {
+    type Error = &'static str;
+
+    /// Converts `BareItem::Decimal` into `f64`, otherwise returns an error naming the expected type.
+    /// ```
+    /// # use sfv::BareItem;
+    /// # use std::convert::TryInto;
+    /// let bare_item = BareItem::Decimal(sfv::Decimal::new(25, 1));
+    /// let value: f64 = bare_item.try_into().unwrap();
+    /// assert_eq!(value, 2.5);
+    ///
+    /// let n: Result<i64, _> = BareItem::Token("a".into()).try_into();
+    /// assert_eq!(n, Err("try_from: bare item is not an integer"));
+    /// ```
+    fn try_from(bare_item: BareItem) -> Result<Self, Self::Error> {
+        f64::try_from(&bare_item)
+    }
+}
+
+impl TryFrom<&BareItem> for f64 {
+    type Error = &'static str;
+
+    fn try_from(bare_item: &BareItem) -> Result<Self, Self::Error> {
+        match bare_item {
+            BareItem::Decimal(val) => val.to_f64().ok_or("try_from: decimal is out of f64 range"),
+            _ => Err("try_from: bare item is not a decimal"),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a BareItem> for &'a str {
+    type Error = &'static str;
+
+    /// Borrows the value of `BareItem::String`. Use `BareItem::as_token` for tokens.
+    fn try_from(bare_item: &'a BareItem) -> Result<Self, Self::Error> {
+        match bare_item {
+            BareItem::String(val) => Ok(val),
+            _ => Err("try_from: bare item is not a string"),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a BareItem> for &'a [u8] {
+    type Error = &'static str;
+
+    fn try_from(bare_item: &'a BareItem) -> Result<Self, Self::Error> {
+        match bare_item {
+            BareItem::ByteSeq(val) => Ok(val),
+            _ => Err("try_from: bare item is not a byte sequence"),
+        }
+    }
+}
+
+/// Numeric value of `Integer` or `Decimal` type, for fields that accept either.
+///
+/// Equality compares both type and value. Use `numeric_cmp` to compare values regardless of their type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Number {
+    /// Decimal number
     Decimal(Decimal),
+    /// Integer number
     Integer(i64),
 }
 
+impl Number {
+    /// Returns value as `Decimal`. Integers are converted exactly.
+    /// ```
+    /// # use sfv::{Decimal, FromStr, Number};
+    /// assert_eq!(Number::Integer(2).to_decimal(), Decimal::from(2));
+    /// assert_eq!(Number::Decimal(Decimal::from_str("2.5").unwrap()).to_decimal(), Decimal::from_str("2.5").unwrap());
+    /// ```
+    pub fn to_decimal(&self) -> Decimal {
+        match *self {
+            Number::Decimal(val) => val,
+            Number::Integer(val) => Decimal::from(val),
+        }
+    }
+
+    /// Returns value as `i64` if it has no fractional part, otherwise returns `None`.
+    /// ```
+    /// # use sfv::{Decimal, FromStr, Number};
+    /// assert_eq!(Number::Decimal(Decimal::from_str("3.0").unwrap()).to_int(), Some(3));
+    /// assert_eq!(Number::Decimal(Decimal::from_str("3.5").unwrap()).to_int(), None);
+    /// ```
+    pub fn to_int(&self) -> Option<i64> {
+        match *self {
+            Number::Decimal(val) if val.fract().is_zero() => val.to_i64(),
+            Number::Decimal(_) => None,
+            Number::Integer(val) => Some(val),
+        }
+    }
+
+    /// Compares numeric values, regardless of whether they are integers or decimals.
+    /// ```
+    /// # use sfv::{Decimal, FromStr, Number};
+    /// # use std::cmp::Ordering;
+    /// let one_and_half = Number::Decimal(Decimal::from_str("1.5").unwrap());
+    /// assert_eq!(Number::Integer(1).numeric_cmp(&one_and_half), Ordering::Less);
+    /// assert_eq!(Number::Integer(1).numeric_cmp(&Number::Decimal(Decimal::from(1))), Ordering::Equal);
+    /// ```
+    pub fn numeric_cmp(&self, other: &Number) -> Ordering {
+        match (self, other) {
+            (Number::Integer(val), Number::Integer(other)) => val.cmp(other),
+            _ => self.to_decimal().cmp(&other.to_decimal()),
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(val: i64) -> Self {
+        Number::Integer(val)
+    }
+}
+
+impl From<Decimal> for Number {
+    fn from(val: Decimal) -> Self {
+        Number::Decimal(val)
+    }
+}
+
+impl From<Number> for BareItem {
+    /// Converts `Number` into `BareItem::Integer` or `BareItem::Decimal`.
+    fn from(number: Number) -> Self {
+        match number {
+            Number::Decimal(val) => BareItem::Decimal(val),
+            Number::Integer(val) => BareItem::Integer(val),
+        }
+    }
+}
+
 /// Similar to `BareItem`, but used to serialize values via `RefItemSerializer`, `RefListSerializer`, `RefDictSerializer`.
+/// Like `BareItem`, it is non-exhaustive.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum RefBareItem<'a> {
     Integer(i64),
     Decimal(Decimal),
@@ -425,9 +1245,27 @@ pub enum RefBareItem<'a> {
     Token(&'a str),
 }
 
+impl<'a> RefBareItem<'a> {
+    /// Returns type of `RefBareItem` value.
+    /// ```
+    /// # use sfv::{RefBareItem, BareItemType};
+    /// assert_eq!(RefBareItem::Integer(1).item_type(), BareItemType::Integer);
+    /// ```
+    pub fn item_type(&self) -> BareItemType {
+        match self {
+            RefBareItem::Decimal(_) => BareItemType::Decimal,
+            RefBareItem::Integer(_) => BareItemType::Integer,
+            RefBareItem::String(_) => BareItemType::String,
+            RefBareItem::ByteSeq(_) => BareItemType::ByteSeq,
+            RefBareItem::Boolean(_) => BareItemType::Boolean,
+            RefBareItem::Token(_) => BareItemType::Token,
+        }
+    }
+}
+
 impl BareItem {
     /// Converts `BareItem` into `RefBareItem`.
-    fn to_ref_bare_item(&self) -> RefBareItem {
+    fn to_ref_bare_item(&self) -> RefBareItem<'_> {
         match self {
             BareItem::Integer(val) => RefBareItem::Integer(*val),
             BareItem::Decimal(val) => RefBareItem::Decimal(*val),
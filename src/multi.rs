@@ -0,0 +1,254 @@
+/*!
+Parsing that preserves every occurrence of duplicate keys.
+
+`Parser` follows the specification and keeps only the last value of a
+duplicated dictionary or parameter key. Linters and security tooling often
+need to see exactly what was sent instead, so the types in this module store
+members as ordered key-value pairs and keep duplicates as they appear on the wire.
+
+```
+use sfv::multi::{self, MultiListEntry};
+use sfv::BareItem;
+
+let dict = multi::parse_dictionary("a=1, b;q=1;q=2, a=3".as_bytes()).unwrap();
+let keys: Vec<&str> = dict.iter().map(|(key, _)| key.as_str()).collect();
+assert_eq!(keys, vec!["a", "b", "a"]);
+
+if let MultiListEntry::Item(item) = &dict[1].1 {
+    assert_eq!(item.params[0], ("q".to_owned(), BareItem::Integer(1)));
+    assert_eq!(item.params[1], ("q".to_owned(), BareItem::Integer(2)));
+}
+```
+*/
+
+use crate::{BareItem, InnerList, Item, ListEntry, Parameters, ParseOptions, Parser, SFVResult};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Parameters of `MultiItem` or `MultiInnerList`, in wire order and including duplicate keys.
+pub type MultiParameters = Vec<(String, BareItem)>;
+
+/// Dictionary members in wire order, including duplicate keys.
+pub type MultiDictionary = Vec<(String, MultiListEntry)>;
+
+/// List members, each of which keeps duplicate parameter keys.
+pub type MultiList = Vec<MultiListEntry>;
+
+/// `Item` that keeps duplicate parameter keys.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiItem {
+    /// Value of `MultiItem`.
+    pub bare_item: BareItem,
+    /// `MultiItem`'s associated parameters. Can be empty.
+    pub params: MultiParameters,
+}
+
+/// `InnerList` that keeps duplicate parameter keys.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiInnerList {
+    /// `MultiItems` that `MultiInnerList` contains. Can be empty.
+    pub items: Vec<MultiItem>,
+    /// `MultiInnerList`'s associated parameters. Can be empty.
+    pub params: MultiParameters,
+}
+
+/// Member of `MultiList` or `MultiDictionary`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MultiListEntry {
+    /// Member of `MultiItem` type.
+    Item(MultiItem),
+    /// Member of `MultiInnerList` type.
+    InnerList(MultiInnerList),
+}
+
+impl From<MultiItem> for Item {
+    /// Converts into `Item`, keeping the last value of duplicate parameter keys as `Parser` does.
+    fn from(item: MultiItem) -> Self {
+        Item::with_params(item.bare_item, to_parameters(item.params))
+    }
+}
+
+impl From<MultiInnerList> for InnerList {
+    /// Converts into `InnerList`, keeping the last value of duplicate parameter keys as `Parser` does.
+    fn from(inner_list: MultiInnerList) -> Self {
+        InnerList::with_params(
+            inner_list.items.into_iter().map(Item::from).collect(),
+            to_parameters(inner_list.params),
+        )
+    }
+}
+
+impl From<MultiListEntry> for ListEntry {
+    /// Converts into `ListEntry`, keeping the last value of duplicate parameter keys as `Parser` does.
+    fn from(entry: MultiListEntry) -> Self {
+        match entry {
+            MultiListEntry::Item(item) => ListEntry::Item(item.into()),
+            MultiListEntry::InnerList(inner_list) => ListEntry::InnerList(inner_list.into()),
+        }
+    }
+}
+
+/// Parses input into `MultiItem`, keeping duplicate parameter keys.
+pub fn parse_item(input_bytes: &[u8]) -> SFVResult<MultiItem> {
+    Parser::parse_with(input_bytes, parse_multi_item)
+}
+
+/// Parses input into `MultiList`, keeping duplicate parameter keys.
+pub fn parse_list(input_bytes: &[u8]) -> SFVResult<MultiList> {
+    Parser::parse_with(input_bytes, |input_chars| {
+        let mut members = MultiList::new();
+        Parser::parse_list_with(input_chars, &ParseOptions::default(), |input_chars| {
+            members.push(parse_multi_list_entry(input_chars)?);
+            Ok(())
+        })?;
+        Ok(members)
+    })
+}
+
+/// Parses input into `MultiDictionary`, keeping duplicate member and parameter keys.
+///
+/// Collecting the members into `Dictionary` gives the same result as `Parser::parse_dictionary`:
+/// ```
+/// # use sfv::multi;
+/// # use sfv::{Dictionary, ListEntry, Parser};
+/// let input = "a=1;x;x=2, b, a=3";
+/// let dict: Dictionary = multi::parse_dictionary(input.as_bytes())
+///     .unwrap()
+///     .into_iter()
+///     .map(|(key, member)| (key, ListEntry::from(member)))
+///     .collect();
+/// assert_eq!(dict, Parser::parse_dictionary(input.as_bytes()).unwrap());
+/// ```
+pub fn parse_dictionary(input_bytes: &[u8]) -> SFVResult<MultiDictionary> {
+    Parser::parse_with(input_bytes, |input_chars| {
+        let mut dict = MultiDictionary::new();
+        Parser::parse_dict_with(
+            input_chars,
+            &ParseOptions::default(),
+            |input_chars, this_key| {
+                let member = if let Some('=') = input_chars.peek() {
+                    input_chars.next();
+                    parse_multi_list_entry(input_chars)?
+                } else {
+                    MultiListEntry::Item(MultiItem {
+                        bare_item: BareItem::Boolean(true),
+                        params: parse_multi_parameters(input_chars)?,
+                    })
+                };
+                dict.push((this_key, member));
+                Ok(())
+            },
+        )?;
+        Ok(dict)
+    })
+}
+
+fn parse_multi_list_entry(input_chars: &mut Peekable<Chars>) -> SFVResult<MultiListEntry> {
+    match input_chars.peek() {
+        Some('(') => Ok(MultiListEntry::InnerList(parse_multi_inner_list(
+            input_chars,
+        )?)),
+        _ => Ok(MultiListEntry::Item(parse_multi_item(input_chars)?)),
+    }
+}
+
+fn parse_multi_item(input_chars: &mut Peekable<Chars>) -> SFVResult<MultiItem> {
+    let bare_item = Parser::parse_bare_item(input_chars, &ParseOptions::default())?;
+    let params = parse_multi_parameters(input_chars)?;
+    Ok(MultiItem { bare_item, params })
+}
+
+fn parse_multi_inner_list(input_chars: &mut Peekable<Chars>) -> SFVResult<MultiInnerList> {
+    let mut items = Vec::new();
+    Parser::parse_inner_list_with(input_chars, &ParseOptions::default(), |input_chars| {
+        items.push(parse_multi_item(input_chars)?);
+        Ok(())
+    })?;
+    let params = parse_multi_parameters(input_chars)?;
+    Ok(MultiInnerList { items, params })
+}
+
+fn parse_multi_parameters(input_chars: &mut Peekable<Chars>) -> SFVResult<MultiParameters> {
+    let mut params = MultiParameters::new();
+    Parser::parse_parameters_with(
+        input_chars,
+        &ParseOptions::default(),
+        |param_name, param_value| {
+            params.push((param_name, param_value));
+            Ok(())
+        },
+    )?;
+    Ok(params)
+}
+
+fn to_parameters(params: MultiParameters) -> Parameters {
+    params.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_duplicates_in_order() -> Result<(), &'static str> {
+        let list = parse_list("(1;a 2);a=1;a=?0, x;b;b".as_bytes())?;
+        let expected = vec![
+            MultiListEntry::InnerList(MultiInnerList {
+                items: vec![
+                    MultiItem {
+                        bare_item: BareItem::Integer(1),
+                        params: vec![("a".into(), BareItem::Boolean(true))],
+                    },
+                    MultiItem {
+                        bare_item: BareItem::Integer(2),
+                        params: vec![],
+                    },
+                ],
+                params: vec![
+                    ("a".into(), BareItem::Integer(1)),
+                    ("a".into(), BareItem::Boolean(false)),
+                ],
+            }),
+            MultiListEntry::Item(MultiItem {
+                bare_item: BareItem::Token("x".into()),
+                params: vec![
+                    ("b".into(), BareItem::Boolean(true)),
+                    ("b".into(), BareItem::Boolean(true)),
+                ],
+            }),
+        ];
+        assert_eq!(expected, list);
+        Ok(())
+    }
+
+    #[test]
+    fn conversion_matches_parser() -> Result<(), &'static str> {
+        let input = "1;a=2;b;a=3";
+        let item: Item = parse_item(input.as_bytes())?.into();
+        assert_eq!(Parser::parse_item(input.as_bytes())?, item);
+
+        let input = "(a b);x=1;x=2, c;y";
+        let list: Vec<ListEntry> = parse_list(input.as_bytes())?
+            .into_iter()
+            .map(ListEntry::from)
+            .collect();
+        assert_eq!(Parser::parse_list(input.as_bytes())?, list);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_same_errors_as_parser() {
+        for input in &["a=1,", "a=1 b", "a=(1", "a=(1,2)", "A=1", "a;X"] {
+            assert_eq!(
+                Parser::parse_dictionary(input.as_bytes()).unwrap_err(),
+                parse_dictionary(input.as_bytes()).unwrap_err()
+            );
+        }
+        for input in &["1,", "1 2", "(1"] {
+            assert_eq!(
+                Parser::parse_list(input.as_bytes()).unwrap_err(),
+                parse_list(input.as_bytes()).unwrap_err()
+            );
+        }
+    }
+}
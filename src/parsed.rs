@@ -0,0 +1,316 @@
+use crate::serializer::Serializer;
+use crate::{chars, Dictionary, ListEntry, SFVResult};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::ops::{Deref, Range};
+use std::str::from_utf8;
+
+/// Parsed structured field value together with the raw input it was parsed from.
+///
+/// Returned by `Parser::parse_*_with_raw` methods. Keeps the original bytes so that
+/// the field can be forwarded, hashed or reported exactly as it was received.
+/// # Examples
+/// ```
+/// # use sfv::Parser;
+/// let parsed = Parser::parse_dictionary_with_raw("a=1,   b=(x \"y, z\")".as_bytes()).unwrap();
+/// assert_eq!(parsed.len(), 2);
+/// assert_eq!(parsed.raw_member(1), Some("b=(x \"y, z\")".as_bytes()));
+/// assert_eq!(parsed.member_spans(), &[0..3, 7..19]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed<T> {
+    value: T,
+    raw: Vec<u8>,
+    member_spans: Vec<Range<usize>>,
+}
+
+impl<T> Parsed<T> {
+    // `raw` must be a valid structured field value, as its members are located by splitting it on top-level commas.
+    pub(crate) fn new(value: T, raw: &[u8]) -> Parsed<T> {
+        Parsed {
+            value,
+            raw: raw.to_vec(),
+            member_spans: member_spans(raw),
+        }
+    }
+
+    /// Returns parsed value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes `Parsed`, returning parsed value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns input exactly as it was parsed.
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Returns byte ranges of top-level members in the raw input, excluding surrounding whitespace.
+    /// `Item` has a single member. Dictionary members with duplicate keys each have their own span,
+    /// so there may be more spans than members of the parsed `Dictionary`.
+    pub fn member_spans(&self) -> &[Range<usize>] {
+        &self.member_spans
+    }
+
+    /// Returns raw bytes of the top-level member at `idx` in the input.
+    pub fn raw_member(&self, idx: usize) -> Option<&[u8]> {
+        self.member_spans
+            .get(idx)
+            .map(|span| &self.raw[span.clone()])
+    }
+}
+
+impl Parsed<Dictionary> {
+    /// Returns `DictionarySplice` editing members of the raw input.
+    /// # Examples
+    /// ```
+    /// # use sfv::{BareItem, Item, Parser};
+    /// let parsed = Parser::parse_dictionary_with_raw("a=1,b=(x  y);p,  c=?0 ".as_bytes()).unwrap();
+    /// let value = parsed
+    ///     .splice()
+    ///     .insert("b", Item::new(BareItem::Integer(2)).into())
+    ///     .remove("c")
+    ///     .insert("d", Item::new(BareItem::Boolean(true)).into())
+    ///     .finish()
+    ///     .unwrap();
+    /// assert_eq!(value, "a=1,b=2, d ".as_bytes());
+    /// ```
+    pub fn splice(&self) -> DictionarySplice<'_> {
+        DictionarySplice {
+            parsed: self,
+            edits: IndexMap::new(),
+        }
+    }
+}
+
+/// Edits of `Parsed<Dictionary>` applied to its raw input, so that untouched members, separators
+/// and surrounding whitespace are copied verbatim. Edited members are serialized anew.
+#[derive(Debug, Clone)]
+pub struct DictionarySplice<'a> {
+    parsed: &'a Parsed<Dictionary>,
+    // New value of each edited member, `None` if it's removed
+    edits: IndexMap<String, Option<ListEntry>>,
+}
+
+impl DictionarySplice<'_> {
+    /// Replaces member `key`, keeping its position, or appends it if there's no such member.
+    /// Members with duplicate keys in the raw input are replaced at the position of the first one.
+    /// The key and value are validated by `finish`.
+    pub fn insert(mut self, key: &str, entry: ListEntry) -> Self {
+        self.edits.insert(key.to_owned(), Some(entry));
+        self
+    }
+
+    /// Removes member `key`, including members with duplicate keys in the raw input.
+    pub fn remove(mut self, key: &str) -> Self {
+        self.edits.insert(key.to_owned(), None);
+        self
+    }
+
+    /// Returns the edited field value. It's empty if every member was removed, in which case the field should be omitted.
+    pub fn finish(mut self) -> SFVResult<Vec<u8>> {
+        let raw = &self.parsed.raw;
+        let spans = &self.parsed.member_spans;
+
+        let mut members = Vec::new();
+        for (idx, span) in spans.iter().enumerate() {
+            let member = &raw[span.clone()];
+            let member = match self.edits.get_mut(member_key(member)) {
+                None => Cow::Borrowed(member),
+                // Taken, so that later members with the same key are removed
+                Some(edit) => match edit.take() {
+                    Some(entry) => Cow::Owned(serialize_member(member_key(member), &entry)?),
+                    None => continue,
+                },
+            };
+            // Members keep the separator that preceded them, whitespace included
+            let separator = match idx {
+                0 => &b", "[..],
+                _ => &raw[spans[idx - 1].end..span.start],
+            };
+            members.push((separator, member));
+        }
+        for (key, edit) in &self.edits {
+            if let Some(entry) = edit {
+                members.push((&b", "[..], Cow::Owned(serialize_member(key, entry)?)));
+            }
+        }
+
+        let mut output = Vec::with_capacity(raw.len());
+        if members.is_empty() {
+            return Ok(output);
+        }
+        let (start, end) = match (spans.first(), spans.last()) {
+            (Some(first), Some(last)) => (first.start, last.end),
+            _ => (0, raw.len()),
+        };
+        output.extend_from_slice(&raw[..start]);
+        for (idx, (separator, member)) in members.iter().enumerate() {
+            if idx > 0 {
+                output.extend_from_slice(separator);
+            }
+            output.extend_from_slice(member);
+        }
+        output.extend_from_slice(&raw[end..]);
+        Ok(output)
+    }
+}
+
+// Key of raw Dictionary member, which is ASCII as it was parsed
+fn member_key(member: &[u8]) -> &str {
+    let len = member
+        .iter()
+        .position(|&byte| !chars::is_allowed_key_char(byte as char))
+        .unwrap_or(member.len());
+    from_utf8(&member[..len]).unwrap_or_default()
+}
+
+fn serialize_member(key: &str, entry: &ListEntry) -> SFVResult<Vec<u8>> {
+    let mut output = String::new();
+    Serializer::serialize_dict_member(key, entry, &mut output)?;
+    Ok(output.into_bytes())
+}
+
+impl<T> Deref for Parsed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+pub(crate) fn member_spans(raw: &[u8]) -> Vec<Range<usize>> {
+    // Outside of strings, commas only separate top-level members.
+    let mut spans = vec![];
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, byte) in raw.iter().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            b',' if !in_string => {
+                spans.push(trim(raw, start..idx, spans.is_empty()));
+                start = idx + 1;
+            }
+            _ => (),
+        }
+    }
+
+    let last = trim(raw, start..raw.len(), spans.is_empty());
+    if !last.is_empty() || !spans.is_empty() {
+        spans.push(last);
+    }
+    spans
+}
+
+// Trims whitespace the parser skips around a member: only SP at the start of input, OWS elsewhere
+fn trim(raw: &[u8], mut span: Range<usize>, is_first: bool) -> Range<usize> {
+    let is_skipped = |byte: u8| byte == b' ' || (!is_first && byte == b'\t');
+    while span.start < span.end && is_skipped(raw[span.start]) {
+        span.start += 1;
+    }
+    while span.start < span.end && matches!(raw[span.end - 1], b' ' | b'\t') {
+        span.end -= 1;
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BareItem, Item, ListEntry, Parser, SFVResult};
+
+    #[test]
+    fn spans_of_list_members() -> SFVResult<()> {
+        let input = "  \"a,\\\"b\", (1 2);q=\",\" ,\t:YQ==:  ";
+        let parsed = Parser::parse_list_with_raw(input.as_bytes())?;
+        assert_eq!(input.as_bytes(), parsed.raw());
+        assert_eq!(3, parsed.value().len());
+        assert_eq!(&[2..9, 11..22, 25..31], parsed.member_spans());
+        assert_eq!(Some(":YQ==:".as_bytes()), parsed.raw_member(2));
+        assert_eq!(None, parsed.raw_member(3));
+        Ok(())
+    }
+
+    #[test]
+    fn spans_of_item_and_empty_list() -> SFVResult<()> {
+        let parsed = Parser::parse_item_with_raw(" 1;a=2 ".as_bytes())?;
+        assert_eq!(1, parsed.member_spans().len());
+        assert_eq!(Some("1;a=2".as_bytes()), parsed.raw_member(0));
+
+        let parsed = Parser::parse_list_with_raw("".as_bytes())?;
+        assert!(parsed.member_spans().is_empty());
+        assert!(parsed.into_value().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn spans_of_duplicate_dictionary_keys() -> SFVResult<()> {
+        let parsed = Parser::parse_dictionary_with_raw("a=1, a=2".as_bytes())?;
+        assert_eq!(1, parsed.len());
+        assert_eq!(&[0..3, 5..8], parsed.member_spans());
+        Ok(())
+    }
+
+    #[test]
+    fn splice_dictionary_members() -> SFVResult<()> {
+        let input = "  a=1;p,\tb=(\"x\"   y),c ,d=:AQI=:\t";
+        let parsed = Parser::parse_dictionary_with_raw(input.as_bytes())?;
+        assert_eq!(input.as_bytes(), &parsed.splice().finish()?[..]);
+
+        let entry: ListEntry = Item::new(BareItem::Integer(2)).into();
+        let value = parsed
+            .splice()
+            .remove("a")
+            .insert("c", entry.clone())
+            .insert("e", entry)
+            .remove("x")
+            .finish()?;
+        assert_eq!(
+            "  b=(\"x\"   y),c=2 ,d=:AQI=:, e=2\t".as_bytes(),
+            &value[..]
+        );
+        assert_eq!(
+            Parser::parse_dictionary("b=(\"x\" y), c=2, d=:AQI=:, e=2".as_bytes())?,
+            Parser::parse_dictionary(&value)?
+        );
+
+        let value = ["a", "b", "c", "d"]
+            .iter()
+            .fold(parsed.splice(), |splice, key| splice.remove(key))
+            .finish()?;
+        assert!(value.is_empty());
+
+        let parsed = Parser::parse_dictionary_with_raw("".as_bytes())?;
+        let value = parsed
+            .splice()
+            .insert("a", Item::new(BareItem::Boolean(true)).into())
+            .finish()?;
+        assert_eq!("a".as_bytes(), &value[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn splice_duplicate_and_invalid_keys() -> SFVResult<()> {
+        let parsed = Parser::parse_dictionary_with_raw("a=1, b, a=2".as_bytes())?;
+        let entry: ListEntry = Item::new(BareItem::Integer(3)).into();
+        let value = parsed.splice().insert("a", entry.clone()).finish()?;
+        assert_eq!("a=3, b".as_bytes(), &value[..]);
+        let value = parsed.splice().remove("a").finish()?;
+        assert_eq!("b".as_bytes(), &value[..]);
+
+        let result = parsed.splice().insert("A", entry).finish();
+        assert_eq!(Err("serialize_key: disallowed character in input"), result);
+        let result = parsed
+            .splice()
+            .insert("b", Item::new(BareItem::Integer(i64::MAX)).into())
+            .finish();
+        assert!(result.is_err());
+        Ok(())
+    }
+}
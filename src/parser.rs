@@ -1,10 +1,17 @@
+use crate::chars;
 use crate::utils;
 use crate::{
-    BareItem, Decimal, Dictionary, FromStr, InnerList, Item, List, ListEntry, Num, Parameters,
-    SFVResult,
+    BareItem, Decimal, Dictionary, FromStr, InnerList, Item, List, ListEntry, Number, Parameters,
+    Parsed, SFVResult,
 };
+use data_encoding::BASE64;
+use indexmap::IndexMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::io::{self, Read};
 use std::iter::Peekable;
 use std::str::{from_utf8, Chars};
+use std::sync::Arc;
 
 /// Implements parsing logic for each structured field value type.
 pub trait ParseValue {
@@ -35,103 +42,417 @@ pub trait ParseMore {
 
 impl ParseValue for Item {
     fn parse(input_chars: &mut Peekable<Chars>) -> SFVResult<Item> {
-        // https://httpwg.org/specs/rfc8941.html#parse-item
-        let bare_item = Parser::parse_bare_item(input_chars)?;
-        let params = Parser::parse_parameters(input_chars)?;
-
-        Ok(Item { bare_item, params })
+        Parser::parse_item_value(input_chars, &ParseOptions::default())
     }
 }
 
 impl ParseValue for List {
     fn parse(input_chars: &mut Peekable<Chars>) -> SFVResult<List> {
-        // https://httpwg.org/specs/rfc8941.html#parse-list
-        // List represents an array of (item_or_inner_list, parameters)
+        Parser::parse_list_members(input_chars, List::new(), &ParseOptions::default())
+    }
+}
 
-        let mut members = vec![];
+impl ParseValue for Dictionary {
+    fn parse(input_chars: &mut Peekable<Chars>) -> SFVResult<Dictionary> {
+        Parser::parse_dict_members(
+            input_chars,
+            Dictionary::new(),
+            &ParseOptions::default(),
+            None,
+        )
+    }
+}
 
-        while input_chars.peek().is_some() {
-            members.push(Parser::parse_list_entry(input_chars)?);
+impl ParseMore for List {
+    fn parse_more(&mut self, input_bytes: &[u8]) -> SFVResult<()> {
+        let parsed_list = Parser::parse_list(input_bytes)?;
+        self.extend(parsed_list);
+        Ok(())
+    }
+}
 
-            utils::consume_ows_chars(input_chars);
+impl ParseMore for Dictionary {
+    fn parse_more(&mut self, input_bytes: &[u8]) -> SFVResult<()> {
+        let parsed_dict = Parser::parse_dictionary(input_bytes)?;
+        self.extend(parsed_dict);
+        Ok(())
+    }
+}
 
-            if input_chars.peek().is_none() {
-                return Ok(members);
-            }
+/// Unescapes sf-string wire form, including the surrounding double quotes, into its value.
+/// Returns an error if `input` is not exactly one valid sf-string.
+/// # Examples
+/// ```
+/// # use sfv::unescape_string;
+/// assert_eq!(unescape_string(r#""say \"hi\"""#).unwrap(), r#"say "hi""#);
+/// assert!(unescape_string(r#""unterminated"#).is_err());
+/// ```
+pub fn unescape_string(input: &str) -> SFVResult<String> {
+    let mut input_chars = input.chars().peekable();
+    let output = Parser::parse_string(&mut input_chars, &ParseOptions::default())?;
+
+    if input_chars.next().is_some() {
+        return Err("unescape_string: trailing characters after closing '\"'");
+    }
+    Ok(output)
+}
 
-            if let Some(c) = input_chars.next() {
-                if c != ',' {
-                    return Err("parse_list: trailing characters after list member");
+type Normalizer = Arc<dyn Fn(&mut BareItem) + Send + Sync>;
+
+/// How parsing handles a dictionary member or parameter whose key already appeared in the same dictionary or parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// The later value replaces the earlier one, keeping its position, as the specification requires.
+    #[default]
+    Overwrite,
+    /// The earlier value is kept and later ones are ignored.
+    KeepFirst,
+    /// Parsing fails.
+    Reject,
+}
+
+impl DuplicateKeys {
+    // Inserts `value` into `map` as the policy specifies, returning `error` if it rejects a duplicate key
+    pub(crate) fn insert<K: Hash + Eq, V, S: BuildHasher>(
+        self,
+        map: &mut IndexMap<K, V, S>,
+        key: K,
+        value: V,
+        error: &'static str,
+    ) -> SFVResult<()> {
+        match map.entry(key) {
+            indexmap::map::Entry::Occupied(mut entry) => match self {
+                DuplicateKeys::Overwrite => {
+                    entry.insert(value);
                 }
+                DuplicateKeys::KeepFirst => {}
+                DuplicateKeys::Reject => return Err(error),
+            },
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(value);
             }
+        }
+        Ok(())
+    }
+}
 
-            utils::consume_ows_chars(input_chars);
+/// Upper bounds on the size of parsed values, set with `ParseOptions::limits`,
+/// so that parsing stops early on values too large for the application.
+/// Each limit is unset by default.
+/// # Examples
+/// ```
+/// # use sfv::{ParseLimits, ParseOptions, Parser};
+/// let options = ParseOptions::new().limits(ParseLimits::new().max_list_members(2));
+/// assert!(Parser::parse_list_with_options("a, (b c)".as_bytes(), &options).is_ok());
+/// assert_eq!(
+///     Parser::parse_list_with_options("a, b, c".as_bytes(), &options),
+///     Err("parse_list: too many members")
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseLimits {
+    pub(crate) max_list_members: Option<usize>,
+    pub(crate) max_dictionary_members: Option<usize>,
+    pub(crate) max_parameters: Option<usize>,
+    pub(crate) max_string_len: Option<usize>,
+    max_byte_seq_len: Option<usize>,
+}
 
-            if input_chars.peek().is_none() {
-                return Err("parse_list: trailing comma");
-            }
-        }
+impl ParseLimits {
+    /// Returns `ParseLimits` without any limit set.
+    pub fn new() -> ParseLimits {
+        ParseLimits::default()
+    }
 
-        Ok(members)
+    /// Sets the maximum number of members of a list, and of items of each inner list.
+    /// Exceeding it fails with `parse_list: too many members` or `parse_inner_list: too many items`.
+    pub fn max_list_members(mut self, max: usize) -> ParseLimits {
+        self.max_list_members = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of dictionary members, counting members with duplicate keys.
+    /// Exceeding it fails with `parse_dict: too many members`.
+    pub fn max_dictionary_members(mut self, max: usize) -> ParseLimits {
+        self.max_dictionary_members = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of parameters of each item, inner list or dictionary member,
+    /// counting parameters with duplicate keys.
+    /// Exceeding it fails with `parse_parameters: too many parameters`.
+    pub fn max_parameters(mut self, max: usize) -> ParseLimits {
+        self.max_parameters = Some(max);
+        self
+    }
+
+    /// Sets the maximum length in bytes of each unescaped string. Parsing stops as soon as a string exceeds it.
+    /// Exceeding it fails with `parse_string: string is too long`.
+    pub fn max_string_len(mut self, max: usize) -> ParseLimits {
+        self.max_string_len = Some(max);
+        self
+    }
+
+    /// Sets the maximum length in bytes of each decoded byte sequence. It's checked before decoding.
+    /// Exceeding it fails with `parse_byte_seq: byte sequence is too long`.
+    pub fn max_byte_seq_len(mut self, max: usize) -> ParseLimits {
+        self.max_byte_seq_len = Some(max);
+        self
+    }
+
+    // Returns `error` if `len` exceeds `limit`
+    pub(crate) fn check(limit: Option<usize>, len: usize, error: &'static str) -> SFVResult<()> {
+        match limit {
+            Some(max) if len > max => Err(error),
+            _ => Ok(()),
+        }
     }
 }
 
-impl ParseValue for Dictionary {
-    fn parse(input_chars: &mut Peekable<Chars>) -> SFVResult<Dictionary> {
-        let mut dict = Dictionary::new();
+/// Options for parsing input with `Parser::parse_*_with_options` methods.
+/// Default options parse input as `Parser::parse_*` methods do.
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    normalizers: Vec<Normalizer>,
+    strict_base64: bool,
+    pub(crate) reject_parameters: bool,
+    terminators: Vec<u8>,
+    pub(crate) duplicate_dictionary_keys: DuplicateKeys,
+    pub(crate) duplicate_parameter_keys: DuplicateKeys,
+    pub(crate) limits: ParseLimits,
+    max_input_len: Option<usize>,
+}
 
-        while input_chars.peek().is_some() {
-            let this_key = Parser::parse_key(input_chars)?;
+impl ParseOptions {
+    /// Returns default `ParseOptions`.
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
 
-            if let Some('=') = input_chars.peek() {
-                input_chars.next();
-                let member = Parser::parse_list_entry(input_chars)?;
-                dict.insert(this_key, member);
-            } else {
-                let value = true;
-                let params = Parser::parse_parameters(input_chars)?;
-                let member = Item {
-                    bare_item: BareItem::Boolean(value),
-                    params,
-                };
-                dict.insert(this_key, member.into());
-            }
+    /// Registers a hook transforming every parsed `BareItem`, both item values and parameter values.
+    /// Hooks run in registration order, right after each bare item is parsed.
+    /// Dictionary members and parameters without a value are `BareItem::Boolean(true)` and are not passed to the hooks.
+    /// # Examples
+    /// ```
+    /// # use sfv::{BareItem, ParseOptions, Parser, SerializeValue};
+    /// let options = ParseOptions::new()
+    ///     .normalize_with(|bare_item| {
+    ///         if let BareItem::Token(token) = bare_item {
+    ///             token.make_ascii_lowercase();
+    ///         }
+    ///     })
+    ///     .normalize_with(|bare_item| {
+    ///         if let BareItem::Integer(value) = bare_item {
+    ///             *value = (*value).min(7);
+    ///         }
+    ///     });
+    /// let dict = Parser::parse_dictionary_with_options("a=GZIP, u=9;q=Br".as_bytes(), &options).unwrap();
+    /// assert_eq!(dict.serialize_value().unwrap(), "a=gzip, u=7;q=br");
+    /// ```
+    pub fn normalize_with<F>(mut self, normalizer: F) -> ParseOptions
+    where
+        F: Fn(&mut BareItem) + Send + Sync + 'static,
+    {
+        self.normalizers.push(Arc::new(normalizer));
+        self
+    }
 
-            utils::consume_ows_chars(input_chars);
+    /// Sets whether byte sequences must be canonical base64, i.e. padded with `=` and without non-zero trailing bits.
+    /// By default, as the specification recommends, missing padding and trailing bits are accepted.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseOptions, Parser};
+    /// let options = ParseOptions::new().strict_base64(true);
+    /// assert!(Parser::parse_item_with_options(":aGk=:".as_bytes(), &options).is_ok());
+    /// assert!(Parser::parse_item_with_options(":aGk:".as_bytes(), &options).is_err());
+    /// assert!(Parser::parse_item_with_options(":aGl=:".as_bytes(), &options).is_err());
+    /// ```
+    pub fn strict_base64(mut self, strict: bool) -> ParseOptions {
+        self.strict_base64 = strict;
+        self
+    }
 
-            if input_chars.peek().is_none() {
-                return Ok(dict);
-            }
+    /// Sets whether parameters are rejected, for fields whose definition doesn't allow them.
+    /// When set, parsing fails on the first parameter of any item, inner list or dictionary member.
+    /// As errors are static strings, the error doesn't name the rejected parameter.
+    /// To report it, parse without this option and look for the first non-empty parameters instead.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseOptions, Parser};
+    /// let options = ParseOptions::new().reject_parameters(true);
+    /// assert!(Parser::parse_list_with_options("a, (b c)".as_bytes(), &options).is_ok());
+    /// assert_eq!(
+    ///     Parser::parse_list_with_options("a, (b c);q=1".as_bytes(), &options),
+    ///     Err("parse_parameters: parameters are not allowed")
+    /// );
+    /// ```
+    pub fn reject_parameters(mut self, reject: bool) -> ParseOptions {
+        self.reject_parameters = reject;
+        self
+    }
 
-            if let Some(c) = input_chars.next() {
-                if c != ',' {
-                    return Err("parse_dict: trailing characters after dictionary member");
-                }
-            }
+    /// Sets bytes at which `Parser::parse_*_prefix` methods stop, for values embedded in a larger grammar.
+    /// A terminator is recognized wherever the value may end: after a list or dictionary member, after an item,
+    /// and inside a token, which otherwise may contain characters such as `|`, `/` or `:`.
+    /// Terminators within strings and byte sequences are parsed as part of those.
+    /// `Parser::parse_*_with_options` methods report input after a terminator as trailing characters.
+    /// `;` and `=` always start a parameter or a member value, so they can't end a value,
+    /// and parsing with either of them among the terminators fails with `parse: ';' and '=' can't be terminators`.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseOptions, Parser};
+    /// let options = ParseOptions::new().terminators(b"|");
+    /// let input = "a=1, b=tok|meta".as_bytes();
+    /// let (dict, consumed) = Parser::parse_dictionary_prefix(input, &options).unwrap();
+    /// assert_eq!(dict.len(), 2);
+    /// assert_eq!(&input[consumed..], "|meta".as_bytes());
+    /// ```
+    pub fn terminators(mut self, terminators: &[u8]) -> ParseOptions {
+        self.terminators = terminators.to_vec();
+        self
+    }
 
-            utils::consume_ows_chars(input_chars);
+    /// Sets how dictionary members with a key that already appeared in the same dictionary are handled.
+    /// By default, as the specification requires, the later value replaces the earlier one.
+    /// # Examples
+    /// ```
+    /// # use sfv::{DuplicateKeys, ParseOptions, Parser, SerializeValue};
+    /// let input = "a=1, b, a=2".as_bytes();
+    /// let options = ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::KeepFirst);
+    /// let dict = Parser::parse_dictionary_with_options(input, &options).unwrap();
+    /// assert_eq!(dict.serialize_value().unwrap(), "a=1, b");
+    ///
+    /// let options = ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::Reject);
+    /// assert_eq!(
+    ///     Parser::parse_dictionary_with_options(input, &options),
+    ///     Err("parse_dict: duplicate key")
+    /// );
+    /// ```
+    pub fn duplicate_dictionary_keys(mut self, policy: DuplicateKeys) -> ParseOptions {
+        self.duplicate_dictionary_keys = policy;
+        self
+    }
 
-            if input_chars.peek().is_none() {
-                return Err("parse_dict: trailing comma");
-            }
+    /// Sets how parameters with a key that already appeared in the same parameters are handled.
+    /// By default, as the specification requires, the later value replaces the earlier one.
+    /// # Examples
+    /// ```
+    /// # use sfv::{DuplicateKeys, ParseOptions, Parser, SerializeValue};
+    /// let input = "a;q=1;v;q=2".as_bytes();
+    /// let options = ParseOptions::new().duplicate_parameter_keys(DuplicateKeys::KeepFirst);
+    /// let item = Parser::parse_item_with_options(input, &options).unwrap();
+    /// assert_eq!(item.serialize_value().unwrap(), "a;q=1;v");
+    ///
+    /// let options = ParseOptions::new().duplicate_parameter_keys(DuplicateKeys::Reject);
+    /// assert_eq!(
+    ///     Parser::parse_item_with_options(input, &options),
+    ///     Err("parse_parameters: duplicate key")
+    /// );
+    /// ```
+    pub fn duplicate_parameter_keys(mut self, policy: DuplicateKeys) -> ParseOptions {
+        self.duplicate_parameter_keys = policy;
+        self
+    }
+
+    /// Sets limits on the size of parsed values. Parsing fails as soon as one of them is exceeded.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseLimits, ParseOptions, Parser};
+    /// let limits = ParseLimits::new().max_parameters(1).max_string_len(8);
+    /// let options = ParseOptions::new().limits(limits);
+    /// assert!(Parser::parse_item_with_options("\"abc\";q=1".as_bytes(), &options).is_ok());
+    /// assert_eq!(
+    ///     Parser::parse_item_with_options("\"abc\";q=1;v".as_bytes(), &options),
+    ///     Err("parse_parameters: too many parameters")
+    /// );
+    /// assert_eq!(
+    ///     Parser::parse_item_with_options("\"too long string\"".as_bytes(), &options),
+    ///     Err("parse_string: string is too long")
+    /// );
+    /// ```
+    pub fn limits(mut self, limits: ParseLimits) -> ParseOptions {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the maximum length of input in bytes. Longer input is rejected before it's parsed,
+    /// including input of `Parser::parse_*_prefix` methods that would stop before exceeding it.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseOptions, Parser};
+    /// let options = ParseOptions::new().max_input_len(8);
+    /// assert!(Parser::parse_list_with_options("a, b, c".as_bytes(), &options).is_ok());
+    /// assert_eq!(
+    ///     Parser::parse_list_with_options("a, b, c, d".as_bytes(), &options),
+    ///     Err("parse: input is too long")
+    /// );
+    /// ```
+    pub fn max_input_len(mut self, max: usize) -> ParseOptions {
+        self.max_input_len = Some(max);
+        self
+    }
+
+    pub(crate) fn is_terminator(&self, c: char) -> bool {
+        c.is_ascii() && self.terminators.contains(&(c as u8))
+    }
+
+    // Checks whether input ends here, either because it's exhausted or because a terminator follows.
+    fn is_at_end<I: Input>(&self, input_chars: &mut I) -> bool {
+        match input_chars.peek_char() {
+            Some(c) => self.is_terminator(c),
+            None => true,
         }
-        Ok(dict)
     }
 }
 
-impl ParseMore for List {
-    fn parse_more(&mut self, input_bytes: &[u8]) -> SFVResult<()> {
-        let parsed_list = Parser::parse_list(input_bytes)?;
-        self.extend(parsed_list);
-        Ok(())
+// Input advanced by the parsing loops shared between owned and borrowed values, e.g. `Parser::parse_list_with`.
+// Keys and bare items are parsed by the input itself, so that borrowed values can be sliced from it.
+pub(crate) trait Input {
+    type Key;
+    type BareItem: From<bool>;
+
+    fn peek_char(&mut self) -> Option<char>;
+
+    fn next_char(&mut self) -> Option<char>;
+
+    fn parse_key(&mut self) -> SFVResult<Self::Key>;
+
+    fn parse_bare_item(&mut self, options: &ParseOptions) -> SFVResult<Self::BareItem>;
+}
+
+impl Input for Peekable<Chars<'_>> {
+    type Key = String;
+    type BareItem = BareItem;
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.peek().copied()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        self.next()
+    }
+
+    fn parse_key(&mut self) -> SFVResult<String> {
+        Parser::parse_key(self)
+    }
+
+    fn parse_bare_item(&mut self, options: &ParseOptions) -> SFVResult<BareItem> {
+        Parser::parse_bare_item(self, options)
     }
 }
 
-impl ParseMore for Dictionary {
-    fn parse_more(&mut self, input_bytes: &[u8]) -> SFVResult<()> {
-        let parsed_dict = Parser::parse_dictionary(input_bytes)?;
-        self.extend(parsed_dict);
-        Ok(())
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("normalizers", &self.normalizers.len())
+            .field("strict_base64", &self.strict_base64)
+            .field("reject_parameters", &self.reject_parameters)
+            .field("terminators", &self.terminators)
+            .field("duplicate_dictionary_keys", &self.duplicate_dictionary_keys)
+            .field("duplicate_parameter_keys", &self.duplicate_parameter_keys)
+            .field("limits", &self.limits)
+            .field("max_input_len", &self.max_input_len)
+            .finish()
     }
 }
 
@@ -154,9 +475,376 @@ impl Parser {
         Self::parse::<Item>(input_bytes)
     }
 
+    /// Parses input into structured field value of Dictionary type,
+    /// preallocating space for `capacity` members.
+    /// ```
+    /// # use sfv::Parser;
+    /// let dict = Parser::parse_dictionary_with_capacity("a=1, b=2".as_bytes(), 8).unwrap();
+    /// assert_eq!(dict.len(), 2);
+    /// assert!(dict.capacity() >= 8);
+    /// ```
+    pub fn parse_dictionary_with_capacity(
+        input_bytes: &[u8],
+        capacity: usize,
+    ) -> SFVResult<Dictionary> {
+        Self::parse_with(input_bytes, |input_chars| {
+            Self::parse_dict_members(
+                input_chars,
+                Dictionary::with_capacity(capacity),
+                &ParseOptions::default(),
+                None,
+            )
+        })
+    }
+
+    /// Parses input into structured field value of List type,
+    /// preallocating space for `capacity` members.
+    /// ```
+    /// # use sfv::Parser;
+    /// let list = Parser::parse_list_with_capacity("1, 2".as_bytes(), 8).unwrap();
+    /// assert_eq!(list.len(), 2);
+    /// assert!(list.capacity() >= 8);
+    /// ```
+    pub fn parse_list_with_capacity(input_bytes: &[u8], capacity: usize) -> SFVResult<List> {
+        Self::parse_with(input_bytes, |input_chars| {
+            Self::parse_list_members(
+                input_chars,
+                List::with_capacity(capacity),
+                &ParseOptions::default(),
+            )
+        })
+    }
+
+    /// Parses `http::HeaderValue` into structured field value of type `T`.
+    /// # Examples
+    /// ```
+    /// # use sfv::{List, Parser};
+    /// let header_value = http::HeaderValue::from_static("a, (b c)");
+    /// let list: List = Parser::from_header_value(&header_value).unwrap();
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    #[cfg(feature = "http")]
+    pub fn from_header_value<T: ParseValue>(header_value: &http::HeaderValue) -> SFVResult<T> {
+        Self::parse(header_value.as_bytes())
+    }
+
+    /// Parses input into structured field value of Dictionary type, hashing member names with `S`.
+    /// Only the top-level map uses `S`: parameters of members are `Parameters`, hashed with `RandomState`.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ListEntry, Parser};
+    /// use indexmap::IndexMap;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type FixedHasher = BuildHasherDefault<DefaultHasher>;
+    /// let dict: IndexMap<String, ListEntry, FixedHasher> = Parser::parse_dictionary_with_hasher("a=1, b".as_bytes()).unwrap();
+    /// assert_eq!(dict.len(), 2);
+    /// ```
+    pub fn parse_dictionary_with_hasher<S: BuildHasher + Default>(
+        input_bytes: &[u8],
+    ) -> SFVResult<IndexMap<String, ListEntry, S>> {
+        Self::parse_with(input_bytes, |input_chars| {
+            Self::parse_dict_members(
+                input_chars,
+                IndexMap::with_hasher(S::default()),
+                &ParseOptions::default(),
+                None,
+            )
+        })
+    }
+
+    /// Parses input into structured field value of Dictionary type using `options`, hashing member names with `S`.
+    /// See `parse_dictionary_with_hasher`.
+    pub fn parse_dictionary_with_hasher_with_options<S: BuildHasher + Default>(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+    ) -> SFVResult<IndexMap<String, ListEntry, S>> {
+        Self::parse_with_options(input_bytes, options, |input_chars| {
+            Self::parse_dict_members(
+                input_chars,
+                IndexMap::with_hasher(S::default()),
+                options,
+                None,
+            )
+        })
+    }
+
+    /// Parses input into structured field value of Dictionary type using `options`.
+    pub fn parse_dictionary_with_options(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+    ) -> SFVResult<Dictionary> {
+        Self::parse_with_options(input_bytes, options, |input_chars| {
+            Self::parse_dict_members(input_chars, Dictionary::new(), options, None)
+        })
+    }
+
+    /// Parses input into structured field value of Dictionary type, keeping only members named in `keys`.
+    /// Other members are validated but dropped right after parsing, instead of being stored.
+    /// ```
+    /// # use sfv::Parser;
+    /// let dict = Parser::parse_dictionary_filtered("u=2, x=(1 2 3), i".as_bytes(), &["u", "i"]).unwrap();
+    /// assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["u", "i"]);
+    ///
+    /// assert!(Parser::parse_dictionary_filtered("u=2, x=(1 2".as_bytes(), &["u"]).is_err());
+    /// ```
+    pub fn parse_dictionary_filtered(input_bytes: &[u8], keys: &[&str]) -> SFVResult<Dictionary> {
+        Self::parse_with(input_bytes, |input_chars| {
+            Self::parse_dict_members(
+                input_chars,
+                Dictionary::with_capacity(keys.len()),
+                &ParseOptions::default(),
+                Some(keys),
+            )
+        })
+    }
+
+    /// Parses input into structured field value of List type using `options`.
+    pub fn parse_list_with_options(input_bytes: &[u8], options: &ParseOptions) -> SFVResult<List> {
+        Self::parse_with_options(input_bytes, options, |input_chars| {
+            Self::parse_list_members(input_chars, List::new(), options)
+        })
+    }
+
+    /// Parses input into structured field value of Item type using `options`.
+    pub fn parse_item_with_options(input_bytes: &[u8], options: &ParseOptions) -> SFVResult<Item> {
+        Self::parse_with_options(input_bytes, options, |input_chars| {
+            Self::parse_item_value(input_chars, options)
+        })
+    }
+
+    /// Parses the beginning of input into structured field value of Dictionary type using `options`,
+    /// stopping at the first of `ParseOptions::terminators` that follows a complete value.
+    /// Returns the value together with the number of bytes consumed, so that the terminator, if any, is at that offset.
+    /// Whitespace before the terminator is consumed.
+    pub fn parse_dictionary_prefix(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+    ) -> SFVResult<(Dictionary, usize)> {
+        Self::parse_prefix_with(input_bytes, options, |input_chars| {
+            Self::parse_dict_members(input_chars, Dictionary::new(), options, None)
+        })
+    }
+
+    /// Parses the beginning of input into structured field value of List type using `options`.
+    /// See `parse_dictionary_prefix` for details.
+    /// # Examples
+    /// ```
+    /// # use sfv::{ParseOptions, Parser};
+    /// let options = ParseOptions::new().terminators(b"|");
+    /// let (list, consumed) = Parser::parse_list_prefix("a, (b c)  | x".as_bytes(), &options).unwrap();
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(consumed, 10);
+    /// ```
+    pub fn parse_list_prefix(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+    ) -> SFVResult<(List, usize)> {
+        Self::parse_prefix_with(input_bytes, options, |input_chars| {
+            Self::parse_list_members(input_chars, List::new(), options)
+        })
+    }
+
+    /// Parses the beginning of input into structured field value of Item type using `options`.
+    /// See `parse_dictionary_prefix` for details.
+    pub fn parse_item_prefix(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+    ) -> SFVResult<(Item, usize)> {
+        Self::parse_prefix_with(input_bytes, options, |input_chars| {
+            Self::parse_item_value(input_chars, options)
+        })
+    }
+
+    /// Parses input into structured field value of Dictionary type, retaining the raw input.
+    pub fn parse_dictionary_with_raw(input_bytes: &[u8]) -> SFVResult<Parsed<Dictionary>> {
+        let value = Self::parse_dictionary(input_bytes)?;
+        Ok(Parsed::new(value, input_bytes))
+    }
+
+    /// Parses input into structured field value of List type, retaining the raw input.
+    pub fn parse_list_with_raw(input_bytes: &[u8]) -> SFVResult<Parsed<List>> {
+        let value = Self::parse_list(input_bytes)?;
+        Ok(Parsed::new(value, input_bytes))
+    }
+
+    /// Parses input into structured field value of Item type, retaining the raw input.
+    pub fn parse_item_with_raw(input_bytes: &[u8]) -> SFVResult<Parsed<Item>> {
+        let value = Self::parse_item(input_bytes)?;
+        Ok(Parsed::new(value, input_bytes))
+    }
+
+    /// Parses field lines of the same field into structured field value of Dictionary type,
+    /// as if they were combined with ", " into a single line, without combining them.
+    /// Every line is parsed on its own, so a line has to end at a member boundary,
+    /// e.g. a string containing a comma can't be split across lines.
+    /// Members of later lines replace members with the same key of earlier ones, keeping their position.
+    /// As in the combined line, where it would leave an empty member, an empty line is an error unless it's the only one.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let lines = vec!["a=1, b".as_bytes(), "c;p, a=2".as_bytes()];
+    /// let dict = Parser::parse_dictionary_from_lines(lines).unwrap();
+    /// assert_eq!(dict.serialize_value().unwrap(), "a=2, b, c;p");
+    ///
+    /// let lines = vec!["a=1, b".as_bytes(), "".as_bytes(), "c;p, a=2".as_bytes()];
+    /// assert_eq!(Parser::parse_dictionary_from_lines(lines), Err("parse_dict: empty field line"));
+    /// ```
+    pub fn parse_dictionary_from_lines<'a, I>(lines: I) -> SFVResult<Dictionary>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        Self::parse_lines(lines, "parse_dict: empty field line")
+    }
+
+    /// Parses field lines of the same field into structured field value of List type,
+    /// as if they were combined with ", " into a single line, without combining them.
+    /// See `parse_dictionary_from_lines` for details.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let lines = vec!["gzip;q=1".as_bytes(), "br, (a b)".as_bytes()];
+    /// let list = Parser::parse_list_from_lines(lines).unwrap();
+    /// assert_eq!(list.serialize_value().unwrap(), "gzip;q=1, br, (a b)");
+    /// ```
+    pub fn parse_list_from_lines<'a, I>(lines: I) -> SFVResult<List>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        Self::parse_lines(lines, "parse_list: empty field line")
+    }
+
+    fn parse_lines<'a, T, I>(lines: I, empty_line_error: &'static str) -> SFVResult<T>
+    where
+        T: ParseMore + Default,
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut value = T::default();
+        let mut lines = lines.into_iter().peekable();
+        let mut is_first = true;
+        while let Some(line) = lines.next() {
+            // Combined with other lines, an empty line leaves nothing between two commas
+            let is_only_line = is_first && lines.peek().is_none();
+            let is_ows = |c: &u8| *c == b' ' || *c == b'\t';
+            if !is_only_line && line.iter().all(is_ows) {
+                return Err(empty_line_error);
+            }
+            // In the combined line, lines after the first follow ", ", after which OWS is allowed
+            let line = if is_first {
+                line
+            } else {
+                let start = line.iter().position(|c| !is_ows(c)).unwrap_or(line.len());
+                &line[start..]
+            };
+            value.parse_more(line)?;
+            is_first = false;
+        }
+        Ok(value)
+    }
+
+    /// Reads input from `reader` until EOF and parses it into structured field value of Dictionary type.
+    /// Trailing line breaks are ignored. Returns an error of `InvalidData` kind
+    /// if the input is longer than `max_len` bytes or can't be parsed.
+    /// # Examples
+    /// ```
+    /// # use sfv::Parser;
+    /// let input = "a=1, b=2\n".as_bytes();
+    /// let dict = Parser::read_dictionary(input, 1024).unwrap();
+    /// assert_eq!(dict.len(), 2);
+    ///
+    /// assert!(Parser::read_dictionary(input, 4).is_err());
+    /// ```
+    pub fn read_dictionary<R: Read>(reader: R, max_len: usize) -> io::Result<Dictionary> {
+        Self::read_with(reader, max_len, Self::parse_dictionary)
+    }
+
+    /// Reads input from `reader` until EOF and parses it into structured field value of List type.
+    /// Trailing line breaks are ignored. Returns an error of `InvalidData` kind
+    /// if the input is longer than `max_len` bytes or can't be parsed.
+    pub fn read_list<R: Read>(reader: R, max_len: usize) -> io::Result<List> {
+        Self::read_with(reader, max_len, Self::parse_list)
+    }
+
+    /// Reads input from `reader` until EOF and parses it into structured field value of Item type.
+    /// Trailing line breaks are ignored. Returns an error of `InvalidData` kind
+    /// if the input is longer than `max_len` bytes or can't be parsed.
+    pub fn read_item<R: Read>(reader: R, max_len: usize) -> io::Result<Item> {
+        Self::read_with(reader, max_len, Self::parse_item)
+    }
+
+    fn read_with<R, T, F>(reader: R, max_len: usize, parse_value: F) -> io::Result<T>
+    where
+        R: Read,
+        F: FnOnce(&[u8]) -> SFVResult<T>,
+    {
+        let mut input_bytes = Vec::new();
+        reader
+            .take((max_len as u64).saturating_add(1))
+            .read_to_end(&mut input_bytes)?;
+        if input_bytes.len() > max_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read: input is longer than max_len",
+            ));
+        }
+
+        while let Some(b'\n') | Some(b'\r') = input_bytes.last() {
+            input_bytes.pop();
+        }
+        parse_value(&input_bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
     // Generic parse method for checking input before parsing
     // and handling trailing text error
     fn parse<T: ParseValue>(input_bytes: &[u8]) -> SFVResult<T> {
+        Self::parse_with(input_bytes, T::parse)
+    }
+
+    pub(crate) fn parse_with<T, F>(input_bytes: &[u8], parse_value: F) -> SFVResult<T>
+    where
+        F: FnOnce(&mut Peekable<Chars>) -> SFVResult<T>,
+    {
+        // Without terminators, the whole input is consumed
+        let (output, _) =
+            Self::parse_prefix_with(input_bytes, &ParseOptions::default(), parse_value)?;
+        Ok(output)
+    }
+
+    // Parses input with `options`, treating terminators as trailing characters
+    fn parse_with_options<T, F>(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+        parse_value: F,
+    ) -> SFVResult<T>
+    where
+        F: FnOnce(&mut Peekable<Chars>) -> SFVResult<T>,
+    {
+        Self::check_options(input_bytes, options)?;
+        Self::parse_with(input_bytes, parse_value)
+    }
+
+    pub(crate) fn check_options(input_bytes: &[u8], options: &ParseOptions) -> SFVResult<()> {
+        if options.terminators.iter().any(|&c| c == b';' || c == b'=') {
+            return Err("parse: ';' and '=' can't be terminators");
+        }
+        ParseLimits::check(
+            options.max_input_len,
+            input_bytes.len(),
+            "parse: input is too long",
+        )
+    }
+
+    fn parse_prefix_with<T, F>(
+        input_bytes: &[u8],
+        options: &ParseOptions,
+        parse_value: F,
+    ) -> SFVResult<(T, usize)>
+    where
+        F: FnOnce(&mut Peekable<Chars>) -> SFVResult<T>,
+    {
+        Self::check_options(input_bytes, options)?;
+
         // https://httpwg.org/specs/rfc8941.html#text-parse
         if !input_bytes.is_ascii() {
             return Err("parse: non-ascii characters in input");
@@ -168,57 +856,254 @@ impl Parser {
             .peekable();
         utils::consume_sp_chars(&mut input_chars);
 
-        let output = T::parse(&mut input_chars)?;
+        let output = parse_value(&mut input_chars)?;
 
         utils::consume_sp_chars(&mut input_chars);
 
-        if input_chars.next().is_some() {
+        if !options.is_at_end(&mut input_chars) {
             return Err("parse: trailing characters after parsed value");
         };
-        Ok(output)
+        // Input is ASCII, so each remaining char is a single byte
+        let consumed = input_bytes.len() - input_chars.count();
+        Ok((output, consumed))
+    }
+
+    fn parse_list_members(
+        input_chars: &mut Peekable<Chars>,
+        mut members: List,
+        options: &ParseOptions,
+    ) -> SFVResult<List> {
+        Self::parse_list_with(input_chars, options, |input_chars| {
+            members.push(Parser::parse_list_entry(input_chars, options)?);
+            Ok(())
+        })?;
+        Ok(members)
+    }
+
+    fn parse_dict_members<S: BuildHasher>(
+        input_chars: &mut Peekable<Chars>,
+        mut dict: IndexMap<String, ListEntry, S>,
+        options: &ParseOptions,
+        keys: Option<&[&str]>,
+    ) -> SFVResult<IndexMap<String, ListEntry, S>> {
+        Self::parse_dict_with(input_chars, options, |input_chars, this_key| {
+            let member = Parser::parse_dict_value(input_chars, options)?;
+
+            let is_kept = match keys {
+                Some(keys) => keys.contains(&this_key.as_str()),
+                None => true,
+            };
+            if is_kept {
+                options.duplicate_dictionary_keys.insert(
+                    &mut dict,
+                    this_key,
+                    member,
+                    "parse_dict: duplicate key",
+                )?;
+            }
+            Ok(())
+        })?;
+        Ok(dict)
     }
 
-    fn parse_list_entry(input_chars: &mut Peekable<Chars>) -> SFVResult<ListEntry> {
+    // Parses comma-separated List members, leaving parsing of each member to `parse_member`
+    pub(crate) fn parse_list_with<I, F>(
+        input_chars: &mut I,
+        options: &ParseOptions,
+        mut parse_member: F,
+    ) -> SFVResult<()>
+    where
+        I: Input,
+        F: FnMut(&mut I) -> SFVResult<()>,
+    {
+        // https://httpwg.org/specs/rfc8941.html#parse-list
+        // List represents an array of (item_or_inner_list, parameters)
+
+        let mut parsed_members = 0;
+        while !options.is_at_end(input_chars) {
+            parsed_members += 1;
+            ParseLimits::check(
+                options.limits.max_list_members,
+                parsed_members,
+                "parse_list: too many members",
+            )?;
+            parse_member(input_chars)?;
+
+            utils::consume_ows_chars(input_chars);
+
+            if options.is_at_end(input_chars) {
+                return Ok(());
+            }
+
+            if let Some(c) = input_chars.next_char() {
+                if c != ',' {
+                    return Err("parse_list: trailing characters after list member");
+                }
+            }
+
+            utils::consume_ows_chars(input_chars);
+
+            if options.is_at_end(input_chars) {
+                return Err("parse_list: trailing comma");
+            }
+        }
+        Ok(())
+    }
+
+    // Parses comma-separated Dictionary members, passing each key to `parse_member` to parse the rest of the member
+    pub(crate) fn parse_dict_with<I, F>(
+        input_chars: &mut I,
+        options: &ParseOptions,
+        mut parse_member: F,
+    ) -> SFVResult<()>
+    where
+        I: Input,
+        F: FnMut(&mut I, I::Key) -> SFVResult<()>,
+    {
+        // https://httpwg.org/specs/rfc8941.html#parse-dictionary
+        // Members with duplicate keys count towards the limit, as they are parsed all the same
+        let mut parsed_members = 0;
+        while !options.is_at_end(input_chars) {
+            parsed_members += 1;
+            ParseLimits::check(
+                options.limits.max_dictionary_members,
+                parsed_members,
+                "parse_dict: too many members",
+            )?;
+            let this_key = input_chars.parse_key()?;
+            parse_member(input_chars, this_key)?;
+
+            utils::consume_ows_chars(input_chars);
+
+            if options.is_at_end(input_chars) {
+                return Ok(());
+            }
+
+            if let Some(c) = input_chars.next_char() {
+                if c != ',' {
+                    return Err("parse_dict: trailing characters after dictionary member");
+                }
+            }
+
+            utils::consume_ows_chars(input_chars);
+
+            if options.is_at_end(input_chars) {
+                return Err("parse_dict: trailing comma");
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parse_dict_member(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<(String, ListEntry)> {
+        let this_key = Parser::parse_key(input_chars)?;
+        let member = Parser::parse_dict_value(input_chars, options)?;
+        Ok((this_key, member))
+    }
+
+    // Parses Dictionary member value following its key
+    fn parse_dict_value(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<ListEntry> {
+        if let Some('=') = input_chars.peek() {
+            input_chars.next();
+            Parser::parse_list_entry(input_chars, options)
+        } else {
+            let value = true;
+            let params = Parser::parse_parameters(input_chars, options)?;
+            Ok(Item {
+                bare_item: BareItem::Boolean(value),
+                params,
+            }
+            .into())
+        }
+    }
+
+    pub(crate) fn parse_list_entry(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<ListEntry> {
         // https://httpwg.org/specs/rfc8941.html#parse-item-or-list
         // ListEntry represents a tuple (item_or_inner_list, parameters)
 
         match input_chars.peek() {
             Some('(') => {
-                let parsed = Self::parse_inner_list(input_chars)?;
+                let parsed = Self::parse_inner_list(input_chars, options)?;
                 Ok(ListEntry::InnerList(parsed))
             }
             _ => {
-                let parsed = Item::parse(input_chars)?;
+                let parsed = Self::parse_item_value(input_chars, options)?;
                 Ok(ListEntry::Item(parsed))
             }
         }
     }
 
-    pub(crate) fn parse_inner_list(input_chars: &mut Peekable<Chars>) -> SFVResult<InnerList> {
+    pub(crate) fn parse_item_value(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<Item> {
+        // https://httpwg.org/specs/rfc8941.html#parse-item
+        let bare_item = Self::parse_bare_item(input_chars, options)?;
+        let params = Self::parse_parameters(input_chars, options)?;
+
+        Ok(Item { bare_item, params })
+    }
+
+    pub(crate) fn parse_inner_list(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<InnerList> {
+        let mut inner_list = Vec::new();
+        Self::parse_inner_list_with(input_chars, options, |input_chars| {
+            inner_list.push(Self::parse_item_value(input_chars, options)?);
+            Ok(())
+        })?;
+        let params = Self::parse_parameters(input_chars, options)?;
+        Ok(InnerList {
+            items: inner_list,
+            params,
+        })
+    }
+
+    // Parses Inner List items up to and including the closing ')', leaving parsing of each item to `parse_item`.
+    // Parameters of the Inner List are left to the caller.
+    pub(crate) fn parse_inner_list_with<I, F>(
+        input_chars: &mut I,
+        options: &ParseOptions,
+        mut parse_item: F,
+    ) -> SFVResult<()>
+    where
+        I: Input,
+        F: FnMut(&mut I) -> SFVResult<()>,
+    {
         // https://httpwg.org/specs/rfc8941.html#parse-innerlist
 
-        if Some('(') != input_chars.next() {
+        if Some('(') != input_chars.next_char() {
             return Err("parse_inner_list: input does not start with '('");
         }
 
-        let mut inner_list = Vec::new();
-        while input_chars.peek().is_some() {
+        let mut parsed_items = 0;
+        while input_chars.peek_char().is_some() {
             utils::consume_sp_chars(input_chars);
 
-            if Some(&')') == input_chars.peek() {
-                input_chars.next();
-                let params = Self::parse_parameters(input_chars)?;
-                return Ok(InnerList {
-                    items: inner_list,
-                    params,
-                });
+            if Some(')') == input_chars.peek_char() {
+                input_chars.next_char();
+                return Ok(());
             }
 
-            let parsed_item = Item::parse(input_chars)?;
-            inner_list.push(parsed_item);
+            parsed_items += 1;
+            ParseLimits::check(
+                options.limits.max_list_members,
+                parsed_items,
+                "parse_inner_list: too many items",
+            )?;
+            parse_item(input_chars)?;
 
-            if let Some(c) = input_chars.peek() {
-                if c != &' ' && c != &')' {
+            if let Some(c) = input_chars.peek_char() {
+                if c != ' ' && c != ')' {
                     return Err("parse_inner_list: bad delimitation");
                 }
             }
@@ -227,25 +1112,33 @@ impl Parser {
         Err("parse_inner_list: the end of the inner list was not found")
     }
 
-    pub(crate) fn parse_bare_item(input_chars: &mut Peekable<Chars>) -> SFVResult<BareItem> {
+    pub(crate) fn parse_bare_item(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<BareItem> {
         // https://httpwg.org/specs/rfc8941.html#parse-bare-item
         if input_chars.peek().is_none() {
             return Err("parse_bare_item: empty item");
         }
 
-        match input_chars.peek() {
-            Some(&'?') => Ok(BareItem::Boolean(Self::parse_bool(input_chars)?)),
-            Some(&'"') => Ok(BareItem::String(Self::parse_string(input_chars)?)),
-            Some(&':') => Ok(BareItem::ByteSeq(Self::parse_byte_sequence(input_chars)?)),
-            Some(&c) if c == '*' || c.is_ascii_alphabetic() => {
-                Ok(BareItem::Token(Self::parse_token(input_chars)?))
+        let mut bare_item = match input_chars.peek() {
+            Some(&'?') => BareItem::Boolean(Self::parse_bool(input_chars)?),
+            Some(&'"') => BareItem::String(Self::parse_string(input_chars, options)?),
+            Some(&':') => BareItem::ByteSeq(Self::parse_byte_sequence(input_chars, options)?),
+            Some(&c) if chars::is_allowed_token_first_char(c) => {
+                BareItem::Token(Self::parse_token(input_chars, options)?)
             }
             Some(&c) if c == '-' || c.is_ascii_digit() => match Self::parse_number(input_chars)? {
-                Num::Decimal(val) => Ok(BareItem::Decimal(val)),
-                Num::Integer(val) => Ok(BareItem::Integer(val)),
+                Number::Decimal(val) => BareItem::Decimal(val),
+                Number::Integer(val) => BareItem::Integer(val),
             },
-            _ => Err("parse_bare_item: item type can't be identified"),
+            _ => return Err("parse_bare_item: item type can't be identified"),
+        };
+
+        for normalize in &options.normalizers {
+            normalize(&mut bare_item);
         }
+        Ok(bare_item)
     }
 
     pub(crate) fn parse_bool(input_chars: &mut Peekable<Chars>) -> SFVResult<bool> {
@@ -262,7 +1155,10 @@ impl Parser {
         }
     }
 
-    pub(crate) fn parse_string(input_chars: &mut Peekable<Chars>) -> SFVResult<String> {
+    pub(crate) fn parse_string(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<String> {
         // https://httpwg.org/specs/rfc8941.html#parse-string
 
         if input_chars.next() != Some('\"') {
@@ -273,7 +1169,9 @@ impl Parser {
         while let Some(curr_char) = input_chars.next() {
             match curr_char {
                 '\"' => return Ok(output_string),
-                '\x7f' | '\x00'..='\x1f' => return Err("parse_string: not a visible character"),
+                c if !chars::is_allowed_string_char(c) => {
+                    return Err("parse_string: not a visible character")
+                }
                 '\\' => match input_chars.next() {
                     Some(c) if c == '\\' || c == '\"' => {
                         output_string.push(c);
@@ -283,15 +1181,23 @@ impl Parser {
                 },
                 _ => output_string.push(curr_char),
             }
+            ParseLimits::check(
+                options.limits.max_string_len,
+                output_string.len(),
+                "parse_string: string is too long",
+            )?;
         }
         Err("parse_string: no closing '\"'")
     }
 
-    pub(crate) fn parse_token(input_chars: &mut Peekable<Chars>) -> SFVResult<String> {
+    pub(crate) fn parse_token(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<String> {
         // https://httpwg.org/specs/rfc8941.html#parse-token
 
         if let Some(first_char) = input_chars.peek() {
-            if !first_char.is_ascii_alphabetic() && first_char != &'*' {
+            if !chars::is_allowed_token_first_char(*first_char) {
                 return Err("parse_token: first character is not ALPHA or '*'");
             }
         } else {
@@ -300,7 +1206,7 @@ impl Parser {
 
         let mut output_string = String::from("");
         while let Some(curr_char) = input_chars.peek() {
-            if !utils::is_tchar(*curr_char) && curr_char != &':' && curr_char != &'/' {
+            if !chars::is_allowed_token_char(*curr_char) || options.is_terminator(*curr_char) {
                 return Ok(output_string);
             }
 
@@ -312,7 +1218,10 @@ impl Parser {
         Ok(output_string)
     }
 
-    pub(crate) fn parse_byte_sequence(input_chars: &mut Peekable<Chars>) -> SFVResult<Vec<u8>> {
+    pub(crate) fn parse_byte_sequence(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<Vec<u8>> {
         // https://httpwg.org/specs/rfc8941.html#parse-binary
 
         if input_chars.next() != Some(':') {
@@ -324,16 +1233,28 @@ impl Parser {
         }
 
         let b64_content = input_chars.take_while(|c| c != &':').collect::<String>();
-        if !b64_content.chars().all(utils::is_allowed_b64_content) {
+        if !b64_content.chars().all(chars::is_allowed_b64_content) {
             return Err("parse_byte_seq: invalid char in byte sequence");
         }
-        match utils::base64()?.decode(b64_content.as_bytes()) {
+        // Checked before decoding, as the decoded length follows from the length of the content
+        let padding = b64_content.bytes().rev().take_while(|&c| c == b'=').count();
+        ParseLimits::check(
+            options.limits.max_byte_seq_len,
+            (b64_content.len() - padding) * 3 / 4,
+            "parse_byte_seq: byte sequence is too long",
+        )?;
+        let encoding = if options.strict_base64 {
+            BASE64
+        } else {
+            utils::base64()?
+        };
+        match encoding.decode(b64_content.as_bytes()) {
             Ok(content) => Ok(content),
             Err(_) => Err("parse_byte_seq: decoding error"),
         }
     }
 
-    pub(crate) fn parse_number(input_chars: &mut Peekable<Chars>) -> SFVResult<Num> {
+    pub(crate) fn parse_number(input_chars: &mut Peekable<Chars>) -> SFVResult<Number> {
         // https://httpwg.org/specs/rfc8941.html#parse-number
 
         let mut sign = 1;
@@ -365,7 +1286,7 @@ impl Parser {
                 return Err("parse_number: integer number is out of range");
             }
 
-            return Ok(Num::Integer(output_number));
+            return Ok(Number::Integer(output_number));
         }
 
         // Parse input_number from string into decimal
@@ -383,7 +1304,7 @@ impl Parser {
                     output_number.set_sign_negative(true)
                 }
 
-                Ok(Num::Decimal(output_number))
+                Ok(Number::Decimal(output_number))
             }
             _ => Err("parse_number: invalid decimal fraction length"),
         }
@@ -420,48 +1341,81 @@ impl Parser {
         Ok((is_integer, input_number))
     }
 
-    pub(crate) fn parse_parameters(input_chars: &mut Peekable<Chars>) -> SFVResult<Parameters> {
+    pub(crate) fn parse_parameters(
+        input_chars: &mut Peekable<Chars>,
+        options: &ParseOptions,
+    ) -> SFVResult<Parameters> {
+        let mut params = Parameters::new();
+        Self::parse_parameters_with(input_chars, options, |param_name, param_value| {
+            // If parameters already contains a name param_name (comparing character-for-character), overwrite its value.
+            // Note that when duplicate Parameter keys are encountered, this has the effect of ignoring all but the last instance.
+            // `ParseOptions::duplicate_parameter_keys` may opt out of this.
+            options.duplicate_parameter_keys.insert(
+                &mut params,
+                param_name,
+                param_value,
+                "parse_parameters: duplicate key",
+            )
+        })?;
+        Ok(params)
+    }
+
+    // Parses parameters, passing each name and value to `insert`
+    pub(crate) fn parse_parameters_with<I, F>(
+        input_chars: &mut I,
+        options: &ParseOptions,
+        mut insert: F,
+    ) -> SFVResult<()>
+    where
+        I: Input,
+        F: FnMut(I::Key, I::BareItem) -> SFVResult<()>,
+    {
         // https://httpwg.org/specs/rfc8941.html#parse-param
 
-        let mut params = Parameters::new();
+        let mut parsed_params = 0;
 
-        while let Some(curr_char) = input_chars.peek() {
-            if curr_char == &';' {
-                input_chars.next();
+        while let Some(curr_char) = input_chars.peek_char() {
+            if curr_char == ';' {
+                input_chars.next_char();
             } else {
                 break;
             }
 
+            if options.reject_parameters {
+                return Err("parse_parameters: parameters are not allowed");
+            }
+            parsed_params += 1;
+            ParseLimits::check(
+                options.limits.max_parameters,
+                parsed_params,
+                "parse_parameters: too many parameters",
+            )?;
+
             utils::consume_sp_chars(input_chars);
 
-            let param_name = Self::parse_key(input_chars)?;
-            let param_value = match input_chars.peek() {
+            let param_name = input_chars.parse_key()?;
+            let param_value = match input_chars.peek_char() {
                 Some('=') => {
-                    input_chars.next();
-                    Self::parse_bare_item(input_chars)?
+                    input_chars.next_char();
+                    input_chars.parse_bare_item(options)?
                 }
-                _ => BareItem::Boolean(true),
+                _ => true.into(),
             };
-            params.insert(param_name, param_value);
+            insert(param_name, param_value)?;
         }
 
-        // If parameters already contains a name param_name (comparing character-for-character), overwrite its value.
-        // Note that when duplicate Parameter keys are encountered, this has the effect of ignoring all but the last instance.
-        Ok(params)
+        Ok(())
     }
 
     pub(crate) fn parse_key(input_chars: &mut Peekable<Chars>) -> SFVResult<String> {
         match input_chars.peek() {
-            Some(c) if c == &'*' || c.is_ascii_lowercase() => (),
+            Some(&c) if chars::is_allowed_key_first_char(c) => (),
             _ => return Err("parse_key: first character is not lcalpha or '*'"),
         }
 
         let mut output = String::new();
         while let Some(curr_char) = input_chars.peek() {
-            if !curr_char.is_ascii_lowercase()
-                && !curr_char.is_ascii_digit()
-                && !"_-*.".contains(*curr_char)
-            {
+            if !chars::is_allowed_key_char(*curr_char) {
                 return Ok(output);
             }
 
@@ -470,4 +1424,14 @@ impl Parser {
         }
         Ok(output)
     }
+
+    pub(crate) fn validate_key(input_key: &str) -> SFVResult<()> {
+        let mut input_chars = input_key.chars().peekable();
+        Self::parse_key(&mut input_chars)?;
+
+        if input_chars.next().is_some() {
+            return Err("validate_key: disallowed character in input");
+        }
+        Ok(())
+    }
 }
@@ -1,7 +1,62 @@
-use crate::serializer::Serializer;
+use crate::serializer::{Serializer, WRITE_FMT_ERROR};
 use crate::{RefBareItem, SFVResult};
+use indexmap::IndexSet;
+use std::fmt;
 use std::marker::PhantomData;
 
+// Keys serialized by a checked `RefDictSerializer`, in order
+type SerializedKeys = Option<IndexSet<String>>;
+
+/// Destination of the serialized output, either `String` or `FmtWriter`.
+pub trait Output {
+    /// Appends the output of `serialize`, or nothing if it fails.
+    fn append(&mut self, serialize: impl FnOnce(&mut String) -> SFVResult<()>) -> SFVResult<()>;
+}
+
+impl Output for String {
+    fn append(&mut self, serialize: impl FnOnce(&mut String) -> SFVResult<()>) -> SFVResult<()> {
+        Serializer::append(self, serialize)
+    }
+}
+
+/// Adapts any `fmt::Write` for use with `RefListSerializer::with_writer` and `RefDictSerializer::with_writer`.
+/// Every call is serialized into a scratch string first and written out only once it's complete,
+/// so that a failed call writes nothing.
+#[derive(Debug)]
+pub struct FmtWriter<W> {
+    writer: W,
+    scratch: String,
+}
+
+impl<W: fmt::Write> FmtWriter<W> {
+    pub fn new(writer: W) -> Self {
+        FmtWriter {
+            writer,
+            scratch: String::new(),
+        }
+    }
+
+    /// Returns the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes `FmtWriter`, returning the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: fmt::Write> Output for FmtWriter<W> {
+    fn append(&mut self, serialize: impl FnOnce(&mut String) -> SFVResult<()>) -> SFVResult<()> {
+        self.scratch.clear();
+        serialize(&mut self.scratch)?;
+        self.writer
+            .write_str(&self.scratch)
+            .map_err(|_| WRITE_FMT_ERROR)
+    }
+}
+
 /// Serializes `Item` field value components incrementally.
 /// ```
 /// use sfv::{RefBareItem, RefItemSerializer};
@@ -16,17 +71,18 @@ use std::marker::PhantomData;
 /// assert_eq!(serialized_item, "11;foo");
 /// ```
 #[derive(Debug)]
-pub struct RefItemSerializer<'a> {
-    pub buffer: &'a mut String,
+pub struct RefItemSerializer<'a, O = String> {
+    pub buffer: &'a mut O,
 }
 
-impl<'a> RefItemSerializer<'a> {
-    pub fn new(buffer: &'a mut String) -> Self {
+impl<'a, O: Output> RefItemSerializer<'a, O> {
+    pub fn new(buffer: &'a mut O) -> Self {
         RefItemSerializer { buffer }
     }
 
-    pub fn bare_item(self, bare_item: &RefBareItem) -> SFVResult<RefParameterSerializer<'a>> {
-        Serializer::serialize_ref_bare_item(bare_item, self.buffer)?;
+    pub fn bare_item(self, bare_item: &RefBareItem) -> SFVResult<RefParameterSerializer<'a, O>> {
+        self.buffer
+            .append(|output| Serializer::serialize_ref_bare_item(bare_item, output))?;
         Ok(RefParameterSerializer {
             buffer: self.buffer,
         })
@@ -35,18 +91,20 @@ impl<'a> RefItemSerializer<'a> {
 
 /// Used by `RefItemSerializer`, `RefListSerializer`, `RefDictSerializer` to serialize a single `Parameter`.
 #[derive(Debug)]
-pub struct RefParameterSerializer<'a> {
-    buffer: &'a mut String,
+pub struct RefParameterSerializer<'a, O = String> {
+    buffer: &'a mut O,
 }
 
-impl<'a> RefParameterSerializer<'a> {
+impl<'a, O: Output> RefParameterSerializer<'a, O> {
     pub fn parameter(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
-        Serializer::serialize_ref_parameter(name, value, self.buffer)?;
+        self.buffer
+            .append(|output| Serializer::serialize_ref_parameter(name, value, output))?;
         Ok(self)
     }
 }
 
 /// Serializes `List` field value components incrementally.
+/// Output is written to `String`, or to any `fmt::Write` wrapped in `FmtWriter`.
 /// ```
 /// use sfv::{RefBareItem, RefListSerializer};
 ///
@@ -73,47 +131,161 @@ impl<'a> RefParameterSerializer<'a> {
 /// );
 /// ```
 #[derive(Debug)]
-pub struct RefListSerializer<'a> {
-    buffer: &'a mut String,
+pub struct RefListSerializer<'a, O = String> {
+    buffer: &'a mut O,
+    has_members: bool,
+    // Output error of an infallible call, returned by the next fallible one
+    error: Option<&'static str>,
 }
 
 impl<'a> RefListSerializer<'a> {
+    /// Returns `RefListSerializer` appending members to `buffer`.
+    /// If `buffer` isn't empty, the first member is separated from its content with a comma,
+    /// so that serialization can continue after members serialized earlier.
     pub fn new(buffer: &'a mut String) -> Self {
-        RefListSerializer { buffer }
+        let has_members = !buffer.is_empty();
+        RefListSerializer {
+            buffer,
+            has_members,
+            error: None,
+        }
     }
 
-    pub fn bare_item(self, bare_item: &RefBareItem) -> SFVResult<Self> {
-        if !self.buffer.is_empty() {
-            self.buffer.push_str(", ");
+    /// Records the current end of the serialized output.
+    /// ```
+    /// use sfv::{RefBareItem, RefListSerializer};
+    ///
+    /// let mut serialized_list = String::new();
+    /// let serializer = RefListSerializer::new(&mut serialized_list)
+    ///     .bare_item(&RefBareItem::Integer(1))
+    ///     .unwrap();
+    /// let checkpoint = serializer.checkpoint();
+    /// let serializer = serializer
+    ///     .bare_item(&RefBareItem::Token("too-long"))
+    ///     .unwrap()
+    ///     .rollback(checkpoint)
+    ///     .bare_item(&RefBareItem::Integer(2))
+    ///     .unwrap();
+    /// assert_eq!(serialized_list, "1, 2");
+    ///
+    /// // A failed call consumes the serializer, so restore the buffer and continue with a new serializer.
+    /// let checkpoint = RefListSerializer::new(&mut serialized_list).checkpoint();
+    /// let result = RefListSerializer::new(&mut serialized_list)
+    ///     .bare_item(&RefBareItem::Integer(3))
+    ///     .unwrap()
+    ///     .parameter("Invalid", &RefBareItem::Boolean(false));
+    /// assert!(result.is_err());
+    /// checkpoint.restore(&mut serialized_list);
+    /// assert_eq!(serialized_list, "1, 2");
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.buffer.len(),
+            keys: 0,
+        }
+    }
+
+    /// Removes everything serialized after `checkpoint` was recorded.
+    pub fn rollback(mut self, checkpoint: Checkpoint) -> Self {
+        checkpoint.restore(self.buffer);
+        self.has_members = !self.buffer.is_empty();
+        self
+    }
+}
+
+impl<'a, O: Output> RefListSerializer<'a, O> {
+    /// Returns `RefListSerializer` writing a new field value to `writer`, after anything it already holds.
+    /// ```
+    /// use sfv::{FmtWriter, RefBareItem, RefListSerializer};
+    /// use std::fmt::Write;
+    ///
+    /// struct Header(String);
+    ///
+    /// impl Write for Header {
+    ///     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    ///         self.0.write_str(s)
+    ///     }
+    /// }
+    ///
+    /// let mut header = FmtWriter::new(Header("Accept-Encoding: ".to_owned()));
+    /// RefListSerializer::with_writer(&mut header)
+    ///     .bare_item(&RefBareItem::Token("gzip"))
+    ///     .unwrap()
+    ///     .bare_item(&RefBareItem::Token("br"))
+    ///     .unwrap()
+    ///     .finish()
+    ///     .unwrap();
+    /// assert_eq!(header.into_inner().0, "Accept-Encoding: gzip, br");
+    /// ```
+    pub fn with_writer(writer: &'a mut O) -> Self {
+        RefListSerializer {
+            buffer: writer,
+            has_members: false,
+            error: None,
         }
-        Serializer::serialize_ref_bare_item(bare_item, self.buffer)?;
-        Ok(RefListSerializer {
-            buffer: self.buffer,
-        })
+    }
+
+    pub fn bare_item(self, bare_item: &RefBareItem) -> SFVResult<Self> {
+        let mut ser = self.check_error()?;
+        let has_members = ser.has_members;
+        ser.buffer.append(|output| {
+            if has_members {
+                output.push_str(", ");
+            }
+            Serializer::serialize_ref_bare_item(bare_item, output)
+        })?;
+        ser.has_members = true;
+        Ok(ser)
     }
 
     pub fn parameter(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
-        if self.buffer.is_empty() {
+        let ser = self.check_error()?;
+        if !ser.has_members {
             return Err("parameters must be serialized after bare item or inner list");
         }
-        Serializer::serialize_ref_parameter(name, value, self.buffer)?;
-        Ok(RefListSerializer {
-            buffer: self.buffer,
-        })
+        ser.buffer
+            .append(|output| Serializer::serialize_ref_parameter(name, value, output))?;
+        Ok(ser)
     }
-    pub fn open_inner_list(self) -> RefInnerListSerializer<'a, Self> {
-        if !self.buffer.is_empty() {
-            self.buffer.push_str(", ");
-        }
-        self.buffer.push('(');
-        RefInnerListSerializer::<RefListSerializer> {
+
+    /// Opens an inner list. An error writing to the output is returned by the next fallible call, or by `finish`.
+    pub fn open_inner_list(self) -> RefInnerListSerializer<'a, Self, O> {
+        let has_members = self.has_members;
+        let error = self.error.or_else(|| {
+            self.buffer
+                .append(|output| {
+                    if has_members {
+                        output.push_str(", ");
+                    }
+                    output.push('(');
+                    Ok(())
+                })
+                .err()
+        });
+        RefInnerListSerializer {
             buffer: self.buffer,
+            has_items: false,
+            keys: None,
+            error,
             caller_type: PhantomData,
         }
     }
+
+    /// Returns an error if writing to the output failed in `open_inner_list` or `close_inner_list`.
+    pub fn finish(self) -> SFVResult<()> {
+        self.check_error().map(|_| ())
+    }
+
+    fn check_error(self) -> SFVResult<Self> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
 }
 
 /// Serializes `Dictionary` field value components incrementally.
+/// Output is written to `String`, or to any `fmt::Write` wrapped in `FmtWriter`.
 /// ```
 /// use sfv::{RefBareItem, RefDictSerializer, Decimal, FromPrimitive};
 ///
@@ -146,99 +318,268 @@ impl<'a> RefListSerializer<'a> {
 /// );
 /// ```
 #[derive(Debug)]
-pub struct RefDictSerializer<'a> {
-    buffer: &'a mut String,
+pub struct RefDictSerializer<'a, O = String> {
+    buffer: &'a mut O,
+    has_members: bool,
+    keys: SerializedKeys,
+    // Output error of an infallible call, returned by the next fallible one
+    error: Option<&'static str>,
 }
 
 impl<'a> RefDictSerializer<'a> {
+    /// Returns `RefDictSerializer` appending members to `buffer`.
+    /// If `buffer` isn't empty, the first member is separated from its content with a comma,
+    /// so that serialization can continue after members serialized earlier.
     pub fn new(buffer: &'a mut String) -> Self {
-        RefDictSerializer { buffer }
+        let has_members = !buffer.is_empty();
+        RefDictSerializer {
+            buffer,
+            has_members,
+            keys: None,
+            error: None,
+        }
     }
 
-    pub fn bare_item_member(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
-        if !self.buffer.is_empty() {
-            self.buffer.push_str(", ");
+    /// Returns `RefDictSerializer` that returns an error instead of serializing a member with a key it already serialized.
+    /// Only members serialized by this serializer and the ones it returns are checked,
+    /// and members undone with `rollback` are forgotten.
+    /// ```
+    /// use sfv::{RefBareItem, RefDictSerializer};
+    ///
+    /// let mut serialized_dict = String::new();
+    /// let result = RefDictSerializer::new_checked(&mut serialized_dict)
+    ///     .bare_item_member("a", &RefBareItem::Integer(1))
+    ///     .unwrap()
+    ///     .bare_item_member("a", &RefBareItem::Integer(2));
+    /// assert_eq!(result.map(|_| ()), Err("serialize_dictionary: duplicate key"));
+    /// assert_eq!(serialized_dict, "a=1");
+    /// ```
+    pub fn new_checked(buffer: &'a mut String) -> Self {
+        RefDictSerializer {
+            keys: Some(IndexSet::new()),
+            ..RefDictSerializer::new(buffer)
         }
-        Serializer::serialize_key(name, self.buffer)?;
-        if value != &RefBareItem::Boolean(true) {
-            self.buffer.push('=');
-            Serializer::serialize_ref_bare_item(value, self.buffer)?;
+    }
+
+    /// Records the current end of the serialized output.
+    /// ```
+    /// use sfv::{RefBareItem, RefDictSerializer};
+    ///
+    /// let mut serialized_dict = String::new();
+    /// let serializer = RefDictSerializer::new(&mut serialized_dict)
+    ///     .bare_item_member("a", &RefBareItem::Integer(1))
+    ///     .unwrap();
+    /// let checkpoint = serializer.checkpoint();
+    /// serializer
+    ///     .bare_item_member("b", &RefBareItem::Integer(2))
+    ///     .unwrap()
+    ///     .parameter("q", &RefBareItem::Integer(1))
+    ///     .unwrap()
+    ///     .rollback(checkpoint);
+    /// assert_eq!(serialized_dict, "a=1");
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.buffer.len(),
+            keys: self.keys.as_ref().map_or(0, IndexSet::len),
         }
-        Ok(self)
+    }
+
+    /// Removes everything serialized after `checkpoint` was recorded.
+    pub fn rollback(mut self, checkpoint: Checkpoint) -> Self {
+        checkpoint.restore(self.buffer);
+        self.has_members = !self.buffer.is_empty();
+        if let Some(keys) = &mut self.keys {
+            keys.truncate(checkpoint.keys);
+        }
+        self
+    }
+}
+
+impl<'a, O: Output> RefDictSerializer<'a, O> {
+    /// Returns `RefDictSerializer` writing a new field value to `writer`, after anything it already holds.
+    /// See `RefListSerializer::with_writer`.
+    pub fn with_writer(writer: &'a mut O) -> Self {
+        RefDictSerializer {
+            buffer: writer,
+            has_members: false,
+            keys: None,
+            error: None,
+        }
+    }
+
+    // Checks that the key of a member about to be serialized is new, if keys are checked
+    fn check_key(&self, name: &str) -> SFVResult<()> {
+        match &self.keys {
+            Some(keys) if keys.contains(name) => Err("serialize_dictionary: duplicate key"),
+            _ => Ok(()),
+        }
+    }
+
+    // Records the key of a serialized member, if keys are checked
+    fn record_key(&mut self, name: &str) {
+        if let Some(keys) = &mut self.keys {
+            keys.insert(name.to_owned());
+        }
+        self.has_members = true;
+    }
+
+    pub fn bare_item_member(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
+        let mut ser = self.check_error()?;
+        ser.check_key(name)?;
+        let has_members = ser.has_members;
+        ser.buffer.append(|output| {
+            if has_members {
+                output.push_str(", ");
+            }
+            Serializer::serialize_key(name, output)?;
+            if value != &RefBareItem::Boolean(true) {
+                output.push('=');
+                Serializer::serialize_ref_bare_item(value, output)?;
+            }
+            Ok(())
+        })?;
+        ser.record_key(name);
+        Ok(ser)
     }
 
     pub fn parameter(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
-        if self.buffer.is_empty() {
+        let ser = self.check_error()?;
+        if !ser.has_members {
             return Err("parameters must be serialized after bare item or inner list");
         }
-        Serializer::serialize_ref_parameter(name, value, self.buffer)?;
-        Ok(RefDictSerializer {
-            buffer: self.buffer,
-        })
+        ser.buffer
+            .append(|output| Serializer::serialize_ref_parameter(name, value, output))?;
+        Ok(ser)
     }
 
-    pub fn open_inner_list(self, name: &str) -> SFVResult<RefInnerListSerializer<'a, Self>> {
-        if !self.buffer.is_empty() {
-            self.buffer.push_str(", ");
-        }
-        Serializer::serialize_key(name, self.buffer)?;
-        self.buffer.push_str("=(");
-        Ok(RefInnerListSerializer::<RefDictSerializer> {
-            buffer: self.buffer,
+    pub fn open_inner_list(self, name: &str) -> SFVResult<RefInnerListSerializer<'a, Self, O>> {
+        let mut ser = self.check_error()?;
+        ser.check_key(name)?;
+        let has_members = ser.has_members;
+        ser.buffer.append(|output| {
+            if has_members {
+                output.push_str(", ");
+            }
+            Serializer::serialize_key(name, output)?;
+            output.push_str("=(");
+            Ok(())
+        })?;
+        ser.record_key(name);
+        Ok(RefInnerListSerializer {
+            buffer: ser.buffer,
+            has_items: false,
+            keys: ser.keys,
+            error: None,
             caller_type: PhantomData,
         })
     }
+
+    /// Returns an error if writing to the output failed in `close_inner_list`.
+    pub fn finish(self) -> SFVResult<()> {
+        self.check_error().map(|_| ())
+    }
+
+    fn check_error(self) -> SFVResult<Self> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
+}
+
+/// Position in the output of `RefListSerializer` or `RefDictSerializer`, used to undo members serialized after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    len: usize,
+    // Number of keys a checked `RefDictSerializer` had recorded
+    keys: usize,
+}
+
+impl Checkpoint {
+    /// Truncates `buffer` to the recorded position.
+    /// Serialization can then continue with a new serializer over the same buffer,
+    /// e.g. after a failed call consumed the previous one.
+    pub fn restore(self, buffer: &mut String) {
+        buffer.truncate(self.len);
+    }
 }
 
 /// Used by `RefItemSerializer`, `RefListSerializer`, `RefDictSerializer` to serialize `InnerList`.
 #[derive(Debug)]
-pub struct RefInnerListSerializer<'a, T> {
-    buffer: &'a mut String,
+pub struct RefInnerListSerializer<'a, T, O = String> {
+    buffer: &'a mut O,
+    has_items: bool,
+    keys: SerializedKeys,
+    // Output error of `open_inner_list`, returned by the next fallible call
+    error: Option<&'static str>,
     caller_type: PhantomData<T>,
 }
 
-impl<'a, T: Container<'a>> RefInnerListSerializer<'a, T> {
-    pub fn inner_list_bare_item(self, bare_item: &RefBareItem) -> SFVResult<Self> {
-        if !self.buffer.is_empty() & !self.buffer.ends_with('(') {
-            self.buffer.push(' ');
+impl<'a, T: Container<'a, O>, O: Output> RefInnerListSerializer<'a, T, O> {
+    pub fn inner_list_bare_item(mut self, bare_item: &RefBareItem) -> SFVResult<Self> {
+        if let Some(error) = self.error {
+            return Err(error);
         }
-        Serializer::serialize_ref_bare_item(bare_item, self.buffer)?;
-        Ok(RefInnerListSerializer {
-            buffer: self.buffer,
-            caller_type: PhantomData,
-        })
+        let has_items = self.has_items;
+        self.buffer.append(|output| {
+            if has_items {
+                output.push(' ');
+            }
+            Serializer::serialize_ref_bare_item(bare_item, output)
+        })?;
+        self.has_items = true;
+        Ok(self)
     }
 
     pub fn inner_list_parameter(self, name: &str, value: &RefBareItem) -> SFVResult<Self> {
-        if self.buffer.is_empty() {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if !self.has_items {
             return Err("parameters must be serialized after bare item or inner list");
         }
-        Serializer::serialize_ref_parameter(name, value, self.buffer)?;
-        Ok(RefInnerListSerializer {
-            buffer: self.buffer,
-            caller_type: PhantomData,
-        })
+        self.buffer
+            .append(|output| Serializer::serialize_ref_parameter(name, value, output))?;
+        Ok(self)
     }
 
+    /// Closes the inner list. An error writing to the output is returned by the next fallible call, or by `finish`.
     pub fn close_inner_list(self) -> T {
-        self.buffer.push(')');
-        T::new(self.buffer)
+        let error = self.error.or_else(|| {
+            self.buffer
+                .append(|output| {
+                    output.push(')');
+                    Ok(())
+                })
+                .err()
+        });
+        T::new(self.buffer, self.keys, error)
     }
 }
 
-pub trait Container<'a> {
-    fn new(buffer: &'a mut String) -> Self;
+pub trait Container<'a, O> {
+    fn new(buffer: &'a mut O, keys: SerializedKeys, error: Option<&'static str>) -> Self;
 }
 
-impl<'a> Container<'a> for RefListSerializer<'a> {
-    fn new(buffer: &mut String) -> RefListSerializer {
-        RefListSerializer { buffer }
+impl<'a, O: Output> Container<'a, O> for RefListSerializer<'a, O> {
+    fn new(buffer: &'a mut O, _keys: SerializedKeys, error: Option<&'static str>) -> Self {
+        RefListSerializer {
+            buffer,
+            has_members: true,
+            error,
+        }
     }
 }
 
-impl<'a> Container<'a> for RefDictSerializer<'a> {
-    fn new(buffer: &mut String) -> RefDictSerializer {
-        RefDictSerializer { buffer }
+impl<'a, O: Output> Container<'a, O> for RefDictSerializer<'a, O> {
+    fn new(buffer: &'a mut O, keys: SerializedKeys, error: Option<&'static str>) -> Self {
+        RefDictSerializer {
+            buffer,
+            has_members: true,
+            keys,
+            error,
+        }
     }
 }
 
@@ -307,4 +648,168 @@ mod alternative_serializer_tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_fast_serialize_rollback() -> SFVResult<()> {
+        let mut output = String::new();
+        let ser = RefListSerializer::new(&mut output);
+        let checkpoint = ser.checkpoint();
+        ser.open_inner_list()
+            .inner_list_bare_item(&RefBareItem::Integer(1))?
+            .close_inner_list()
+            .rollback(checkpoint)
+            .bare_item(&RefBareItem::Integer(2))?;
+        assert_eq!("2", output);
+
+        let mut output = String::new();
+        let checkpoint = RefDictSerializer::new(&mut output)
+            .bare_item_member("a", &RefBareItem::Integer(3))?
+            .checkpoint();
+        let result = RefDictSerializer::new(&mut output)
+            .bare_item_member("b", &RefBareItem::Integer(4))?
+            .open_inner_list("C");
+        assert!(result.is_err());
+        checkpoint.restore(&mut output);
+        RefDictSerializer::new(&mut output).bare_item_member("d", &RefBareItem::Integer(5))?;
+        assert_eq!("a=3, d=5", output);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fast_serialize_dict_checked() -> SFVResult<()> {
+        let mut output = String::new();
+        let result = RefDictSerializer::new_checked(&mut output)
+            .bare_item_member("a", &RefBareItem::Integer(1))?
+            .open_inner_list("b")?
+            .inner_list_bare_item(&RefBareItem::Integer(2))?
+            .close_inner_list()
+            .open_inner_list("a");
+        assert_eq!(
+            Err("serialize_dictionary: duplicate key"),
+            result.map(|_| ())
+        );
+        assert_eq!("a=1, b=(2)", output);
+
+        let mut output = String::new();
+        let ser = RefDictSerializer::new_checked(&mut output)
+            .bare_item_member("a", &RefBareItem::Integer(1))?;
+        let checkpoint = ser.checkpoint();
+        ser.bare_item_member("b", &RefBareItem::Integer(2))?
+            .rollback(checkpoint)
+            .bare_item_member("b", &RefBareItem::Integer(3))?;
+        assert_eq!("a=1, b=3", output);
+
+        let mut output = String::new();
+        RefDictSerializer::new(&mut output)
+            .bare_item_member("a", &RefBareItem::Integer(1))?
+            .bare_item_member("a", &RefBareItem::Integer(2))?;
+        assert_eq!("a=1, a=2", output);
+        Ok(())
+    }
+
+    // Writer holding at most `capacity` bytes
+    struct Fixed {
+        output: String,
+        capacity: usize,
+    }
+
+    impl fmt::Write for Fixed {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if self.output.len() + s.len() > self.capacity {
+                return Err(fmt::Error);
+            }
+            self.output.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fast_serialize_fmt_write() -> SFVResult<()> {
+        let mut writer = FmtWriter::new(Fixed {
+            output: "v: ".to_owned(),
+            capacity: 64,
+        });
+        RefDictSerializer::with_writer(&mut writer)
+            .bare_item_member("a", &RefBareItem::Integer(1))?
+            .open_inner_list("b")?
+            .inner_list_bare_item(&RefBareItem::Token("x"))?
+            .inner_list_bare_item(&RefBareItem::Token("y"))?
+            .inner_list_parameter("p", &RefBareItem::Boolean(true))?
+            .close_inner_list()
+            .parameter("q", &RefBareItem::Integer(2))?
+            .finish()?;
+        assert_eq!("v: a=1, b=(x y;p);q=2", writer.get_ref().output);
+
+        let mut output = writer.into_inner().output;
+        RefItemSerializer::new(&mut output)
+            .bare_item(&RefBareItem::Token("z"))?
+            .parameter("r", &RefBareItem::Boolean(false))?;
+        assert_eq!("v: a=1, b=(x y;p);q=2z;r=?0", output);
+
+        let mut writer = FmtWriter::new(Fixed {
+            output: String::new(),
+            capacity: 6,
+        });
+        let result = RefListSerializer::with_writer(&mut writer)
+            .bare_item(&RefBareItem::Integer(1))?
+            .bare_item(&RefBareItem::Token("abcdef"));
+        assert_eq!(
+            Err("serialize_fmt: failed to write output"),
+            result.map(|_| ())
+        );
+        // Failed calls write nothing
+        assert_eq!("1", writer.get_ref().output);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fast_serialize_deferred_write_error() -> SFVResult<()> {
+        // Errors of infallible calls are returned by the next fallible call
+        let mut writer = FmtWriter::new(Fixed {
+            output: String::new(),
+            capacity: 5,
+        });
+        let result = RefListSerializer::with_writer(&mut writer)
+            .bare_item(&RefBareItem::Integer(1234))?
+            .open_inner_list()
+            .inner_list_bare_item(&RefBareItem::Integer(1));
+        assert_eq!(
+            Err("serialize_fmt: failed to write output"),
+            result.map(|_| ())
+        );
+        assert_eq!("1234", writer.get_ref().output);
+
+        // or by `finish`, if no fallible call follows
+        let mut writer = FmtWriter::new(Fixed {
+            output: String::new(),
+            capacity: 5,
+        });
+        let result = RefDictSerializer::with_writer(&mut writer)
+            .open_inner_list("a")?
+            .inner_list_bare_item(&RefBareItem::Integer(12))?
+            .close_inner_list()
+            .finish();
+        assert_eq!(Err("serialize_fmt: failed to write output"), result);
+        assert_eq!("a=(12", writer.get_ref().output);
+
+        let mut output = String::new();
+        RefListSerializer::new(&mut output)
+            .open_inner_list()
+            .close_inner_list()
+            .finish()?;
+        assert_eq!("()", output);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fast_serialize_inner_list_parameter_without_item() {
+        let mut output = String::new();
+        let result = RefListSerializer::new(&mut output)
+            .open_inner_list()
+            .inner_list_parameter("a", &RefBareItem::Integer(1));
+        assert_eq!(
+            Err("parameters must be serialized after bare item or inner list"),
+            result.map(|_| ())
+        );
+    }
 }
@@ -0,0 +1,241 @@
+use crate::serializer::Serializer;
+use crate::{BareItem, Dictionary, List, ListEntry, Parameters, SFVResult};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Member of `List` or `Dictionary` passed to the callback of `SerializeRewritten`,
+/// allowing it to be dropped, renamed, or to have its parameters rewritten before it's serialized.
+/// The value being serialized is left unchanged.
+#[derive(Debug)]
+pub struct MemberRewrite<'a> {
+    index: usize,
+    name: Option<Cow<'a, str>>,
+    entry: &'a ListEntry,
+    params: Cow<'a, Parameters>,
+    dropped: bool,
+}
+
+impl<'a> MemberRewrite<'a> {
+    fn new(index: usize, name: Option<&'a str>, entry: &'a ListEntry) -> Self {
+        MemberRewrite {
+            index,
+            name: name.map(Cow::Borrowed),
+            entry,
+            params: Cow::Borrowed(entry.params()),
+            dropped: false,
+        }
+    }
+
+    /// Returns position of the member in `List` or `Dictionary`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns key the member is serialized with, or `None` for `List` members.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the member as it is in `List` or `Dictionary`.
+    pub fn entry(&self) -> &ListEntry {
+        self.entry
+    }
+
+    /// Returns parameters the member is serialized with.
+    pub fn params(&self) -> &Parameters {
+        &self.params
+    }
+
+    /// Returns mutable parameters the member is serialized with. They are copied on first call.
+    pub fn params_mut(&mut self) -> &mut Parameters {
+        self.params.to_mut()
+    }
+
+    /// Leaves the member out of the output.
+    pub fn drop_member(&mut self) {
+        self.dropped = true;
+    }
+
+    /// Serializes `Dictionary` member with `name` as its key. The key is validated when serialized,
+    /// and serialization fails if another member that isn't dropped has the same key.
+    /// Has no effect on `List` members.
+    pub fn rename<N: Into<String>>(&mut self, name: N) {
+        if self.name.is_some() {
+            self.name = Some(Cow::Owned(name.into()));
+        }
+    }
+
+    fn serialize(&self, output: &mut String) -> SFVResult<()> {
+        let bare_item = match self.entry {
+            ListEntry::Item(item) => Some(&item.bare_item),
+            ListEntry::InnerList(_) => None,
+        };
+        if let Some(name) = &self.name {
+            Serializer::serialize_key(name, output)?;
+            // Boolean true dictionary members are serialized as the key and parameters only
+            if bare_item == Some(&BareItem::Boolean(true)) {
+                return Serializer::serialize_parameters(&self.params, output);
+            }
+            output.push('=');
+        }
+
+        match self.entry {
+            ListEntry::Item(item) => Serializer::serialize_bare_item(&item.bare_item, output)?,
+            ListEntry::InnerList(inner_list) => {
+                output.push('(');
+                for (idx, item) in inner_list.items.iter().enumerate() {
+                    if idx > 0 {
+                        output.push(' ');
+                    }
+                    Serializer::serialize_item(item, output)?;
+                }
+                output.push(')');
+            }
+        }
+        Serializer::serialize_parameters(&self.params, output)
+    }
+}
+
+/// Serializes `List` or `Dictionary` while rewriting its members, without modifying or cloning it.
+pub trait SerializeRewritten {
+    /// Serializes members in order, calling `rewrite` on every member before it's serialized.
+    /// Returns an empty string if every member is dropped, in which case the field should be omitted.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeRewritten};
+    /// let dict = Parser::parse_dictionary("debug=1, hit;detail=x, ttl=30".as_bytes()).unwrap();
+    /// let value = dict
+    ///     .serialize_rewritten(|member| match member.name() {
+    ///         Some("debug") => member.drop_member(),
+    ///         Some("ttl") => member.rename("max-age"),
+    ///         _ => {
+    ///             member.params_mut().shift_remove("detail");
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(value, "hit, max-age=30");
+    /// ```
+    fn serialize_rewritten<F>(&self, rewrite: F) -> SFVResult<String>
+    where
+        F: FnMut(&mut MemberRewrite<'_>);
+}
+
+impl SerializeRewritten for List {
+    fn serialize_rewritten<F>(&self, rewrite: F) -> SFVResult<String>
+    where
+        F: FnMut(&mut MemberRewrite<'_>),
+    {
+        let members = self
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| MemberRewrite::new(idx, None, entry));
+        serialize_members(members, rewrite)
+    }
+}
+
+impl SerializeRewritten for Dictionary {
+    fn serialize_rewritten<F>(&self, rewrite: F) -> SFVResult<String>
+    where
+        F: FnMut(&mut MemberRewrite<'_>),
+    {
+        let members = self
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, entry))| MemberRewrite::new(idx, Some(name), entry));
+        serialize_members(members, rewrite)
+    }
+}
+
+fn serialize_members<'a, I, F>(members: I, mut rewrite: F) -> SFVResult<String>
+where
+    I: Iterator<Item = MemberRewrite<'a>>,
+    F: FnMut(&mut MemberRewrite<'_>),
+{
+    let mut output = String::new();
+    let mut written = 0;
+    let mut names = HashSet::new();
+    for mut member in members {
+        rewrite(&mut member);
+        if member.dropped {
+            continue;
+        }
+        if let Some(name) = &member.name {
+            if !names.insert(name.clone()) {
+                return Err("serialize_dictionary: duplicate key");
+            }
+        }
+        if written > 0 {
+            output.push_str(", ");
+        }
+        member.serialize(&mut output)?;
+        written += 1;
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, SerializeValue};
+
+    #[test]
+    fn serialize_rewritten_list() -> SFVResult<()> {
+        let list = Parser::parse_list("a;x=1, (b c);x=2, d".as_bytes())?;
+        let value = list.serialize_rewritten(|member| {
+            if member.index() == 2 {
+                member.drop_member();
+            }
+            member
+                .params_mut()
+                .insert("y".to_owned(), BareItem::Boolean(true));
+            member.rename("ignored");
+        })?;
+        assert_eq!("a;x=1;y, (b c);x=2;y", value);
+        assert_eq!("a;x=1, (b c);x=2, d", list.serialize_value()?);
+
+        let value = list.serialize_rewritten(|member| member.drop_member())?;
+        assert!(value.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_rewritten_dictionary() -> SFVResult<()> {
+        let dict = Parser::parse_dictionary("a, b=?0;p, c=(1 2)".as_bytes())?;
+        assert_eq!(dict.serialize_value()?, dict.serialize_rewritten(|_| {})?);
+
+        let value = dict.serialize_rewritten(|member| {
+            let name = member.name().map(|name| format!("x-{}", name));
+            member.rename(name.unwrap());
+            member.params_mut().clear();
+        })?;
+        assert_eq!("x-a, x-b=?0, x-c=(1 2)", value);
+
+        let result = dict.serialize_rewritten(|member| member.rename("Invalid"));
+        assert_eq!(Err("serialize_key: disallowed character in input"), result);
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_rewritten_dictionary_duplicate_key() -> SFVResult<()> {
+        let dict = Parser::parse_dictionary("hit, ttl=30".as_bytes())?;
+        let result = dict.serialize_rewritten(|member| {
+            if member.name() == Some("ttl") {
+                member.rename("hit");
+            }
+        });
+        assert_eq!(Err("serialize_dictionary: duplicate key"), result);
+
+        // Renaming onto the key of a dropped member, or swapping keys, is fine
+        let value = dict.serialize_rewritten(|member| match member.name() {
+            Some("hit") => member.drop_member(),
+            _ => member.rename("hit"),
+        })?;
+        assert_eq!("hit=30", value);
+        let value = dict.serialize_rewritten(|member| match member.name() {
+            Some("hit") => member.rename("ttl"),
+            _ => member.rename("hit"),
+        })?;
+        assert_eq!("ttl, hit=30", value);
+        Ok(())
+    }
+}
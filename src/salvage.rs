@@ -0,0 +1,186 @@
+use crate::parsed::member_spans;
+use crate::{Dictionary, List, ParseOptions, Parser, SFVResult};
+use std::ops::Range;
+
+/// Top-level member left out by `Parser::parse_*_lenient` methods because it failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skipped {
+    /// Byte range of the member in the input, excluding whitespace the strict parser would skip around it.
+    pub span: Range<usize>,
+    /// Error the member failed to parse with.
+    pub error: &'static str,
+}
+
+/// Value parsed by `Parser::parse_*_lenient` methods, along with the members that were skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Salvaged<T> {
+    value: T,
+    skipped: Vec<Skipped>,
+}
+
+impl<T> Salvaged<T> {
+    /// Returns the value made of the members that parsed.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes `Salvaged`, returning the value made of the members that parsed.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns members that failed to parse, in input order.
+    pub fn skipped(&self) -> &[Skipped] {
+        &self.skipped
+    }
+
+    /// Returns whether every member parsed, i.e. whether strict parsing would have succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+impl Parser {
+    /// Parses input into structured field value of List type, skipping members that fail to parse
+    /// instead of failing as a whole, for consumers that prefer partial values over none.
+    /// Input is split into members at commas outside of strings, so a member with an unterminated string
+    /// extends to the end of input. Empty members, e.g. after a trailing comma, are skipped as well.
+    /// Use `Parser::parse_list` where the specification requires the whole field to be ignored on failure.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let salvaged = Parser::parse_list_lenient("a, b c, \"x,y\";q=1, ?2".as_bytes());
+    /// assert_eq!(salvaged.value().serialize_value().unwrap(), "a, \"x,y\";q=1");
+    ///
+    /// let skipped = salvaged.skipped();
+    /// assert_eq!(skipped[0].span, 3..6);
+    /// assert_eq!(skipped[0].error, "parse: trailing characters after parsed value");
+    /// assert_eq!(skipped[1].span, 19..21);
+    /// assert_eq!(skipped[1].error, "parse_bool: invalid variant");
+    /// ```
+    pub fn parse_list_lenient(input_bytes: &[u8]) -> Salvaged<List> {
+        let mut list = List::new();
+        let skipped = parse_members(input_bytes, |member| {
+            let entry = Parser::parse_with(member, |input_chars| {
+                Parser::parse_list_entry(input_chars, &ParseOptions::default())
+            })?;
+            list.push(entry);
+            Ok(())
+        });
+        Salvaged {
+            value: list,
+            skipped,
+        }
+    }
+
+    /// Parses input into structured field value of Dictionary type, skipping members that fail to parse
+    /// instead of failing as a whole. Members with duplicate keys replace earlier ones, keeping their position.
+    /// See `parse_list_lenient` for details.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let salvaged = Parser::parse_dictionary_lenient("a=1, B=2, c=(1 2".as_bytes());
+    /// assert_eq!(salvaged.value().serialize_value().unwrap(), "a=1");
+    /// assert_eq!(salvaged.skipped().len(), 2);
+    /// assert!(!salvaged.is_complete());
+    /// ```
+    pub fn parse_dictionary_lenient(input_bytes: &[u8]) -> Salvaged<Dictionary> {
+        let mut dict = Dictionary::new();
+        let skipped = parse_members(input_bytes, |member| {
+            let (key, entry) = Parser::parse_with(member, |input_chars| {
+                Parser::parse_dict_member(input_chars, &ParseOptions::default())
+            })?;
+            dict.insert(key, entry);
+            Ok(())
+        });
+        Salvaged {
+            value: dict,
+            skipped,
+        }
+    }
+}
+
+// Calls `parse_member` with every top-level member of input, returning the ones it failed on
+fn parse_members<F>(input_bytes: &[u8], mut parse_member: F) -> Vec<Skipped>
+where
+    F: FnMut(&[u8]) -> SFVResult<()>,
+{
+    member_spans(input_bytes)
+        .into_iter()
+        .filter_map(|span| match parse_member(&input_bytes[span.clone()]) {
+            Ok(()) => None,
+            Err(error) => Some(Skipped { span, error }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerializeValue;
+
+    #[test]
+    fn parse_list_lenient_skips_invalid_members() -> SFVResult<()> {
+        let input = "1, (a b;x=?), , 2;p, ü, 3,";
+        let salvaged = Parser::parse_list_lenient(input.as_bytes());
+        assert_eq!("1, 2;p, 3", salvaged.value().serialize_value()?);
+        let spans: Vec<_> = salvaged.skipped().iter().map(|s| s.span.clone()).collect();
+        assert_eq!(vec![3..12, 14..14, 21..23, 27..27], spans);
+        assert_eq!(
+            "parse: non-ascii characters in input",
+            salvaged.skipped()[2].error
+        );
+        assert_eq!("parse_bare_item: empty item", salvaged.skipped()[3].error);
+
+        let input = "a, (b c);q=1";
+        let salvaged = Parser::parse_list_lenient(input.as_bytes());
+        assert!(salvaged.is_complete());
+        assert_eq!(Parser::parse_list(input.as_bytes())?, salvaged.into_value());
+
+        // Only SP may precede the first member, while OWS surrounds the others
+        let salvaged = Parser::parse_list_lenient("\t1".as_bytes());
+        assert!(Parser::parse_list("\t1".as_bytes()).is_err());
+        assert_eq!(
+            &[Skipped {
+                span: 0..2,
+                error: "parse_bare_item: item type can't be identified"
+            }][..],
+            salvaged.skipped()
+        );
+        for input in &["1,\t2", " 1\t,\t2\t"] {
+            let salvaged = Parser::parse_list_lenient(input.as_bytes());
+            assert!(salvaged.is_complete());
+            assert_eq!(Parser::parse_list(input.as_bytes())?, salvaged.into_value());
+        }
+
+        let salvaged = Parser::parse_list_lenient("  ".as_bytes());
+        assert!(salvaged.is_complete());
+        assert!(salvaged.value().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_dictionary_lenient_skips_invalid_members() -> SFVResult<()> {
+        let input = "a=1, B=2, d;p=1, e=, a=4, f=\"x, g=5";
+        let salvaged = Parser::parse_dictionary_lenient(input.as_bytes());
+        assert_eq!("a=4, d;p=1", salvaged.value().serialize_value()?);
+        assert_eq!(
+            &[
+                Skipped {
+                    span: 5..8,
+                    error: "parse_key: first character is not lcalpha or '*'"
+                },
+                Skipped {
+                    span: 17..19,
+                    error: "parse_bare_item: empty item"
+                },
+                Skipped {
+                    span: 26..35,
+                    error: "parse_string: no closing '\"'"
+                },
+            ][..],
+            salvaged.skipped()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+/*!
+Adapters for `#[serde(with = "...")]` that store structured field values as their wire strings.
+
+Useful when a serde data structure, such as a JSON configuration, contains raw
+structured field values. Values are validated by parsing when deserialized
+and are serialized to their canonical form.
+
+```
+use serde::{Deserialize, Serialize};
+use sfv::{Dictionary, Item};
+
+#[derive(Serialize, Deserialize)]
+struct Config {
+    #[serde(with = "sfv::serde::item_string")]
+    cache_control: Item,
+    #[serde(with = "sfv::serde::dictionary_string")]
+    priority: Dictionary,
+}
+
+let config: Config = serde_json::from_str(r#"{"cache_control": "3600;private", "priority": "u=1,   i"}"#).unwrap();
+assert_eq!(config.cache_control.bare_item.as_int(), Some(3600));
+assert_eq!(
+    serde_json::to_string(&config).unwrap(),
+    r#"{"cache_control":"3600;private","priority":"u=1, i"}"#
+);
+
+let invalid = serde_json::from_str::<Config>(r#"{"cache_control": "?", "priority": "u=1"}"#);
+assert!(invalid.is_err());
+```
+*/
+
+use crate::serializer::Serializer as SFVSerializer;
+use crate::{
+    BareItem, Decimal, Dictionary, InnerList, Item, List, Parameters, Parser, SFVResult,
+    SerializeValue,
+};
+use ::serde::de::Error as _;
+use ::serde::ser::Error as _;
+use ::serde::{Deserialize, Deserializer, Serializer};
+use std::convert::TryFrom;
+
+fn serialize_string<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: SerializeValue,
+    S: Serializer,
+{
+    let output = value.serialize_value().map_err(S::Error::custom)?;
+    serializer.serialize_str(&output)
+}
+
+fn deserialize_string<'de, T, D, F>(deserializer: D, parse_value: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(&[u8]) -> Result<T, &'static str>,
+{
+    let input = String::deserialize(deserializer)?;
+    parse_value(input.as_bytes()).map_err(D::Error::custom)
+}
+
+/// (De)serializes `Item` as its wire string.
+pub mod item_string {
+    use super::*;
+
+    /// Serializes `Item` as a string.
+    pub fn serialize<S: Serializer>(value: &Item, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_string(value, serializer)
+    }
+
+    /// Deserializes a string and parses it as `Item`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Item, D::Error> {
+        deserialize_string(deserializer, Parser::parse_item)
+    }
+}
+
+/// (De)serializes `List` as its wire string.
+pub mod list_string {
+    use super::*;
+
+    /// Serializes `List` as a string.
+    #[allow(clippy::ptr_arg)]
+    pub fn serialize<S: Serializer>(value: &List, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_string(value, serializer)
+    }
+
+    /// Deserializes a string and parses it as `List`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<List, D::Error> {
+        deserialize_string(deserializer, Parser::parse_list)
+    }
+}
+
+/// (De)serializes `Dictionary` as its wire string.
+pub mod dictionary_string {
+    use super::*;
+
+    /// Serializes `Dictionary` as a string.
+    pub fn serialize<S: Serializer>(value: &Dictionary, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_string(value, serializer)
+    }
+
+    /// Deserializes a string and parses it as `Dictionary`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dictionary, D::Error> {
+        deserialize_string(deserializer, Parser::parse_dictionary)
+    }
+}
+
+// Deserialized forms of `BareItem`, `Item` and `InnerList`, checked as the serializer checks them
+// before being converted, so that deserialization can't produce values that can't be serialized
+
+#[derive(Deserialize)]
+pub(crate) enum BareItemData {
+    Decimal(Decimal),
+    Integer(i64),
+    String(String),
+    ByteSeq(Vec<u8>),
+    Boolean(bool),
+    Token(String),
+}
+
+impl TryFrom<BareItemData> for BareItem {
+    type Error = &'static str;
+
+    fn try_from(data: BareItemData) -> SFVResult<BareItem> {
+        let bare_item = match data {
+            BareItemData::Decimal(value) => BareItem::Decimal(value),
+            BareItemData::Integer(value) => BareItem::Integer(value),
+            BareItemData::String(value) => BareItem::String(value),
+            BareItemData::ByteSeq(value) => BareItem::ByteSeq(value),
+            BareItemData::Boolean(value) => BareItem::Boolean(value),
+            BareItemData::Token(value) => BareItem::Token(value),
+        };
+        SFVSerializer::serialize_bare_item(&bare_item, &mut String::new())?;
+        Ok(bare_item)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ItemData {
+    bare_item: BareItem,
+    params: Parameters,
+}
+
+impl TryFrom<ItemData> for Item {
+    type Error = &'static str;
+
+    fn try_from(data: ItemData) -> SFVResult<Item> {
+        check_keys(&data.params)?;
+        Ok(Item::with_params(data.bare_item, data.params))
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct InnerListData {
+    items: Vec<Item>,
+    params: Parameters,
+}
+
+impl TryFrom<InnerListData> for InnerList {
+    type Error = &'static str;
+
+    fn try_from(data: InnerListData) -> SFVResult<InnerList> {
+        check_keys(&data.params)?;
+        Ok(InnerList::with_params(data.items, data.params))
+    }
+}
+
+// Parameter values are `BareItem`, which are checked on their own
+fn check_keys(params: &Parameters) -> SFVResult<()> {
+    let mut output = String::new();
+    for key in params.keys() {
+        SFVSerializer::serialize_key(key, &mut output)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BareItem, Dictionary, Item, List, Parser};
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Stored {
+        #[serde(with = "crate::serde::list_string")]
+        list: List,
+    }
+
+    #[test]
+    fn list_string_round_trip() {
+        let stored: Stored = serde_json::from_str(r#"{"list":"1,  tok"}"#).unwrap();
+        let expected: List = vec![
+            Item::new(BareItem::Integer(1)).into(),
+            Item::new(BareItem::Token("tok".into())).into(),
+        ];
+        assert_eq!(expected, stored.list);
+        assert_eq!(
+            r#"{"list":"1, tok"}"#,
+            serde_json::to_string(&stored).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialization_errors_are_reported() {
+        let stored = Stored { list: vec![] };
+        let err = serde_json::to_string(&stored).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("serialize_list: serializing empty field is not allowed"));
+
+        let err = serde_json::from_str::<Stored>(r#"{"list":"1,"}"#).unwrap_err();
+        assert!(err.to_string().contains("parse_list: trailing comma"));
+    }
+
+    #[test]
+    fn parsed_types_round_trip() {
+        let dict = Parser::parse_dictionary("a=(1 2.5);p=:AQ==:, b=tok;q".as_bytes()).unwrap();
+        let json = serde_json::to_string(&dict).unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"a":{"InnerList":{"items":[{"bare_item":{"Integer":1},"params":{}},"#,
+                r#"{"bare_item":{"Decimal":"2.5"},"params":{}}],"params":{"p":{"ByteSeq":[1]}}}},"#,
+                r#""b":{"Item":{"bare_item":{"Token":"tok"},"params":{"q":{"Boolean":true}}}}}"#
+            ),
+            json
+        );
+        assert_eq!(dict, serde_json::from_str::<Dictionary>(&json).unwrap());
+    }
+
+    #[test]
+    fn invalid_values_are_not_deserialized() {
+        for (json, expected_err) in &[
+            (
+                r#"{"Token":"not a token"}"#,
+                "serialise_token: disallowed character",
+            ),
+            (
+                r#"{"Integer":1000000000000000}"#,
+                "serialize_integer: integer is out of range",
+            ),
+            (
+                r#"{"String":"tab\t"}"#,
+                "serialize_string: not a visible character",
+            ),
+        ] {
+            let err = serde_json::from_str::<BareItem>(json).unwrap_err();
+            assert!(err.to_string().contains(expected_err), "{}", err);
+        }
+
+        let json = r#"{"bare_item":{"Integer":1},"params":{"Key":{"Boolean":true}}}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err.to_string().contains("serialize_key"), "{}", err);
+
+        let json = r#"{"items":[{"bare_item":{"Token":"?"},"params":{}}],"params":{}}"#;
+        assert!(serde_json::from_str::<crate::InnerList>(json).is_err());
+    }
+}
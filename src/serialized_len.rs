@@ -0,0 +1,163 @@
+use crate::{BareItem, Dictionary, InnerList, Item, List, ListEntry, Parameters, RefBareItem};
+use data_encoding::BASE64;
+
+/// Computes the length of a structured field value's serialization without serializing it.
+pub trait SerializedLen {
+    /// Returns the number of bytes `SerializeValue` or the serializers would output for the value.
+    /// The value is not validated, so the result is meaningless for a value that fails to serialize,
+    /// e.g. an empty `List` or a token with disallowed characters.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializedLen, SerializeValue};
+    /// let dict = Parser::parse_dictionary("a=\"some\\\\string\",   b=(1 2);q=0.50".as_bytes()).unwrap();
+    /// assert_eq!(dict.serialized_len(), dict.serialize_value().unwrap().len());
+    /// ```
+    fn serialized_len(&self) -> usize;
+}
+
+impl SerializedLen for RefBareItem<'_> {
+    fn serialized_len(&self) -> usize {
+        match self {
+            RefBareItem::Integer(value) => integer_len(*value),
+            RefBareItem::Decimal(value) => {
+                // Same formatting as `Serializer::serialize_decimal`
+                let decimal = value.round_dp(3);
+                if decimal.fract().is_zero() {
+                    decimal.trunc().to_string().len() + ".0".len()
+                } else {
+                    decimal.to_string().len()
+                }
+            }
+            RefBareItem::String(value) => {
+                let escaped = value.chars().filter(|&c| c == '\\' || c == '\"').count();
+                value.len() + escaped + 2
+            }
+            RefBareItem::ByteSeq(value) => BASE64.encode_len(value.len()) + 2,
+            RefBareItem::Boolean(_) => 2,
+            RefBareItem::Token(value) => value.len(),
+        }
+    }
+}
+
+impl SerializedLen for BareItem {
+    fn serialized_len(&self) -> usize {
+        self.to_ref_bare_item().serialized_len()
+    }
+}
+
+impl SerializedLen for Parameters {
+    fn serialized_len(&self) -> usize {
+        self.iter()
+            .map(|(key, value)| {
+                // Boolean `true` parameters serialize as the key only
+                let value_len = match value {
+                    BareItem::Boolean(true) => 0,
+                    _ => value.serialized_len() + 1,
+                };
+                key.len() + value_len + 1
+            })
+            .sum()
+    }
+}
+
+impl SerializedLen for Item {
+    fn serialized_len(&self) -> usize {
+        self.bare_item.serialized_len() + self.params.serialized_len()
+    }
+}
+
+impl SerializedLen for InnerList {
+    fn serialized_len(&self) -> usize {
+        let items_len: usize = self.items.iter().map(Item::serialized_len).sum();
+        let separators_len = self.items.len().saturating_sub(1);
+        items_len + separators_len + self.params.serialized_len() + 2
+    }
+}
+
+impl SerializedLen for ListEntry {
+    fn serialized_len(&self) -> usize {
+        match self {
+            ListEntry::Item(item) => item.serialized_len(),
+            ListEntry::InnerList(inner_list) => inner_list.serialized_len(),
+        }
+    }
+}
+
+impl SerializedLen for List {
+    fn serialized_len(&self) -> usize {
+        let members_len: usize = self.iter().map(ListEntry::serialized_len).sum();
+        members_len + separators_len(self.len())
+    }
+}
+
+impl SerializedLen for Dictionary {
+    fn serialized_len(&self) -> usize {
+        let members_len: usize = self
+            .iter()
+            .map(|(key, member)| {
+                // Boolean `true` members serialize as the key and parameters only
+                let value_len = match member {
+                    ListEntry::Item(item) if item.bare_item == BareItem::Boolean(true) => {
+                        item.params.serialized_len()
+                    }
+                    _ => member.serialized_len() + 1,
+                };
+                key.len() + value_len
+            })
+            .sum();
+        members_len + separators_len(self.len())
+    }
+}
+
+fn integer_len(value: i64) -> usize {
+    let sign_len = if value < 0 { 1 } else { 0 };
+    let mut value = value.unsigned_abs();
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    sign_len + digits
+}
+
+// Members of `List` and `Dictionary` are separated by ", "
+fn separators_len(members: usize) -> usize {
+    members.saturating_sub(1) * 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Decimal, FromStr, Parser, SerializeValue};
+
+    #[test]
+    fn serialized_len_matches_serialization() -> Result<(), Box<dyn std::error::Error>> {
+        let lists = [
+            "1, -1, 0, 999999999999999, -999999999999999",
+            "1.5, -0.001, 12.0, 1.25;a, 0.0",
+            "\"\", \"a\\\"b\\\\c\", :: , :YQ==:, :YWJj:, ?0, ?1",
+            "tok, *, a:b/c;key=?1;k2=?0;k3=\"s\"",
+            "(), (1 2 3);p=1, (a;b c);d, ();e",
+        ];
+        for input in lists.iter() {
+            let list = Parser::parse_list(input.as_bytes())?;
+            assert_eq!(list.serialize_value()?.len(), list.serialized_len());
+        }
+
+        let dicts = ["a, b=?0, c;p, d=();q=2, e=?1;r=tok", "k=:AQID:;x=-1.5"];
+        for input in dicts.iter() {
+            let dict = Parser::parse_dictionary(input.as_bytes())?;
+            assert_eq!(dict.serialize_value()?.len(), dict.serialized_len());
+        }
+
+        for value in ["1.0004", "-0.0004", "2.50", "999999999999.9994"].iter() {
+            let item = Item::new(BareItem::Decimal(Decimal::from_str(value)?));
+            assert_eq!(item.serialize_value()?.len(), item.serialized_len());
+        }
+
+        assert_eq!(0, List::new().serialized_len());
+        assert_eq!(0, Dictionary::new().serialized_len());
+        assert_eq!(6, RefBareItem::ByteSeq(b"ab").serialized_len());
+        Ok(())
+    }
+}
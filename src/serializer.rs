@@ -1,9 +1,12 @@
-use crate::utils;
+use crate::chars;
 use crate::{
-    BareItem, Decimal, Dictionary, InnerList, Item, List, ListEntry, Parameters, RefBareItem,
-    SFVResult,
+    BareItem, Decimal, InnerList, Item, List, ListEntry, Parameters, RefBareItem, SFVResult,
 };
 use data_encoding::BASE64;
+use indexmap::IndexMap;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::io;
 
 /// Serializes structured field value into String.
 pub trait SerializeValue {
@@ -21,14 +24,154 @@ pub trait SerializeValue {
     /// );
     /// ```
     fn serialize_value(&self) -> SFVResult<String>;
+
+    /// Appends serialized structured field value to `output`, so one buffer can be reused for many values.
+    /// On error `output` is left as it was.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let items = ["a;q=1", "b", "\"c\""];
+    /// let mut output = String::with_capacity(16);
+    /// for item in items.iter() {
+    ///     output.clear();
+    ///     Parser::parse_item(item.as_bytes()).unwrap().serialize_value_into(&mut output).unwrap();
+    ///     assert_eq!(&output, item);
+    /// }
+    /// ```
+    fn serialize_value_into(&self, output: &mut String) -> SFVResult<()> {
+        output.push_str(&self.serialize_value()?);
+        Ok(())
+    }
+
+    /// Serializes structured field value into bytes, ready to be written to the wire.
+    /// Serialized values are always ASCII, so the bytes are taken from the `String` without copying.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let item = Parser::parse_item("gzip;q=1".as_bytes()).unwrap();
+    /// assert_eq!(item.serialize_value_bytes().unwrap(), b"gzip;q=1");
+    /// ```
+    fn serialize_value_bytes(&self) -> SFVResult<Vec<u8>> {
+        self.serialize_value().map(String::into_bytes)
+    }
+
+    /// Serializes structured field value into `http::HeaderValue`.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let dict = Parser::parse_dictionary("u=2,  i".as_bytes()).unwrap();
+    /// assert_eq!(dict.serialize_header_value().unwrap(), "u=2, i");
+    /// ```
+    #[cfg(feature = "http")]
+    fn serialize_header_value(&self) -> SFVResult<http::HeaderValue> {
+        let output = self.serialize_value()?;
+        http::HeaderValue::from_str(&output)
+            .map_err(|_| "serialize_header_value: invalid header value")
+    }
+
+    /// Serializes structured field value into `writer`.
+    /// `List` and `Dictionary` are written member by member without building the whole `String`,
+    /// so members preceding an invalid one may already be written when an error is returned.
+    /// Serialization errors are returned as `io::ErrorKind::InvalidData`.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let list = Parser::parse_list("a,  (b c);d".as_bytes()).unwrap();
+    /// let mut output = Vec::new();
+    /// list.serialize_into(&mut output).unwrap();
+    /// assert_eq!(output, b"a, (b c);d");
+    /// ```
+    fn serialize_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        let output = self.serialize_value().map_err(invalid_data)?;
+        writer.write_all(output.as_bytes())
+    }
+
+    /// Serializes structured field value into `writer`, e.g. a fixed size buffer or a `fmt::Formatter`.
+    /// Like `serialize_into`, writes `List` and `Dictionary` member by member.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let dict = Parser::parse_dictionary("a=1,   b".as_bytes()).unwrap();
+    /// let mut output = String::from("Example: ");
+    /// dict.serialize_fmt(&mut output).unwrap();
+    /// assert_eq!(output, "Example: a=1, b");
+    /// ```
+    fn serialize_fmt<W: fmt::Write>(&self, writer: &mut W) -> SFVResult<()>
+    where
+        Self: Sized,
+    {
+        let output = self.serialize_value()?;
+        writer.write_str(&output).map_err(|_| WRITE_FMT_ERROR)
+    }
+
+    /// Serializes structured field value into String without the space after commas separating `List` and `Dictionary` members.
+    ///
+    /// The output parses to the same value, but it is not the canonical serialization defined by RFC 8941,
+    /// so use it only where the receivers are known to parse field values, rather than e.g. comparing them as strings.
+    /// Spaces separating `InnerList` items are required, so they are kept.
+    /// Other values are serialized as `serialize_value` does.
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// let list = Parser::parse_list("a;q=1, (b c), :AQ==:".as_bytes()).unwrap();
+    /// assert_eq!(list.serialize_value_compact().unwrap(), "a;q=1,(b c),:AQ==:");
+    /// ```
+    fn serialize_value_compact(&self) -> SFVResult<String> {
+        self.serialize_value()
+    }
+}
+
+pub(crate) const WRITE_FMT_ERROR: &str = "serialize_fmt: failed to write output";
+
+fn invalid_data(err: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
 }
 
-impl SerializeValue for Dictionary {
+impl<S: BuildHasher> SerializeValue for IndexMap<String, ListEntry, S> {
     fn serialize_value(&self) -> SFVResult<String> {
         let mut output = String::new();
         Serializer::serialize_dict(self, &mut output)?;
         Ok(output)
     }
+
+    fn serialize_value_into(&self, output: &mut String) -> SFVResult<()> {
+        Serializer::append(output, |output| Serializer::serialize_dict(self, output))
+    }
+
+    fn serialize_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        Serializer::write_dict_members(
+            self,
+            ", ",
+            |member| writer.write_all(member.as_bytes()),
+            invalid_data,
+        )
+    }
+
+    fn serialize_fmt<W: fmt::Write>(&self, writer: &mut W) -> SFVResult<()> {
+        Serializer::write_dict_members(
+            self,
+            ", ",
+            |member| writer.write_str(member).map_err(|_| WRITE_FMT_ERROR),
+            |err| err,
+        )
+    }
+
+    fn serialize_value_compact(&self) -> SFVResult<String> {
+        let mut output = String::new();
+        Serializer::write_dict_members(
+            self,
+            ",",
+            |member| {
+                output.push_str(member);
+                Ok(())
+            },
+            |err| err,
+        )?;
+        Ok(output)
+    }
 }
 
 impl SerializeValue for List {
@@ -37,6 +180,42 @@ impl SerializeValue for List {
         Serializer::serialize_list(self, &mut output)?;
         Ok(output)
     }
+
+    fn serialize_value_into(&self, output: &mut String) -> SFVResult<()> {
+        Serializer::append(output, |output| Serializer::serialize_list(self, output))
+    }
+
+    fn serialize_into<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        Serializer::write_list_members(
+            self,
+            ", ",
+            |member| writer.write_all(member.as_bytes()),
+            invalid_data,
+        )
+    }
+
+    fn serialize_fmt<W: fmt::Write>(&self, writer: &mut W) -> SFVResult<()> {
+        Serializer::write_list_members(
+            self,
+            ", ",
+            |member| writer.write_str(member).map_err(|_| WRITE_FMT_ERROR),
+            |err| err,
+        )
+    }
+
+    fn serialize_value_compact(&self) -> SFVResult<String> {
+        let mut output = String::new();
+        Serializer::write_list_members(
+            self,
+            ",",
+            |member| {
+                output.push_str(member);
+                Ok(())
+            },
+            |err| err,
+        )?;
+        Ok(output)
+    }
 }
 
 impl SerializeValue for Item {
@@ -45,12 +224,43 @@ impl SerializeValue for Item {
         Serializer::serialize_item(self, &mut output)?;
         Ok(output)
     }
+
+    fn serialize_value_into(&self, output: &mut String) -> SFVResult<()> {
+        Serializer::append(output, |output| Serializer::serialize_item(self, output))
+    }
+}
+
+/// Escapes `value` into its sf-string wire form, including the surrounding double quotes.
+/// Returns an error if `value` contains non-ASCII or non-visible characters.
+/// # Examples
+/// ```
+/// # use sfv::escape_string;
+/// assert_eq!(escape_string(r#"say "hi""#).unwrap(), r#""say \"hi\"""#);
+/// assert!(escape_string("tab\t").is_err());
+/// ```
+pub fn escape_string(value: &str) -> SFVResult<String> {
+    let mut output = String::with_capacity(value.len() + 2);
+    Serializer::serialize_string(value, &mut output)?;
+    Ok(output)
 }
 
 /// Container serialization functions
 pub(crate) struct Serializer;
 
 impl Serializer {
+    // Runs `serialize` on `output`, removing anything it appended if it fails
+    pub(crate) fn append(
+        output: &mut String,
+        serialize: impl FnOnce(&mut String) -> SFVResult<()>,
+    ) -> SFVResult<()> {
+        let len = output.len();
+        let result = serialize(output);
+        if result.is_err() {
+            output.truncate(len);
+        }
+        result
+    }
+
     pub(crate) fn serialize_item(input_item: &Item, output: &mut String) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc8941.html#ser-item
 
@@ -67,14 +277,7 @@ impl Serializer {
         }
 
         for (idx, member) in input_list.iter().enumerate() {
-            match member {
-                ListEntry::Item(item) => {
-                    Self::serialize_item(item, output)?;
-                }
-                ListEntry::InnerList(inner_list) => {
-                    Self::serialize_inner_list(inner_list, output)?;
-                }
-            };
+            Self::serialize_list_member(member, output)?;
 
             // If more items remain in input_list:
             //      Append “,” to output.
@@ -86,31 +289,17 @@ impl Serializer {
         Ok(())
     }
 
-    pub(crate) fn serialize_dict(input_dict: &Dictionary, output: &mut String) -> SFVResult<()> {
+    pub(crate) fn serialize_dict<S: BuildHasher>(
+        input_dict: &IndexMap<String, ListEntry, S>,
+        output: &mut String,
+    ) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc8941.html#ser-dictionary
         if input_dict.is_empty() {
             return Err("serialize_dictionary: serializing empty field is not allowed");
         }
 
         for (idx, (member_name, member_value)) in input_dict.iter().enumerate() {
-            Serializer::serialize_key(member_name, output)?;
-
-            match member_value {
-                ListEntry::Item(ref item) => {
-                    // If dict member is boolean true, no need to serialize it: only its params must be serialized
-                    // Otherwise serialize entire item with its params
-                    if item.bare_item == BareItem::Boolean(true) {
-                        Self::serialize_parameters(&item.params, output)?;
-                    } else {
-                        output.push('=');
-                        Self::serialize_item(item, output)?;
-                    }
-                }
-                ListEntry::InnerList(inner_list) => {
-                    output.push('=');
-                    Self::serialize_inner_list(inner_list, output)?;
-                }
-            }
+            Self::serialize_dict_member(member_name, member_value, output)?;
 
             // If more items remain in input_dictionary:
             //      Append “,” to output.
@@ -122,6 +311,92 @@ impl Serializer {
         Ok(())
     }
 
+    // Serializes `List` member by member, passing each one to `write` preceded by `separator`,
+    // so output can be streamed without holding the whole serialized `List`
+    #[allow(clippy::ptr_arg)]
+    fn write_list_members<E>(
+        input_list: &List,
+        separator: &str,
+        mut write: impl FnMut(&str) -> Result<(), E>,
+        map_err: fn(&'static str) -> E,
+    ) -> Result<(), E> {
+        if input_list.is_empty() {
+            return Err(map_err(
+                "serialize_list: serializing empty field is not allowed",
+            ));
+        }
+
+        // Buffer is reused for every member
+        let mut output = String::new();
+        for (idx, member) in input_list.iter().enumerate() {
+            output.clear();
+            if idx > 0 {
+                output.push_str(separator);
+            }
+            Self::serialize_list_member(member, &mut output).map_err(map_err)?;
+            write(&output)?;
+        }
+        Ok(())
+    }
+
+    // Same as `write_list_members`, for `Dictionary`
+    fn write_dict_members<S: BuildHasher, E>(
+        input_dict: &IndexMap<String, ListEntry, S>,
+        separator: &str,
+        mut write: impl FnMut(&str) -> Result<(), E>,
+        map_err: fn(&'static str) -> E,
+    ) -> Result<(), E> {
+        if input_dict.is_empty() {
+            return Err(map_err(
+                "serialize_dictionary: serializing empty field is not allowed",
+            ));
+        }
+
+        let mut output = String::new();
+        for (idx, (member_name, member_value)) in input_dict.iter().enumerate() {
+            output.clear();
+            if idx > 0 {
+                output.push_str(separator);
+            }
+            Self::serialize_dict_member(member_name, member_value, &mut output).map_err(map_err)?;
+            write(&output)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn serialize_list_member(member: &ListEntry, output: &mut String) -> SFVResult<()> {
+        match member {
+            ListEntry::Item(item) => Self::serialize_item(item, output),
+            ListEntry::InnerList(inner_list) => Self::serialize_inner_list(inner_list, output),
+        }
+    }
+
+    pub(crate) fn serialize_dict_member(
+        member_name: &str,
+        member_value: &ListEntry,
+        output: &mut String,
+    ) -> SFVResult<()> {
+        Serializer::serialize_key(member_name, output)?;
+
+        match member_value {
+            ListEntry::Item(ref item) => {
+                // If dict member is boolean true, no need to serialize it: only its params must be serialized
+                // Otherwise serialize entire item with its params
+                if item.bare_item == BareItem::Boolean(true) {
+                    Self::serialize_parameters(&item.params, output)?;
+                } else {
+                    output.push('=');
+                    Self::serialize_item(item, output)?;
+                }
+            }
+            ListEntry::InnerList(inner_list) => {
+                output.push('=');
+                Self::serialize_inner_list(inner_list, output)?;
+            }
+        }
+        Ok(())
+    }
+
     fn serialize_inner_list(input_inner_list: &InnerList, output: &mut String) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc8941.html#ser-innerlist
 
@@ -197,15 +472,12 @@ impl Serializer {
     pub(crate) fn serialize_key(input_key: &str, output: &mut String) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc8941.html#ser-key
 
-        let disallowed_chars =
-            |c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || "_-*.".contains(c));
-
-        if input_key.chars().any(disallowed_chars) {
+        if input_key.chars().any(|c| !chars::is_allowed_key_char(c)) {
             return Err("serialize_key: disallowed character in input");
         }
 
         if let Some(char) = input_key.chars().next() {
-            if !(char.is_ascii_lowercase() || char == '*') {
+            if !chars::is_allowed_key_first_char(char) {
                 return Err("serialize_key: first character is not lcalpha or '*'");
             }
         }
@@ -257,8 +529,7 @@ impl Serializer {
             return Err("serialize_string: non-ascii character");
         }
 
-        let vchar_or_sp = |char| char == '\x7f' || ('\x00'..='\x1f').contains(&char);
-        if value.chars().any(vchar_or_sp) {
+        if value.chars().any(|c| !chars::is_allowed_string_char(c)) {
             return Err("serialize_string: not a visible character");
         }
 
@@ -281,17 +552,14 @@ impl Serializer {
             return Err("serialize_string: non-ascii character");
         }
 
-        let mut chars = value.chars();
-        if let Some(char) = chars.next() {
-            if !(char.is_ascii_alphabetic() || char == '*') {
+        let mut value_chars = value.chars();
+        if let Some(char) = value_chars.next() {
+            if !chars::is_allowed_token_first_char(char) {
                 return Err("serialise_token: first character is not ALPHA or '*'");
             }
         }
 
-        if chars
-            .clone()
-            .any(|c| !(utils::is_tchar(c) || c == ':' || c == '/'))
-        {
+        if value_chars.any(|c| !chars::is_allowed_token_char(c)) {
             return Err("serialise_token: disallowed character");
         }
 
@@ -0,0 +1,223 @@
+use crate::events::{self, FieldKind};
+use crate::utils;
+use crate::{Event, SFVResult};
+use std::collections::VecDeque;
+use std::str::from_utf8;
+
+/// Parses List or Dictionary field value received in chunks, emitting `Event`s of each member as soon as it's complete.
+///
+/// A member is complete once the comma following it is fed, so only the current member is buffered.
+/// After an error, the parser keeps returning it.
+/// # Examples
+/// ```
+/// # use sfv::{BareItem, Event, StreamingParser};
+/// let mut events = vec![];
+/// let mut parser = StreamingParser::list(|event| events.push(event));
+/// parser.feed("1, \"a,".as_bytes()).unwrap();
+/// parser.feed("b\";q, (x".as_bytes()).unwrap();
+/// parser.feed(" y)".as_bytes()).unwrap();
+/// parser.finish().unwrap();
+///
+/// assert_eq!(events[0], Event::BareItem(BareItem::Integer(1)));
+/// assert_eq!(events[1], Event::BareItem(BareItem::String("a,b".into())));
+/// assert_eq!(events.len(), 8);
+/// ```
+pub struct StreamingParser<F> {
+    field_type: FieldKind,
+    on_event: F,
+    // Input following the last complete member
+    buffer: String,
+    // Scanning state of `buffer`, up to `scanned` bytes
+    scanned: usize,
+    in_string: bool,
+    escaped: bool,
+    members: usize,
+    error: Option<&'static str>,
+}
+
+impl<F: FnMut(Event)> StreamingParser<F> {
+    /// Returns new `StreamingParser` of List type field value, passing its events to `on_event`.
+    pub fn list(on_event: F) -> StreamingParser<F> {
+        StreamingParser::new(FieldKind::List, on_event)
+    }
+
+    /// Returns new `StreamingParser` of Dictionary type field value, passing its events to `on_event`.
+    pub fn dictionary(on_event: F) -> StreamingParser<F> {
+        StreamingParser::new(FieldKind::Dictionary, on_event)
+    }
+
+    fn new(field_type: FieldKind, on_event: F) -> StreamingParser<F> {
+        StreamingParser {
+            field_type,
+            on_event,
+            buffer: String::new(),
+            scanned: 0,
+            in_string: false,
+            escaped: false,
+            members: 0,
+            error: None,
+        }
+    }
+
+    /// Feeds the next chunk of input, emitting events of members completed by it.
+    pub fn feed(&mut self, chunk: &[u8]) -> SFVResult<()> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let result = self.feed_chunk(chunk);
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+        result
+    }
+
+    /// Parses the remaining input as the last member, emitting its events.
+    /// Returns an error if the input fed so far is not a valid field value.
+    pub fn finish(mut self) -> SFVResult<()> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let tail = std::mem::take(&mut self.buffer);
+        self.parse_member(&tail, true)
+    }
+
+    fn feed_chunk(&mut self, chunk: &[u8]) -> SFVResult<()> {
+        // https://httpwg.org/specs/rfc8941.html#text-parse
+        if !chunk.is_ascii() {
+            return Err("parse: non-ascii characters in input");
+        }
+        let chunk = from_utf8(chunk).map_err(|_| "parse: conversion from bytes to str failed")?;
+        self.buffer.push_str(chunk);
+
+        // Outside of strings, commas only separate top-level members
+        while let Some(comma_idx) = self.find_separator() {
+            let member: String = self.buffer.drain(..=comma_idx).collect();
+            self.scanned = 0;
+            self.parse_member(&member, false)?;
+        }
+        Ok(())
+    }
+
+    fn find_separator(&mut self) -> Option<usize> {
+        let bytes = self.buffer.as_bytes();
+        while self.scanned < bytes.len() {
+            let idx = self.scanned;
+            self.scanned += 1;
+            match bytes[idx] {
+                _ if self.escaped => self.escaped = false,
+                b'\\' if self.in_string => self.escaped = true,
+                b'"' => self.in_string = !self.in_string,
+                b',' if !self.in_string => return Some(idx),
+                _ => (),
+            }
+        }
+        None
+    }
+
+    // Parses a member followed by a comma, or the last member if `is_last` is set.
+    fn parse_member(&mut self, member: &str, is_last: bool) -> SFVResult<()> {
+        let mut input_chars = member.chars().peekable();
+        if self.members == 0 {
+            utils::consume_sp_chars(&mut input_chars);
+        } else {
+            utils::consume_ows_chars(&mut input_chars);
+        }
+
+        let (trailing_characters, trailing_comma) = events::separator_errors(self.field_type);
+        if is_last && input_chars.peek().is_none() {
+            return match self.members {
+                0 => Ok(()),
+                _ => Err(trailing_comma),
+            };
+        }
+
+        let mut pending = VecDeque::new();
+        events::parse_member_events(&mut input_chars, self.field_type, &mut pending)?;
+        utils::consume_ows_chars(&mut input_chars);
+        match input_chars.next() {
+            None if is_last => (),
+            Some(',') if !is_last => (),
+            _ => return Err(trailing_characters),
+        }
+
+        self.members += 1;
+        for event in pending {
+            (self.on_event)(event);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BareItem, Parser};
+
+    fn stream_dictionary(chunks: &[&str]) -> SFVResult<Vec<Event>> {
+        let mut events = vec![];
+        let mut parser = StreamingParser::dictionary(|event| events.push(event));
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes())?;
+        }
+        parser.finish()?;
+        Ok(events)
+    }
+
+    #[test]
+    fn events_match_event_iterator() -> SFVResult<()> {
+        let input = "a=1,\tb=\"x,\\\"y\";p=?0, c=(1 2);q=tok, d";
+        let expected: SFVResult<Vec<Event>> = Parser::dictionary_events(input.as_bytes()).collect();
+        for split_at in 0..input.len() {
+            let (first, second) = input.split_at(split_at);
+            assert_eq!(expected, stream_dictionary(&[first, second]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn members_are_emitted_when_complete() {
+        let mut events = vec![];
+        let mut parser = StreamingParser::list(|event| events.push(event));
+        parser.feed("1, 2".as_bytes()).unwrap();
+        parser.feed(", ".as_bytes()).unwrap();
+        parser.finish().unwrap_err();
+        assert_eq!(
+            vec![
+                Event::BareItem(BareItem::Integer(1)),
+                Event::BareItem(BareItem::Integer(2)),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(Ok(vec![]), stream_dictionary(&[" ", ""]));
+        assert_eq!(
+            Err("parse_dict: trailing comma"),
+            stream_dictionary(&["a,", " "])
+        );
+        assert_eq!(
+            Err("parse_dict: trailing characters after dictionary member"),
+            stream_dictionary(&["a=1 b", ",c"])
+        );
+        assert_eq!(
+            Err("parse_bare_item: item type can't be identified"),
+            stream_dictionary(&["a=", ",c"])
+        );
+        assert_eq!(
+            Err("parse: non-ascii characters in input"),
+            stream_dictionary(&["a=\"ü\""])
+        );
+
+        let mut parser = StreamingParser::list(|_| ());
+        assert_eq!(
+            Err("parse_list: trailing characters after list member"),
+            parser.feed("1 2, 3".as_bytes())
+        );
+        assert_eq!(
+            Err("parse_list: trailing characters after list member"),
+            parser.feed("4".as_bytes())
+        );
+    }
+}
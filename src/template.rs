@@ -0,0 +1,178 @@
+use crate::serializer::Serializer;
+use crate::{BareItem, Parser, SFVResult};
+
+/// Builds field value from a template, interpolating serialized bare items into `{}` placeholders.
+///
+/// Each argument is serialized as a `BareItem`, so strings are always quoted and escaped,
+/// and tokens are validated. Use `{{` and `}}` for literal braces.
+/// # Examples
+/// ```
+/// # use sfv::FieldTemplate;
+/// let user_input = "x\", admin=?1";
+/// let value = FieldTemplate::new("id={}, name={};lang={}")
+///     .arg(42)
+///     .string(user_input)
+///     .token("en")
+///     .render_dictionary()
+///     .unwrap();
+/// assert_eq!(value, r#"id=42, name="x\", admin=?1";lang=en"#);
+///
+/// assert!(FieldTemplate::new("a={}").token("not a token").render().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldTemplate {
+    template: String,
+    args: Vec<BareItem>,
+}
+
+impl FieldTemplate {
+    /// Returns new `FieldTemplate` without arguments.
+    pub fn new<T: Into<String>>(template: T) -> FieldTemplate {
+        FieldTemplate {
+            template: template.into(),
+            args: vec![],
+        }
+    }
+
+    /// Appends argument for the next placeholder.
+    pub fn arg<V: Into<BareItem>>(mut self, value: V) -> FieldTemplate {
+        self.args.push(value.into());
+        self
+    }
+
+    /// Appends `BareItem::String` argument for the next placeholder.
+    pub fn string<V: Into<String>>(self, value: V) -> FieldTemplate {
+        self.arg(BareItem::String(value.into()))
+    }
+
+    /// Appends `BareItem::Token` argument for the next placeholder.
+    pub fn token<V: Into<String>>(self, value: V) -> FieldTemplate {
+        self.arg(BareItem::Token(value.into()))
+    }
+
+    /// Appends `BareItem::ByteSeq` argument for the next placeholder.
+    pub fn byte_seq<V: Into<Vec<u8>>>(self, value: V) -> FieldTemplate {
+        self.arg(BareItem::ByteSeq(value.into()))
+    }
+
+    /// Substitutes serialized arguments into the template.
+    /// Returns an error if an argument can't be serialized,
+    /// if the number of arguments doesn't match the number of placeholders, or if the template has an unmatched brace.
+    /// The resulting value is not validated, use `render_item`, `render_list` or `render_dictionary` for that.
+    pub fn render(&self) -> SFVResult<String> {
+        let mut output = String::with_capacity(self.template.len());
+        let mut args = self.args.iter();
+        let mut template_chars = self.template.chars().peekable();
+
+        while let Some(curr_char) = template_chars.next() {
+            match (curr_char, template_chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    template_chars.next();
+                    output.push(curr_char);
+                }
+                ('{', Some('}')) => {
+                    template_chars.next();
+                    let arg = args
+                        .next()
+                        .ok_or("render: too few arguments for template")?;
+                    Serializer::serialize_bare_item(arg, &mut output)?;
+                }
+                ('{', _) => return Err("render: unmatched '{' in template"),
+                ('}', _) => return Err("render: unmatched '}' in template"),
+                _ => output.push(curr_char),
+            }
+        }
+
+        if args.next().is_some() {
+            return Err("render: too many arguments for template");
+        }
+        Ok(output)
+    }
+
+    /// Renders the template and checks that the result is a valid structured field value of Item type.
+    pub fn render_item(&self) -> SFVResult<String> {
+        let output = self.render()?;
+        Parser::parse_item(output.as_bytes())?;
+        Ok(output)
+    }
+
+    /// Renders the template and checks that the result is a valid structured field value of List type.
+    pub fn render_list(&self) -> SFVResult<String> {
+        let output = self.render()?;
+        Parser::parse_list(output.as_bytes())?;
+        Ok(output)
+    }
+
+    /// Renders the template and checks that the result is a valid structured field value of Dictionary type.
+    pub fn render_dictionary(&self) -> SFVResult<String> {
+        let output = self.render()?;
+        Parser::parse_dictionary(output.as_bytes())?;
+        Ok(output)
+    }
+}
+
+/// Renders a `FieldTemplate` with the given arguments, each converted into `BareItem`.
+/// ```
+/// # use sfv::{sfv_format, BareItem};
+/// let value = sfv_format!("u={}, i, tag={}", 3, BareItem::String("a \"b\"".into())).unwrap();
+/// assert_eq!(value, r#"u=3, i, tag="a \"b\"""#);
+/// ```
+#[macro_export]
+macro_rules! sfv_format {
+    ($template:expr $(, $arg:expr)* $(,)?) => {
+        $crate::FieldTemplate::new($template)$(.arg($arg))*.render()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_substitutes_in_order() -> SFVResult<()> {
+        let template = FieldTemplate::new("({} {});p={}")
+            .arg(1)
+            .arg(false)
+            .byte_seq(vec![1, 2]);
+        assert_eq!("(1 ?0);p=:AQI=:", template.render()?);
+        assert_eq!("(1 ?0);p=:AQI=:", template.render_list()?);
+        assert_eq!(
+            Err("parse_bare_item: item type can't be identified"),
+            template.render_item()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_handles_braces() -> SFVResult<()> {
+        assert_eq!(
+            "\"{{}}\"",
+            FieldTemplate::new("\"{{{{}}}}\"").render_item()?
+        );
+        assert_eq!(
+            Err("render: unmatched '{' in template"),
+            FieldTemplate::new("{a}").render()
+        );
+        assert_eq!(
+            Err("render: unmatched '}' in template"),
+            FieldTemplate::new("a}").render()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_checks_arguments() {
+        assert_eq!(
+            Err("render: too few arguments for template"),
+            FieldTemplate::new("{}, {}").arg(1).render()
+        );
+        assert_eq!(
+            Err("render: too many arguments for template"),
+            FieldTemplate::new("{}").arg(1).arg(2).render()
+        );
+        assert_eq!(
+            Err("serialize_string: non-ascii character"),
+            FieldTemplate::new("{}").string("ü").render()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::FromStr;
-use crate::{BareItem, Decimal, Dictionary, InnerList, Item, List, Num, Parameters};
-use crate::{ParseMore, ParseValue, Parser};
+use crate::{BareItem, Decimal, Dictionary, InnerList, Item, List, Number, Parameters};
+use crate::{ParseMore, ParseOptions, ParseValue, Parser};
 use std::error::Error;
 use std::iter::FromIterator;
 
@@ -170,7 +170,7 @@ fn parse_inner_list_errors() -> Result<(), Box<dyn Error>> {
     let mut input = "c b); a=1".chars().peekable();
     assert_eq!(
         Err("parse_inner_list: input does not start with '('"),
-        Parser::parse_inner_list(&mut input)
+        Parser::parse_inner_list(&mut input, &ParseOptions::default())
     );
     Ok(())
 }
@@ -183,7 +183,10 @@ fn parse_inner_list_with_param_and_spaces() -> Result<(), Box<dyn Error>> {
     let item1 = Item::new(BareItem::Token("c".to_owned()));
     let item2 = Item::new(BareItem::Token("b".to_owned()));
     let expected = InnerList::with_params(vec![item1, item2], inner_list_param);
-    assert_eq!(expected, Parser::parse_inner_list(&mut input)?);
+    assert_eq!(
+        expected,
+        Parser::parse_inner_list(&mut input, &ParseOptions::default())?
+    );
     Ok(())
 }
 
@@ -324,23 +327,29 @@ fn parse_dict_multiple_spaces() -> Result<(), Box<dyn Error>> {
 fn parse_bare_item() -> Result<(), Box<dyn Error>> {
     assert_eq!(
         BareItem::Boolean(false),
-        Parser::parse_bare_item(&mut "?0".chars().peekable())?
+        Parser::parse_bare_item(&mut "?0".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         BareItem::String("test string".to_owned()),
-        Parser::parse_bare_item(&mut "\"test string\"".chars().peekable())?
+        Parser::parse_bare_item(
+            &mut "\"test string\"".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         BareItem::Token("*token".to_owned()),
-        Parser::parse_bare_item(&mut "*token".chars().peekable())?
+        Parser::parse_bare_item(&mut "*token".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         BareItem::ByteSeq("base_64 encoding test".to_owned().into_bytes()),
-        Parser::parse_bare_item(&mut ":YmFzZV82NCBlbmNvZGluZyB0ZXN0:".chars().peekable())?
+        Parser::parse_bare_item(
+            &mut ":YmFzZV82NCBlbmNvZGluZyB0ZXN0:".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         BareItem::Decimal(Decimal::from_str("-3.55")?),
-        Parser::parse_bare_item(&mut "-3.55".chars().peekable())?
+        Parser::parse_bare_item(&mut "-3.55".chars().peekable(), &ParseOptions::default())?
     );
     Ok(())
 }
@@ -349,15 +358,15 @@ fn parse_bare_item() -> Result<(), Box<dyn Error>> {
 fn parse_bare_item_errors() -> Result<(), Box<dyn Error>> {
     assert_eq!(
         Err("parse_bare_item: item type can't be identified"),
-        Parser::parse_bare_item(&mut "!?0".chars().peekable())
+        Parser::parse_bare_item(&mut "!?0".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_bare_item: item type can't be identified"),
-        Parser::parse_bare_item(&mut "_11abc".chars().peekable())
+        Parser::parse_bare_item(&mut "_11abc".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_bare_item: item type can't be identified"),
-        Parser::parse_bare_item(&mut "   ".chars().peekable())
+        Parser::parse_bare_item(&mut "   ".chars().peekable(), &ParseOptions::default())
     );
     Ok(())
 }
@@ -365,11 +374,11 @@ fn parse_bare_item_errors() -> Result<(), Box<dyn Error>> {
 #[test]
 fn parse_bool() -> Result<(), Box<dyn Error>> {
     let mut input = "?0gk".chars().peekable();
-    assert_eq!(false, Parser::parse_bool(&mut input)?);
+    assert!(!Parser::parse_bool(&mut input)?);
     assert_eq!(input.collect::<String>(), "gk");
 
-    assert_eq!(false, Parser::parse_bool(&mut "?0".chars().peekable())?);
-    assert_eq!(true, Parser::parse_bool(&mut "?1".chars().peekable())?);
+    assert!(!Parser::parse_bool(&mut "?0".chars().peekable())?);
+    assert!(Parser::parse_bool(&mut "?1".chars().peekable())?);
     Ok(())
 }
 
@@ -389,24 +398,33 @@ fn parse_bool_errors() -> Result<(), Box<dyn Error>> {
 #[test]
 fn parse_string() -> Result<(), Box<dyn Error>> {
     let mut input = "\"some string\" ;not string".chars().peekable();
-    assert_eq!("some string".to_owned(), Parser::parse_string(&mut input)?);
+    assert_eq!(
+        "some string".to_owned(),
+        Parser::parse_string(&mut input, &ParseOptions::default())?
+    );
     assert_eq!(input.collect::<String>(), " ;not string");
 
     assert_eq!(
         "test".to_owned(),
-        Parser::parse_string(&mut "\"test\"".chars().peekable())?
+        Parser::parse_string(&mut "\"test\"".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         r#"te\st"#.to_owned(),
-        Parser::parse_string(&mut "\"te\\\\st\"".chars().peekable())?
+        Parser::parse_string(
+            &mut "\"te\\\\st\"".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "".to_owned(),
-        Parser::parse_string(&mut "\"\"".chars().peekable())?
+        Parser::parse_string(&mut "\"\"".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         "some string".to_owned(),
-        Parser::parse_string(&mut "\"some string\"".chars().peekable())?
+        Parser::parse_string(
+            &mut "\"some string\"".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     Ok(())
 }
@@ -415,23 +433,41 @@ fn parse_string() -> Result<(), Box<dyn Error>> {
 fn parse_string_errors() -> Result<(), Box<dyn Error>> {
     assert_eq!(
         Err("parse_string: first character is not '\"'"),
-        Parser::parse_string(&mut "test".chars().peekable())
+        Parser::parse_string(&mut "test".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_string: last input character is '\\'"),
-        Parser::parse_string(&mut "\"\\".chars().peekable())
+        Parser::parse_string(&mut "\"\\".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_string: disallowed character after '\\'"),
-        Parser::parse_string(&mut "\"\\l\"".chars().peekable())
+        Parser::parse_string(&mut "\"\\l\"".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_string: not a visible character"),
-        Parser::parse_string(&mut "\"\u{1f}\"".chars().peekable())
+        Parser::parse_string(
+            &mut "\"\u{1f}\"".chars().peekable(),
+            &ParseOptions::default()
+        )
     );
     assert_eq!(
         Err("parse_string: no closing '\"'"),
-        Parser::parse_string(&mut "\"smth".chars().peekable())
+        Parser::parse_string(&mut "\"smth".chars().peekable(), &ParseOptions::default())
+    );
+    Ok(())
+}
+
+#[test]
+fn unescape_string() -> Result<(), Box<dyn Error>> {
+    assert_eq!("te\\st", crate::unescape_string("\"te\\\\st\"")?);
+    assert_eq!("", crate::unescape_string("\"\"")?);
+    assert_eq!(
+        Err("unescape_string: trailing characters after closing '\"'"),
+        crate::unescape_string("\"test\" ")
+    );
+    assert_eq!(
+        Err("parse_string: first character is not '\"'"),
+        crate::unescape_string("test")
     );
     Ok(())
 }
@@ -439,36 +475,57 @@ fn parse_string_errors() -> Result<(), Box<dyn Error>> {
 #[test]
 fn parse_token() -> Result<(), Box<dyn Error>> {
     let mut input = "*some:token}not token".chars().peekable();
-    assert_eq!("*some:token".to_owned(), Parser::parse_token(&mut input)?);
+    assert_eq!(
+        "*some:token".to_owned(),
+        Parser::parse_token(&mut input, &ParseOptions::default())?
+    );
     assert_eq!(input.collect::<String>(), "}not token");
 
     assert_eq!(
         "token".to_owned(),
-        Parser::parse_token(&mut "token".chars().peekable())?
+        Parser::parse_token(&mut "token".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         "a_b-c.d3:f%00/*".to_owned(),
-        Parser::parse_token(&mut "a_b-c.d3:f%00/*".chars().peekable())?
+        Parser::parse_token(
+            &mut "a_b-c.d3:f%00/*".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "TestToken".to_owned(),
-        Parser::parse_token(&mut "TestToken".chars().peekable())?
+        Parser::parse_token(
+            &mut "TestToken".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "some".to_owned(),
-        Parser::parse_token(&mut "some@token".chars().peekable())?
+        Parser::parse_token(
+            &mut "some@token".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "*TestToken*".to_owned(),
-        Parser::parse_token(&mut "*TestToken*".chars().peekable())?
+        Parser::parse_token(
+            &mut "*TestToken*".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "*".to_owned(),
-        Parser::parse_token(&mut "*[@:token".chars().peekable())?
+        Parser::parse_token(
+            &mut "*[@:token".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "test".to_owned(),
-        Parser::parse_token(&mut "test token".chars().peekable())?
+        Parser::parse_token(
+            &mut "test token".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
 
     Ok(())
@@ -479,17 +536,17 @@ fn parse_token_errors() -> Result<(), Box<dyn Error>> {
     let mut input = "765token".chars().peekable();
     assert_eq!(
         Err("parse_token: first character is not ALPHA or '*'"),
-        Parser::parse_token(&mut input)
+        Parser::parse_token(&mut input, &ParseOptions::default())
     );
     assert_eq!(input.collect::<String>(), "765token");
 
     assert_eq!(
         Err("parse_token: first character is not ALPHA or '*'"),
-        Parser::parse_token(&mut "7token".chars().peekable())
+        Parser::parse_token(&mut "7token".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_token: empty input string"),
-        Parser::parse_token(&mut "".chars().peekable())
+        Parser::parse_token(&mut "".chars().peekable(), &ParseOptions::default())
     );
     Ok(())
 }
@@ -499,25 +556,34 @@ fn parse_byte_sequence() -> Result<(), Box<dyn Error>> {
     let mut input = ":aGVsbG8:rest_of_str".chars().peekable();
     assert_eq!(
         "hello".to_owned().into_bytes(),
-        Parser::parse_byte_sequence(&mut input)?
+        Parser::parse_byte_sequence(&mut input, &ParseOptions::default())?
     );
     assert_eq!("rest_of_str", input.collect::<String>());
 
     assert_eq!(
         "hello".to_owned().into_bytes(),
-        Parser::parse_byte_sequence(&mut ":aGVsbG8:".chars().peekable())?
+        Parser::parse_byte_sequence(
+            &mut ":aGVsbG8:".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "test_encode".to_owned().into_bytes(),
-        Parser::parse_byte_sequence(&mut ":dGVzdF9lbmNvZGU:".chars().peekable())?
+        Parser::parse_byte_sequence(
+            &mut ":dGVzdF9lbmNvZGU:".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "new:year tree".to_owned().into_bytes(),
-        Parser::parse_byte_sequence(&mut ":bmV3OnllYXIgdHJlZQ==:".chars().peekable())?
+        Parser::parse_byte_sequence(
+            &mut ":bmV3OnllYXIgdHJlZQ==:".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         "".to_owned().into_bytes(),
-        Parser::parse_byte_sequence(&mut "::".chars().peekable())?
+        Parser::parse_byte_sequence(&mut "::".chars().peekable(), &ParseOptions::default())?
     );
     Ok(())
 }
@@ -526,15 +592,21 @@ fn parse_byte_sequence() -> Result<(), Box<dyn Error>> {
 fn parse_byte_sequence_errors() -> Result<(), Box<dyn Error>> {
     assert_eq!(
         Err("parse_byte_seq: first char is not ':'"),
-        Parser::parse_byte_sequence(&mut "aGVsbG8".chars().peekable())
+        Parser::parse_byte_sequence(&mut "aGVsbG8".chars().peekable(), &ParseOptions::default())
     );
     assert_eq!(
         Err("parse_byte_seq: invalid char in byte sequence"),
-        Parser::parse_byte_sequence(&mut ":aGVsb G8=:".chars().peekable())
+        Parser::parse_byte_sequence(
+            &mut ":aGVsb G8=:".chars().peekable(),
+            &ParseOptions::default()
+        )
     );
     assert_eq!(
         Err("parse_byte_seq: no closing ':'"),
-        Parser::parse_byte_sequence(&mut ":aGVsbG8=".chars().peekable())
+        Parser::parse_byte_sequence(
+            &mut ":aGVsbG8=".chars().peekable(),
+            &ParseOptions::default()
+        )
     );
     Ok(())
 }
@@ -543,53 +615,53 @@ fn parse_byte_sequence_errors() -> Result<(), Box<dyn Error>> {
 fn parse_number_int() -> Result<(), Box<dyn Error>> {
     let mut input = "-733333333332d.14".chars().peekable();
     assert_eq!(
-        Num::Integer(-733333333332),
+        Number::Integer(-733333333332),
         Parser::parse_number(&mut input)?
     );
     assert_eq!("d.14", input.collect::<String>());
 
     assert_eq!(
-        Num::Integer(42),
+        Number::Integer(42),
         Parser::parse_number(&mut "42".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(-42),
+        Number::Integer(-42),
         Parser::parse_number(&mut "-42".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(-42),
+        Number::Integer(-42),
         Parser::parse_number(&mut "-042".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(0),
+        Number::Integer(0),
         Parser::parse_number(&mut "0".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(0),
+        Number::Integer(0),
         Parser::parse_number(&mut "00".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(123456789012345),
+        Number::Integer(123456789012345),
         Parser::parse_number(&mut "123456789012345".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(-123456789012345),
+        Number::Integer(-123456789012345),
         Parser::parse_number(&mut "-123456789012345".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(2),
+        Number::Integer(2),
         Parser::parse_number(&mut "2,3".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(4),
+        Number::Integer(4),
         Parser::parse_number(&mut "4-2".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(-999999999999999),
+        Number::Integer(-999999999999999),
         Parser::parse_number(&mut "-999999999999999".chars().peekable())?
     );
     assert_eq!(
-        Num::Integer(999999999999999),
+        Number::Integer(999999999999999),
         Parser::parse_number(&mut "999999999999999".chars().peekable())?
     );
 
@@ -600,37 +672,37 @@ fn parse_number_int() -> Result<(), Box<dyn Error>> {
 fn parse_number_decimal() -> Result<(), Box<dyn Error>> {
     let mut input = "00.42 test string".chars().peekable();
     assert_eq!(
-        Num::Decimal(Decimal::from_str("0.42")?),
+        Number::Decimal(Decimal::from_str("0.42")?),
         Parser::parse_number(&mut input)?
     );
     assert_eq!(" test string", input.collect::<String>());
 
     assert_eq!(
-        Num::Decimal(Decimal::from_str("1.5")?),
+        Number::Decimal(Decimal::from_str("1.5")?),
         Parser::parse_number(&mut "1.5.4.".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("1.8")?),
+        Number::Decimal(Decimal::from_str("1.8")?),
         Parser::parse_number(&mut "1.8.".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("1.7")?),
+        Number::Decimal(Decimal::from_str("1.7")?),
         Parser::parse_number(&mut "1.7.0".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("3.14")?),
+        Number::Decimal(Decimal::from_str("3.14")?),
         Parser::parse_number(&mut "3.14".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("-3.14")?),
+        Number::Decimal(Decimal::from_str("-3.14")?),
         Parser::parse_number(&mut "-3.14".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("123456789012.1")?),
+        Number::Decimal(Decimal::from_str("123456789012.1")?),
         Parser::parse_number(&mut "123456789012.1".chars().peekable())?
     );
     assert_eq!(
-        Num::Decimal(Decimal::from_str("1234567890.112")?),
+        Number::Decimal(Decimal::from_str("1234567890.112")?),
         Parser::parse_number(&mut "1234567890.112".chars().peekable())?
     );
 
@@ -716,7 +788,10 @@ fn parse_params_string() -> Result<(), Box<dyn Error>> {
         "b".to_owned(),
         BareItem::String("param_val".to_owned()),
     )]);
-    assert_eq!(expected, Parser::parse_parameters(&mut input)?);
+    assert_eq!(
+        expected,
+        Parser::parse_parameters(&mut input, &ParseOptions::default())?
+    );
     Ok(())
 }
 
@@ -727,7 +802,10 @@ fn parse_params_bool() -> Result<(), Box<dyn Error>> {
         ("b".to_owned(), BareItem::Boolean(true)),
         ("a".to_owned(), BareItem::Boolean(true)),
     ]);
-    assert_eq!(expected, Parser::parse_parameters(&mut input)?);
+    assert_eq!(
+        expected,
+        Parser::parse_parameters(&mut input, &ParseOptions::default())?
+    );
     Ok(())
 }
 
@@ -738,7 +816,10 @@ fn parse_params_mixed_types() -> Result<(), Box<dyn Error>> {
         ("key1".to_owned(), BareItem::Boolean(false)),
         ("key2".to_owned(), Decimal::from_str("746.15")?.into()),
     ]);
-    assert_eq!(expected, Parser::parse_parameters(&mut input)?);
+    assert_eq!(
+        expected,
+        Parser::parse_parameters(&mut input, &ParseOptions::default())?
+    );
     Ok(())
 }
 
@@ -749,7 +830,10 @@ fn parse_params_with_spaces() -> Result<(), Box<dyn Error>> {
         ("key1".to_owned(), BareItem::Boolean(false)),
         ("key2".to_owned(), 11111.into()),
     ]);
-    assert_eq!(expected, Parser::parse_parameters(&mut input)?);
+    assert_eq!(
+        expected,
+        Parser::parse_parameters(&mut input, &ParseOptions::default())?
+    );
     Ok(())
 }
 
@@ -757,19 +841,25 @@ fn parse_params_with_spaces() -> Result<(), Box<dyn Error>> {
 fn parse_params_empty() -> Result<(), Box<dyn Error>> {
     assert_eq!(
         Parameters::new(),
-        Parser::parse_parameters(&mut " key1=?0; key2=11111".chars().peekable())?
+        Parser::parse_parameters(
+            &mut " key1=?0; key2=11111".chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     assert_eq!(
         Parameters::new(),
-        Parser::parse_parameters(&mut "".chars().peekable())?
+        Parser::parse_parameters(&mut "".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         Parameters::new(),
-        Parser::parse_parameters(&mut "[;a=1".chars().peekable())?
+        Parser::parse_parameters(&mut "[;a=1".chars().peekable(), &ParseOptions::default())?
     );
     assert_eq!(
         Parameters::new(),
-        Parser::parse_parameters(&mut String::new().chars().peekable())?
+        Parser::parse_parameters(
+            &mut String::new().chars().peekable(),
+            &ParseOptions::default()
+        )?
     );
     Ok(())
 }
@@ -813,7 +903,7 @@ fn parse_more_list() -> Result<(), Box<dyn Error>> {
     let expected_list: List = vec![inner_list_1.into(), item3.into()];
 
     let mut parsed_header = Parser::parse_list("(1 2)".as_bytes())?;
-    let _ = parsed_header.parse_more("42".as_bytes())?;
+    parsed_header.parse_more("42".as_bytes())?;
     assert_eq!(expected_list, parsed_header);
     Ok(())
 }
@@ -832,7 +922,7 @@ fn parse_more_dict() -> Result<(), Box<dyn Error>> {
     ]);
 
     let mut parsed_header = Parser::parse_dictionary("a=1, b;foo=*\t\t".as_bytes())?;
-    let _ = parsed_header.parse_more(" c=3".as_bytes())?;
+    parsed_header.parse_more(" c=3".as_bytes())?;
     assert_eq!(expected_dict, parsed_header);
     Ok(())
 }
@@ -848,3 +938,433 @@ fn parse_more_errors() -> Result<(), Box<dyn Error>> {
     assert!(parsed_list_header.is_err());
     Ok(())
 }
+
+#[test]
+fn parse_from_lines() -> Result<(), Box<dyn Error>> {
+    let lines = ["a, (b c)", " d;p=1 "];
+    let expected = Parser::parse_list("a, (b c), d;p=1".as_bytes())?;
+    assert_eq!(
+        expected,
+        Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes()))?
+    );
+
+    let lines = ["a=1, b=2", "a=3"];
+    let expected = Parser::parse_dictionary("a=1, b=2, a=3".as_bytes())?;
+    assert_eq!(
+        expected,
+        Parser::parse_dictionary_from_lines(lines.iter().map(|line| line.as_bytes()))?
+    );
+
+    assert_eq!(List::new(), Parser::parse_list_from_lines(vec![])?);
+    let lines = ["a", "b,"];
+    assert_eq!(
+        Err("parse_list: trailing comma"),
+        Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes()))
+    );
+
+    // Combined lines would have an empty member
+    assert!(Parser::parse_list("a, (b c),  , d;p=1".as_bytes()).is_err());
+    let lines = ["a, (b c)", "  ", " d;p=1 "];
+    assert_eq!(
+        Err("parse_list: empty field line"),
+        Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes()))
+    );
+    let lines = ["a, (b c)", "\t"];
+    assert_eq!(
+        Err("parse_list: empty field line"),
+        Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes()))
+    );
+
+    // OWS is allowed after ", " of the combined line
+    let lines = ["a", "\tb", " \t c"];
+    assert_eq!(
+        Parser::parse_list("a, \tb,  \t c".as_bytes())?,
+        Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes()))?
+    );
+    let lines = ["\ta", "b"];
+    assert!(Parser::parse_list("\ta, b".as_bytes()).is_err());
+    assert!(Parser::parse_list_from_lines(lines.iter().map(|line| line.as_bytes())).is_err());
+    let lines = ["a=1", ""];
+    assert_eq!(
+        Err("parse_dict: empty field line"),
+        Parser::parse_dictionary_from_lines(lines.iter().map(|line| line.as_bytes()))
+    );
+    assert_eq!(
+        Dictionary::new(),
+        Parser::parse_dictionary_from_lines(vec!["".as_bytes()])?
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_with_normalizers() -> Result<(), Box<dyn Error>> {
+    let options = ParseOptions::new()
+        .normalize_with(|bare_item| {
+            if let BareItem::String(value) = bare_item {
+                *value = value.trim().to_owned();
+            }
+        })
+        .normalize_with(|bare_item| {
+            if let BareItem::String(value) = bare_item {
+                value.push('!');
+            }
+        });
+
+    let expected_list: List = vec![
+        Item::with_params(
+            BareItem::String("a!".to_owned()),
+            Parameters::from_iter(vec![("p".to_owned(), BareItem::String("b!".to_owned()))]),
+        )
+        .into(),
+        InnerList::new(vec![Item::new(BareItem::String("c!".to_owned()))]).into(),
+    ];
+    assert_eq!(
+        expected_list,
+        Parser::parse_list_with_options("\" a \";p=\"b \", (\"  c\")".as_bytes(), &options)?
+    );
+
+    let expected_item = Item::with_params(
+        BareItem::Boolean(false),
+        Parameters::from_iter(vec![("flag".to_owned(), BareItem::Boolean(true))]),
+    );
+    assert_eq!(
+        expected_item,
+        Parser::parse_item_with_options("?0;flag".as_bytes(), &options)?
+    );
+    Ok(())
+}
+
+#[test]
+fn read_from_reader() -> Result<(), Box<dyn Error>> {
+    let expected_item = Item::new(BareItem::Token("tok".to_owned()));
+    assert_eq!(expected_item, Parser::read_item("tok\r\n".as_bytes(), 5)?);
+    assert_eq!(expected_item, Parser::read_item("tok".as_bytes(), 3)?);
+
+    let err = Parser::read_item("tok\r\n".as_bytes(), 4).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    assert_eq!("read: input is longer than max_len", err.to_string());
+
+    let err = Parser::read_list("a,\n".as_bytes(), 1024).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    assert_eq!("parse_list: trailing comma", err.to_string());
+    Ok(())
+}
+
+#[test]
+fn parse_byte_sequence_strict() -> Result<(), Box<dyn Error>> {
+    let options = ParseOptions::new().strict_base64(true);
+    assert_eq!(
+        "hello".as_bytes(),
+        Parser::parse_byte_sequence(&mut ":aGVsbG8=:".chars().peekable(), &options)?
+    );
+    assert_eq!(
+        Vec::<u8>::new(),
+        Parser::parse_byte_sequence(&mut "::".chars().peekable(), &options)?
+    );
+    assert_eq!(
+        Err("parse_byte_seq: decoding error"),
+        Parser::parse_byte_sequence(&mut ":aGVsbG8:".chars().peekable(), &options)
+    );
+    assert_eq!(
+        Err("parse_byte_seq: decoding error"),
+        Parser::parse_byte_sequence(&mut ":aGVsbG9=:".chars().peekable(), &options)
+    );
+    assert_eq!(
+        Err("parse_byte_seq: decoding error"),
+        Parser::parse_byte_sequence(&mut ":aGVsbG8==:".chars().peekable(), &options)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_rejecting_parameters() -> Result<(), Box<dyn Error>> {
+    let options = ParseOptions::new().reject_parameters(true);
+    let expected_dict = Dictionary::from_iter(vec![
+        ("a".to_owned(), Item::new(BareItem::Boolean(true)).into()),
+        (
+            "b".to_owned(),
+            InnerList::new(vec![Item::new(1.into())]).into(),
+        ),
+    ]);
+    assert_eq!(
+        expected_dict,
+        Parser::parse_dictionary_with_options("a, b=(1)".as_bytes(), &options)?
+    );
+
+    for input in &["a;x", "a=1;x=2", "b=(1;x)", "b=(1);x"] {
+        assert_eq!(
+            Err("parse_parameters: parameters are not allowed"),
+            Parser::parse_dictionary_with_options(input.as_bytes(), &options)
+        );
+    }
+    assert_eq!(
+        Err("parse_parameters: parameters are not allowed"),
+        Parser::parse_item_with_options("1;a".as_bytes(), &options)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_dictionary_filtered() -> Result<(), Box<dyn Error>> {
+    let input = "a=1, b=(x y);p, c, a=2, d=\"z\"";
+    let expected_dict = Dictionary::from_iter(vec![
+        ("a".to_owned(), Item::new(2.into()).into()),
+        ("c".to_owned(), Item::new(BareItem::Boolean(true)).into()),
+    ]);
+    assert_eq!(
+        expected_dict,
+        Parser::parse_dictionary_filtered(input.as_bytes(), &["c", "a", "missing"])?
+    );
+    assert!(Parser::parse_dictionary_filtered(input.as_bytes(), &[])?.is_empty());
+    assert_eq!(
+        Err("parse_dict: trailing comma"),
+        Parser::parse_dictionary_filtered("a=1, b=2,".as_bytes(), &["a"])
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_prefix_until_terminator() -> Result<(), Box<dyn Error>> {
+    let options = ParseOptions::new().terminators(b"|/");
+
+    let (item, consumed) = Parser::parse_item_prefix("tok;a=\"x|y\" |meta".as_bytes(), &options)?;
+    assert_eq!(Some("tok"), item.bare_item.as_token());
+    assert_eq!(Some("x|y"), item.params["a"].as_str());
+    assert_eq!(12, consumed);
+
+    let (list, consumed) = Parser::parse_list_prefix("1, :YQ==:/rest".as_bytes(), &options)?;
+    assert_eq!(2, list.len());
+    assert_eq!(9, consumed);
+
+    let (list, consumed) = Parser::parse_list_prefix("|".as_bytes(), &options)?;
+    assert!(list.is_empty());
+    assert_eq!(0, consumed);
+
+    let (dict, consumed) = Parser::parse_dictionary_prefix("a, b=2".as_bytes(), &options)?;
+    assert_eq!(2, dict.len());
+    assert_eq!(6, consumed);
+
+    assert_eq!(
+        Err("parse_dict: trailing comma"),
+        Parser::parse_dictionary_prefix("a, |".as_bytes(), &options)
+    );
+    assert_eq!(
+        Err("parse: trailing characters after parsed value"),
+        Parser::parse_item_prefix("1 2|".as_bytes(), &options)
+    );
+    assert_eq!(
+        Err("parse_inner_list: bad delimitation"),
+        Parser::parse_list_prefix("(a|b)".as_bytes(), &options)
+    );
+    assert_eq!(
+        Err("parse: trailing characters after parsed value"),
+        Parser::parse_list_with_options("a|b".as_bytes(), &options)
+    );
+
+    for terminators in [&b";"[..], b"|="].iter() {
+        let options = ParseOptions::new().terminators(terminators);
+        assert_eq!(
+            Err("parse: ';' and '=' can't be terminators"),
+            Parser::parse_item_prefix("a;b".as_bytes(), &options)
+        );
+        assert_eq!(
+            Err("parse: ';' and '=' can't be terminators"),
+            Parser::parse_list_with_options("a".as_bytes(), &options)
+        );
+    }
+
+    let (item, consumed) = Parser::parse_item_prefix("a|b".as_bytes(), &ParseOptions::new())?;
+    assert_eq!(Some("a|b"), item.bare_item.as_token());
+    assert_eq!(3, consumed);
+    Ok(())
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn parse_from_header_value() -> Result<(), Box<dyn Error>> {
+    let header_value = http::HeaderValue::from_static("1;a=tok");
+    let item: Item = Parser::from_header_value(&header_value)?;
+    assert_eq!(Some(1), item.bare_item.as_int());
+
+    let header_value = http::HeaderValue::from_bytes(b"\"caf\xc3\xa9\"")?;
+    assert_eq!(
+        Err("parse: non-ascii characters in input"),
+        Parser::from_header_value::<Item>(&header_value)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_dictionary_with_hasher() -> Result<(), Box<dyn Error>> {
+    use crate::{DuplicateKeys, ListEntry, ParseLimits, SerializeValue};
+    use indexmap::IndexMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    let input = "a=1, b=(x y);p, a=2";
+    let dict: IndexMap<String, ListEntry, BuildHasherDefault<DefaultHasher>> =
+        Parser::parse_dictionary_with_hasher(input.as_bytes())?;
+    assert!(Parser::parse_dictionary(input.as_bytes())?
+        .iter()
+        .eq(dict.iter()));
+    assert_eq!("a=2, b=(x y);p", dict.serialize_value()?);
+
+    let options = ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::KeepFirst);
+    let dict: IndexMap<String, ListEntry, BuildHasherDefault<DefaultHasher>> =
+        Parser::parse_dictionary_with_hasher_with_options(input.as_bytes(), &options)?;
+    assert_eq!("a=1, b=(x y);p", dict.serialize_value()?);
+    let options = ParseOptions::new().limits(ParseLimits::new().max_dictionary_members(2));
+    assert_eq!(
+        Err("parse_dict: too many members"),
+        Parser::parse_dictionary_with_hasher_with_options::<BuildHasherDefault<DefaultHasher>>(
+            input.as_bytes(),
+            &options
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn bare_item_try_into_native_types() -> Result<(), Box<dyn Error>> {
+    use std::convert::{TryFrom, TryInto};
+
+    let item = Parser::parse_item("1;d=2.5;s=\"str\";b=:AQI=:;t=tok;f=?0".as_bytes())?;
+    let n: i64 = item.bare_item.clone().try_into()?;
+    assert_eq!(1, n);
+    assert_eq!(2.5, f64::try_from(&item.params["d"])?);
+    assert_eq!(Decimal::new(25, 1), Decimal::try_from(&item.params["d"])?);
+    assert_eq!("str", <&str>::try_from(&item.params["s"])?);
+    assert_eq!("str", String::try_from(item.params["s"].clone())?);
+    assert_eq!(&[1, 2], <&[u8]>::try_from(&item.params["b"])?);
+    assert_eq!(vec![1, 2], Vec::<u8>::try_from(&item.params["b"])?);
+    assert!(!bool::try_from(&item.params["f"])?);
+
+    assert_eq!(
+        Err("try_from: bare item is not a string"),
+        String::try_from(&item.params["t"])
+    );
+    assert_eq!(
+        Err("try_from: bare item is not a decimal"),
+        f64::try_from(item.bare_item)
+    );
+    assert_eq!(
+        Err("try_from: bare item is not an integer"),
+        i64::try_from(&item.params["d"])
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_item_from_str() -> Result<(), Box<dyn Error>> {
+    use std::convert::TryFrom;
+
+    let expected = Parser::parse_item("\"a\";b=?0".as_bytes())?;
+    assert_eq!(expected, "\"a\";b=?0".parse::<Item>()?);
+    assert_eq!(expected, Item::try_from("  \"a\";b=?0")?);
+    assert_eq!(
+        Err("parse: trailing characters after parsed value"),
+        "1 2".parse::<Item>()
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_with_duplicate_key_policies() -> Result<(), Box<dyn Error>> {
+    use crate::DuplicateKeys;
+
+    let input = "a=1;p=1;p=2, b=(1;p;p=?0), a=2".as_bytes();
+    let dict = Parser::parse_dictionary_with_options(input, &ParseOptions::new())?;
+    assert_eq!(Parser::parse_dictionary(input)?, dict);
+
+    let options = ParseOptions::new()
+        .duplicate_dictionary_keys(DuplicateKeys::KeepFirst)
+        .duplicate_parameter_keys(DuplicateKeys::KeepFirst);
+    let dict = Parser::parse_dictionary_with_options(input, &options)?;
+    assert_eq!(
+        Parser::parse_dictionary("a=1;p=1, b=(1;p)".as_bytes())?,
+        dict
+    );
+
+    let options = ParseOptions::new().duplicate_parameter_keys(DuplicateKeys::Reject);
+    assert_eq!(
+        Err("parse_parameters: duplicate key"),
+        Parser::parse_list_with_options("(1;p;p)".as_bytes(), &options)
+    );
+    let dict = Parser::parse_dictionary_with_options("a, a".as_bytes(), &options)?;
+    assert_eq!(1, dict.len());
+
+    let options = ParseOptions::new().duplicate_dictionary_keys(DuplicateKeys::Reject);
+    assert_eq!(
+        Err("parse_dict: duplicate key"),
+        Parser::parse_dictionary_with_options("a, b, a".as_bytes(), &options)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_with_limits() -> Result<(), Box<dyn Error>> {
+    use crate::ParseLimits;
+
+    let input = "a=(1 2 3);p;q, b=\"str\", c=:AQID:".as_bytes();
+    let options = ParseOptions::new().limits(ParseLimits::new());
+    assert_eq!(
+        Parser::parse_dictionary(input)?,
+        Parser::parse_dictionary_with_options(input, &options)?
+    );
+
+    let limits = ParseLimits::new()
+        .max_list_members(3)
+        .max_dictionary_members(3)
+        .max_parameters(2)
+        .max_string_len(3)
+        .max_byte_seq_len(3);
+    let options = ParseOptions::new().limits(limits);
+    assert!(Parser::parse_dictionary_with_options(input, &options).is_ok());
+
+    let cases = [
+        ("a, b, c, d", "parse_list: too many members"),
+        ("(1 2 3 4)", "parse_inner_list: too many items"),
+        ("a;p;q;r", "parse_parameters: too many parameters"),
+        ("(a);p;q;p", "parse_parameters: too many parameters"),
+        ("\"abcd\"", "parse_string: string is too long"),
+        (":AQIDBA==:", "parse_byte_seq: byte sequence is too long"),
+        // Limits are enforced before the whole value is parsed or decoded
+        ("\"abcd", "parse_string: string is too long"),
+        (":AQIDBAB:", "parse_byte_seq: byte sequence is too long"),
+    ];
+    for (input, error) in cases.iter() {
+        assert_eq!(
+            Err(*error),
+            Parser::parse_list_with_options(input.as_bytes(), &options)
+        );
+    }
+    assert_eq!(
+        Err("parse_dict: too many members"),
+        Parser::parse_dictionary_with_options("a, b, a, b".as_bytes(), &options)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_with_max_input_len() -> Result<(), Box<dyn Error>> {
+    let options = ParseOptions::new().max_input_len(6);
+    assert_eq!(
+        Parser::parse_item("\"abcd\"".as_bytes())?,
+        Parser::parse_item_with_options("\"abcd\"".as_bytes(), &options)?
+    );
+    assert_eq!(
+        Err("parse: input is too long"),
+        Parser::parse_item_with_options("\"abcde\"".as_bytes(), &options)
+    );
+    assert_eq!(
+        Err("parse: input is too long"),
+        Parser::parse_dictionary_with_options("a, b, c".as_bytes(), &options)
+    );
+
+    let options = options.terminators(b"|");
+    assert_eq!(
+        Err("parse: input is too long"),
+        Parser::parse_list_prefix("a, b|rest".as_bytes(), &options)
+    );
+    Ok(())
+}
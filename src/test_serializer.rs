@@ -255,6 +255,20 @@ fn serialize_string_errors() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn escape_string() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        "\"hello \\\"name\\\"\"",
+        crate::escape_string("hello \"name\"")?
+    );
+    assert_eq!("\"\"", crate::escape_string("")?);
+    assert_eq!(
+        Err("serialize_string: not a visible character"),
+        crate::escape_string("text \x7f")
+    );
+    Ok(())
+}
+
 #[test]
 fn serialize_token() -> Result<(), Box<dyn Error>> {
     let mut buf = String::new();
@@ -529,3 +543,171 @@ fn serialize_dict_empty_member_value() -> Result<(), Box<dyn Error>> {
     assert_eq!("a=()", &buf);
     Ok(())
 }
+
+#[cfg(feature = "http")]
+#[test]
+fn serialize_header_value() -> Result<(), Box<dyn Error>> {
+    let list: List = vec![Item::new(BareItem::String("a b".into())).into()];
+    assert_eq!("\"a b\"", list.serialize_header_value()?);
+
+    let list: List = vec![];
+    assert_eq!(
+        Err("serialize_list: serializing empty field is not allowed"),
+        list.serialize_header_value()
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_items_with_chained_params() -> Result<(), Box<dyn Error>> {
+    let item = Item::new(BareItem::Integer(1))
+        .param("a", Decimal::from_str("0.5")?)
+        .param("b", true);
+    assert_eq!("1;a=0.5;b", item.serialize_value()?);
+
+    let inner_list = InnerList::new(vec![item]).param("c", 2);
+    let list: List = vec![inner_list.into()];
+    assert_eq!("(1;a=0.5;b);c=2", list.serialize_value()?);
+
+    let item = Item::new(BareItem::Integer(1)).param("A", true);
+    assert_eq!(
+        Err("serialize_key: disallowed character in input"),
+        item.serialize_value()
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_into_writer() -> Result<(), Box<dyn Error>> {
+    let list: List = vec![
+        Item::new(BareItem::Token("a".to_owned())).into(),
+        InnerList::new(vec![Item::new(BareItem::Integer(1))]).into(),
+    ];
+    let mut output = Vec::new();
+    list.serialize_into(&mut output)?;
+    assert_eq!(b"a, (1)".to_vec(), output);
+
+    let mut dict = Dictionary::new();
+    dict.insert("a".to_owned(), Item::new(BareItem::Boolean(true)).into());
+    dict.insert("b".to_owned(), Item::new(BareItem::Integer(2)).into());
+    let mut output = Vec::new();
+    dict.serialize_into(&mut output)?;
+    assert_eq!(b"a, b=2".to_vec(), output);
+
+    let mut output = Vec::new();
+    Item::new(BareItem::Boolean(false)).serialize_into(&mut output)?;
+    assert_eq!(b"?0".to_vec(), output);
+
+    let err = List::new().serialize_into(&mut Vec::new()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        "serialize_list: serializing empty field is not allowed",
+        err.to_string()
+    );
+
+    // Members preceding the invalid one are already written
+    dict.insert(
+        "c".to_owned(),
+        Item::new(BareItem::Token("#".to_owned())).into(),
+    );
+    let mut output = Vec::new();
+    assert!(dict.serialize_into(&mut output).is_err());
+    assert_eq!(b"a, b=2".to_vec(), output);
+    Ok(())
+}
+
+#[test]
+fn serialize_fmt_writer() -> Result<(), Box<dyn Error>> {
+    use std::fmt::{self, Write};
+
+    // Fixed size buffer failing once full
+    struct Limited(String, usize);
+    impl Write for Limited {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if self.0.len() + s.len() > self.1 {
+                return Err(fmt::Error);
+            }
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    let list: List = vec![
+        Item::new(BareItem::Integer(1)).into(),
+        Item::new(BareItem::Integer(22)).into(),
+    ];
+    let mut output = Limited(String::new(), 5);
+    list.serialize_fmt(&mut output)?;
+    assert_eq!("1, 22", output.0);
+
+    let mut output = Limited(String::new(), 4);
+    assert_eq!(
+        Err("serialize_fmt: failed to write output"),
+        list.serialize_fmt(&mut output)
+    );
+    assert_eq!("1", output.0);
+
+    let mut output = String::new();
+    Item::new(BareItem::Boolean(true)).serialize_fmt(&mut output)?;
+    assert_eq!("?1", output);
+    assert_eq!(
+        Err("serialize_dictionary: serializing empty field is not allowed"),
+        Dictionary::new().serialize_fmt(&mut output)
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_value_bytes() -> Result<(), Box<dyn Error>> {
+    let list: List = vec![
+        Item::new(BareItem::ByteSeq(b"hi".to_vec())).into(),
+        Item::new(BareItem::String("a b".to_owned())).into(),
+    ];
+    assert_eq!(b":aGk=:, \"a b\"".to_vec(), list.serialize_value_bytes()?);
+    assert_eq!(
+        Err("serialize_list: serializing empty field is not allowed"),
+        List::new().serialize_value_bytes()
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_value_into_buffer() -> Result<(), Box<dyn Error>> {
+    let mut output = String::from("prefix: ");
+    Item::new(BareItem::Integer(1)).serialize_value_into(&mut output)?;
+    assert_eq!("prefix: 1", output);
+
+    let list: List = vec![
+        Item::new(BareItem::Integer(2)).into(),
+        Item::new(BareItem::Token("#".to_owned())).into(),
+    ];
+    assert_eq!(
+        Err("serialise_token: first character is not ALPHA or '*'"),
+        list.serialize_value_into(&mut output)
+    );
+    assert_eq!("prefix: 1", output);
+    Ok(())
+}
+
+#[test]
+fn serialize_compact() -> Result<(), Box<dyn Error>> {
+    use crate::Parser;
+
+    let input = "a=1;p, b=(1 2);q=?0, c";
+    let dict = Parser::parse_dictionary(input.as_bytes())?;
+    let compact = dict.serialize_value_compact()?;
+    assert_eq!("a=1;p,b=(1 2);q=?0,c", compact);
+    assert_eq!(dict, Parser::parse_dictionary(compact.as_bytes())?);
+
+    let list = Parser::parse_list("1, 2".as_bytes())?;
+    assert_eq!("1,2", list.serialize_value_compact()?);
+    assert_eq!(
+        "1;a",
+        Parser::parse_item("1;a".as_bytes())?.serialize_value_compact()?
+    );
+    assert_eq!(
+        Err("serialize_list: serializing empty field is not allowed"),
+        List::new().serialize_value_compact()
+    );
+    Ok(())
+}
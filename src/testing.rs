@@ -0,0 +1,327 @@
+/*!
+Helpers for asserting that structured field values are equal in tests.
+
+`assert_structured_eq!` compares values member by member and, on failure,
+lists every differing member or parameter instead of dumping both values.
+
+```
+use sfv::{assert_structured_eq, Parser};
+
+let actual = Parser::parse_dictionary("a=1;q=2, b".as_bytes()).unwrap();
+let expected = Parser::parse_dictionary("a=1;q=2,   b=?1".as_bytes()).unwrap();
+assert_structured_eq!(actual, expected);
+
+// Order of Dictionary and Parameters keys can be ignored.
+let reordered = Parser::parse_dictionary("b, a=1;q=2".as_bytes()).unwrap();
+assert_structured_eq!(actual, reordered, ignore_order);
+```
+*/
+
+use crate::{BareItem, Dictionary, InnerList, Item, List, ListEntry, Parameters, SerializeValue};
+
+/// Whether the order of `Dictionary` and `Parameters` keys is significant when comparing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// Keys must appear in the same order.
+    Strict,
+    /// Keys may appear in any order.
+    Ignore,
+}
+
+/// Structured field values that can be compared member by member.
+pub trait Compare {
+    /// Appends a description of every difference between `self` and `expected` to `output`.
+    /// Each description is prefixed with the location of the difference, starting from `path`.
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>);
+}
+
+/// Returns descriptions of all differences between `actual` and `expected`, or an empty `Vec` if they are equal.
+pub fn differences<T: Compare>(actual: &T, expected: &T, key_order: KeyOrder) -> Vec<String> {
+    let mut output = vec![];
+    actual.compare(expected, "", key_order, &mut output);
+    output
+}
+
+/// Panics with a readable list of differences if `actual` and `expected` are not equal.
+/// Prefer the `assert_structured_eq!` macro.
+#[track_caller]
+pub fn assert_structured_eq<T: Compare>(actual: &T, expected: &T, key_order: KeyOrder) {
+    let diffs = differences(actual, expected, key_order);
+    if !diffs.is_empty() {
+        panic!(
+            "structured field values are not equal:\n  {}",
+            diffs.join("\n  ")
+        );
+    }
+}
+
+/// Asserts that two structured field values are equal, printing every differing member on failure.
+/// Pass `ignore_order` as the third argument to ignore the order of `Dictionary` and `Parameters` keys.
+#[macro_export]
+macro_rules! assert_structured_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_structured_eq(
+            &$actual,
+            &$expected,
+            $crate::testing::KeyOrder::Strict,
+        )
+    };
+    ($actual:expr, $expected:expr, ignore_order $(,)?) => {
+        $crate::testing::assert_structured_eq(
+            &$actual,
+            &$expected,
+            $crate::testing::KeyOrder::Ignore,
+        )
+    };
+}
+
+impl Compare for BareItem {
+    fn compare(&self, expected: &Self, path: &str, _: KeyOrder, output: &mut Vec<String>) {
+        if self != expected {
+            output.push(format!(
+                "{}: expected {}, found {}",
+                display_path(path),
+                describe_bare_item(expected),
+                describe_bare_item(self)
+            ));
+        }
+    }
+}
+
+impl Compare for Parameters {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        compare_maps(self, expected, path, ";", key_order, output);
+    }
+}
+
+impl Compare for Item {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        self.bare_item
+            .compare(&expected.bare_item, path, key_order, output);
+        self.params
+            .compare(&expected.params, path, key_order, output);
+    }
+}
+
+impl Compare for InnerList {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        compare_sequences(&self.items, &expected.items, path, key_order, output);
+        self.params
+            .compare(&expected.params, path, key_order, output);
+    }
+}
+
+impl Compare for ListEntry {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        match (self, expected) {
+            (ListEntry::Item(actual), ListEntry::Item(expected)) => {
+                actual.compare(expected, path, key_order, output)
+            }
+            (ListEntry::InnerList(actual), ListEntry::InnerList(expected)) => {
+                actual.compare(expected, path, key_order, output)
+            }
+            _ => output.push(format!(
+                "{}: expected {}, found {}",
+                display_path(path),
+                describe_list_entry(expected),
+                describe_list_entry(self)
+            )),
+        }
+    }
+}
+
+impl Compare for List {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        compare_sequences(self, expected, path, key_order, output);
+    }
+}
+
+impl Compare for Dictionary {
+    fn compare(&self, expected: &Self, path: &str, key_order: KeyOrder, output: &mut Vec<String>) {
+        compare_maps(self, expected, path, "", key_order, output);
+    }
+}
+
+fn compare_sequences<T: Compare + Describe>(
+    actual: &[T],
+    expected: &[T],
+    path: &str,
+    key_order: KeyOrder,
+    output: &mut Vec<String>,
+) {
+    for (idx, expected_member) in expected.iter().enumerate() {
+        let member_path = format!("{}[{}]", path, idx);
+        match actual.get(idx) {
+            Some(actual_member) => {
+                actual_member.compare(expected_member, &member_path, key_order, output)
+            }
+            None => output.push(format!(
+                "{}: missing, expected {}",
+                member_path,
+                expected_member.describe()
+            )),
+        }
+    }
+    for (idx, actual_member) in actual.iter().enumerate().skip(expected.len()) {
+        output.push(format!(
+            "{}[{}]: unexpected {}",
+            path,
+            idx,
+            actual_member.describe()
+        ));
+    }
+}
+
+fn compare_maps<T: Compare + Describe>(
+    actual: &indexmap::IndexMap<String, T>,
+    expected: &indexmap::IndexMap<String, T>,
+    path: &str,
+    separator: &str,
+    key_order: KeyOrder,
+    output: &mut Vec<String>,
+) {
+    for (key, expected_value) in expected {
+        let member_path = format!("{}{}{}", path, separator, key);
+        match actual.get(key) {
+            Some(actual_value) => {
+                actual_value.compare(expected_value, &member_path, key_order, output)
+            }
+            None => output.push(format!(
+                "{}: missing, expected {}",
+                member_path,
+                expected_value.describe()
+            )),
+        }
+    }
+    for (key, actual_value) in actual {
+        if !expected.contains_key(key) {
+            output.push(format!(
+                "{}{}{}: unexpected {}",
+                path,
+                separator,
+                key,
+                actual_value.describe()
+            ));
+        }
+    }
+
+    let same_keys =
+        actual.len() == expected.len() && actual.keys().all(|k| expected.contains_key(k));
+    if key_order == KeyOrder::Strict && same_keys && !actual.keys().eq(expected.keys()) {
+        let join_keys = |map: &indexmap::IndexMap<String, T>| {
+            map.keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        output.push(format!(
+            "{}: key order differs, expected [{}], found [{}]",
+            display_path(path),
+            join_keys(expected),
+            join_keys(actual)
+        ));
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "value"
+    } else {
+        path
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for BareItem {
+    fn describe(&self) -> String {
+        describe_bare_item(self)
+    }
+}
+
+impl Describe for Item {
+    fn describe(&self) -> String {
+        self.serialize_value()
+            .unwrap_or_else(|_| format!("{:?}", self))
+    }
+}
+
+impl Describe for ListEntry {
+    fn describe(&self) -> String {
+        match self {
+            ListEntry::Item(item) => item.describe(),
+            ListEntry::InnerList(inner_list) => {
+                let list: List = vec![inner_list.clone().into()];
+                list.serialize_value()
+                    .unwrap_or_else(|_| format!("{:?}", inner_list))
+            }
+        }
+    }
+}
+
+fn describe_bare_item(bare_item: &BareItem) -> String {
+    Item::new(bare_item.clone()).describe()
+}
+
+fn describe_list_entry(entry: &ListEntry) -> String {
+    match entry {
+        ListEntry::Item(_) => format!("item {}", entry.describe()),
+        ListEntry::InnerList(_) => format!("inner list {}", entry.describe()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn differences_in_dictionary() -> Result<(), &'static str> {
+        let actual = Parser::parse_dictionary("a=1;q=2, b=(x y), c".as_bytes())?;
+        let expected = Parser::parse_dictionary("a=2;q=2;r, b=(x), d=\"s\"".as_bytes())?;
+        assert_eq!(
+            vec![
+                "a: expected 2, found 1",
+                "a;r: missing, expected ?1",
+                "b[1]: unexpected y",
+                "d: missing, expected \"s\"",
+                "c: unexpected ?1",
+            ],
+            differences(&actual, &expected, KeyOrder::Strict)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn differences_in_key_order() -> Result<(), &'static str> {
+        let actual = Parser::parse_item("1;a;b".as_bytes())?;
+        let expected = Parser::parse_item("1;b;a".as_bytes())?;
+        assert_eq!(
+            vec!["value: key order differs, expected [b, a], found [a, b]"],
+            differences(&actual, &expected, KeyOrder::Strict)
+        );
+        assert!(differences(&actual, &expected, KeyOrder::Ignore).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn differences_in_list_entry_type() -> Result<(), &'static str> {
+        let actual = Parser::parse_list("1, (2)".as_bytes())?;
+        let expected = Parser::parse_list("(1), (2)".as_bytes())?;
+        assert_eq!(
+            vec!["[0]: expected inner list (1), found item 1"],
+            differences(&actual, &expected, KeyOrder::Strict)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "structured field values are not equal:\n  [0]: expected 2, found 1")]
+    fn assert_structured_eq_panics() {
+        let actual = Parser::parse_list("1".as_bytes()).unwrap();
+        let expected = Parser::parse_list("2".as_bytes()).unwrap();
+        crate::assert_structured_eq!(actual, expected);
+    }
+}
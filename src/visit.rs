@@ -0,0 +1,171 @@
+use crate::{BareItem, Dictionary, InnerList, Item, List, ListEntry, Parameters};
+
+/// Callbacks of `VisitMut::visit_mut`, each defaulting to doing nothing.
+pub trait MutVisitor {
+    /// Called with every `Dictionary` member key and parameter key.
+    /// If keys become equal, the member visited last replaces the others, at the position of the one visited first.
+    fn visit_key(&mut self, _key: &mut String) {}
+
+    /// Called with every bare item, including parameter values.
+    fn visit_bare_item(&mut self, _bare_item: &mut BareItem) {}
+
+    /// Called with parameters of every `Item` and `InnerList`, before their keys and values are visited.
+    fn visit_parameters(&mut self, _params: &mut Parameters) {}
+}
+
+/// Traverses structured field value, handing mutable references to its keys, bare items and parameters to `MutVisitor`.
+pub trait VisitMut {
+    /// Visits the value in document order: a member's key, then its bare item or inner list items,
+    /// then its parameters, each followed by their keys and values.
+    /// # Examples
+    /// ```
+    /// # use sfv::{BareItem, MutVisitor, Parser, SerializeValue, VisitMut};
+    /// struct Normalize;
+    ///
+    /// impl MutVisitor for Normalize {
+    ///     fn visit_bare_item(&mut self, bare_item: &mut BareItem) {
+    ///         match bare_item {
+    ///             BareItem::Integer(value) => *value = (*value).min(100),
+    ///             BareItem::Token(value) => *value = value.to_lowercase(),
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut dict = Parser::parse_dictionary("a=500;q=Low, b=(GZIP 7)".as_bytes()).unwrap();
+    /// dict.visit_mut(&mut Normalize);
+    /// assert_eq!(dict.serialize_value().unwrap(), "a=100;q=low, b=(gzip 7)");
+    /// ```
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V);
+}
+
+impl VisitMut for Parameters {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        visitor.visit_parameters(self);
+        // Keys of `IndexMap` can't be changed in place, so entries are reinserted in order
+        let params = std::mem::take(self);
+        for (mut key, mut value) in params {
+            visitor.visit_key(&mut key);
+            visitor.visit_bare_item(&mut value);
+            self.insert(key, value);
+        }
+    }
+}
+
+impl VisitMut for Item {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        visitor.visit_bare_item(&mut self.bare_item);
+        self.params.visit_mut(visitor);
+    }
+}
+
+impl VisitMut for InnerList {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        for item in self.items.iter_mut() {
+            item.visit_mut(visitor);
+        }
+        self.params.visit_mut(visitor);
+    }
+}
+
+impl VisitMut for ListEntry {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        match self {
+            ListEntry::Item(item) => item.visit_mut(visitor),
+            ListEntry::InnerList(inner_list) => inner_list.visit_mut(visitor),
+        }
+    }
+}
+
+impl VisitMut for List {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        for member in self.iter_mut() {
+            member.visit_mut(visitor);
+        }
+    }
+}
+
+impl VisitMut for Dictionary {
+    fn visit_mut<V: MutVisitor>(&mut self, visitor: &mut V) {
+        let dict = std::mem::take(self);
+        for (mut key, mut member) in dict {
+            visitor.visit_key(&mut key);
+            member.visit_mut(visitor);
+            self.insert(key, member);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, SFVResult, SerializeValue};
+
+    #[derive(Default)]
+    struct Recorder {
+        visited: Vec<String>,
+    }
+
+    impl MutVisitor for Recorder {
+        fn visit_key(&mut self, key: &mut String) {
+            self.visited.push(format!("key {}", key));
+            key.insert_str(0, "x-");
+        }
+
+        fn visit_bare_item(&mut self, bare_item: &mut BareItem) {
+            self.visited.push(format!("item {:?}", bare_item));
+        }
+
+        fn visit_parameters(&mut self, params: &mut Parameters) {
+            self.visited.push(format!("params {}", params.len()));
+        }
+    }
+
+    #[test]
+    fn visit_mut_in_document_order() -> SFVResult<()> {
+        let mut dict = Parser::parse_dictionary("a=1;p=2, b=(3;q);r".as_bytes())?;
+        let mut recorder = Recorder::default();
+        dict.visit_mut(&mut recorder);
+        assert_eq!(
+            vec![
+                "key a",
+                "item Integer(1)",
+                "params 1",
+                "key p",
+                "item Integer(2)",
+                "key b",
+                "item Integer(3)",
+                "params 1",
+                "key q",
+                "item Boolean(true)",
+                "params 1",
+                "key r",
+                "item Boolean(true)",
+            ],
+            recorder.visited
+        );
+        assert_eq!("x-a=1;x-p=2, x-b=(3;x-q);x-r", dict.serialize_value()?);
+        Ok(())
+    }
+
+    #[test]
+    fn visit_mut_list() -> SFVResult<()> {
+        struct Negate;
+        impl MutVisitor for Negate {
+            fn visit_bare_item(&mut self, bare_item: &mut BareItem) {
+                if let BareItem::Integer(value) = bare_item {
+                    *value = -*value;
+                }
+            }
+
+            fn visit_parameters(&mut self, params: &mut Parameters) {
+                params.shift_remove("drop");
+            }
+        }
+
+        let mut list = Parser::parse_list("1;drop;keep=2, (3 4);drop".as_bytes())?;
+        list.visit_mut(&mut Negate);
+        assert_eq!("-1;keep=-2, (-3 -4)", list.serialize_value()?);
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use sfv::fields::PolicyWithReporting;
 use sfv::{ListEntry, Parser};
 use std::error::Error;
 
@@ -32,3 +33,22 @@ fn test_report_to_header() -> Result<(), Box<dyn Error>> {
     }
     Err("unexpected endpoint value".into())
 }
+
+#[test]
+fn test_report_to_header_with_policy_helper() -> Result<(), Box<dyn Error>> {
+    let coep = PolicyWithReporting::parse(br#"require-corp; report-to="coep""#)?;
+    assert_eq!(coep.value, "require-corp");
+
+    let endpoints = Parser::parse_dictionary(br#"coep="https://example.com/coep""#)?;
+    let endpoint = coep
+        .report_to
+        .as_deref()
+        .and_then(|name| endpoints.get(name));
+    match endpoint {
+        Some(ListEntry::Item(item)) => {
+            assert_eq!(item.bare_item.as_str(), Some("https://example.com/coep"))
+        }
+        _ => return Err("unexpected endpoint value".into()),
+    }
+    Ok(())
+}
@@ -46,11 +46,9 @@ fn run_test_case(test_case: &TestData) -> Result<(), Box<dyn Error>> {
         .join(", ");
 
     let actual_result = match test_case.header_type.as_str() {
-        "item" => Parser::parse_item(input.as_bytes()).map(|itm| FieldType::Item(itm)),
-        "list" => Parser::parse_list(input.as_bytes()).map(|lst| FieldType::List(lst)),
-        "dictionary" => {
-            Parser::parse_dictionary(input.as_bytes()).map(|dict| FieldType::Dict(dict))
-        }
+        "item" => Parser::parse_item(input.as_bytes()).map(FieldType::Item),
+        "list" => Parser::parse_list(input.as_bytes()).map(FieldType::List),
+        "dictionary" => Parser::parse_dictionary(input.as_bytes()).map(FieldType::Dict),
         _ => return Err("run_test_case: unexpected field value type in test case".into()),
     };
 
@@ -129,7 +127,7 @@ fn build_expected_field_value(test_case: &TestData) -> Result<FieldType, Box<dyn
             let dict = build_dict(expected_value)?;
             Ok(FieldType::Dict(dict))
         }
-        _ => return Err("unknown field type".into()),
+        _ => Err("unknown field type".into()),
     }
 }
 